@@ -0,0 +1,52 @@
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+/// HP of damage dealt by a Black Bomb explosion, it can never destroy a unit.
+pub const BLACK_BOMB_DAMAGE: u32 = 50;
+
+/// Units within this distance of the Black Bomb are hit by the explosion.
+pub const BLACK_BOMB_RADIUS: u32 = 3;
+
+
+impl GameState {
+    /// The Black Bomb explodes, it is removed from the map.
+    ///
+    /// Every unit within 3 tiles takes 50 HP of damage, including the player's own units,
+    /// but it cannot go below 1 HP. Units inside of transports are not affected.
+    pub fn explode_unit(&mut self, id: UnitId) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        if !unit.class.can_explode() || unit.is_loaded() {
+            return Err(RuleError::CannotExplode(id));
+        }
+
+        let coord = unit.coord;
+
+        self.remove_unit(id);
+
+        let mut events = vec![Event::Exploded { unit: id, coord }];
+
+        for (id, unit) in self.units.iter_mut() {
+            if !unit.is_loaded() && unit.coord.distance(&coord) <= BLACK_BOMB_RADIUS {
+                let damage = BLACK_BOMB_DAMAGE.min(unit.hp - 1);
+
+                if damage > 0 {
+                    unit.hp -= damage;
+                    events.push(Event::BombHit { unit: *id, damage });
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,289 @@
+use crate::movement::{MovementType};
+use crate::unit::{UnitClass};
+use crate::structure::{StructureAction, STRUCTURE_HP};
+
+
+/// The direction that a cannon is facing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: &[Self] = &[
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+    ];
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildingClass {
+    HQ1, // Orange Star
+    HQ2, // Blue Moon
+    HQ3, // Green Earth
+    HQ4, // Yellow Comet
+    HQ5, // Black Hole
+    City,
+    Base,
+    Airport,
+    Port,
+    ComTower,
+    Lab,
+    MissileSilo,
+    MissileSiloEmpty,
+    BlackCrystal,
+    Laser,
+    Minicannon { direction: Direction, grass: bool },
+    Volcano,
+    BlackOnyx, // Flying Fortress
+    Fortress,
+    BlackArmageddon,
+    BlackCannon { direction: Direction },
+    BlackObelisk,
+}
+
+impl BuildingClass {
+    pub const ALL: &[Self] = &[
+        Self::HQ1,
+        Self::HQ2,
+        Self::HQ3,
+        Self::HQ4,
+        Self::HQ5,
+        Self::City,
+        Self::Base,
+        Self::Airport,
+        Self::Port,
+        Self::ComTower,
+        Self::Lab,
+        Self::MissileSilo,
+        Self::MissileSiloEmpty,
+        Self::BlackCrystal,
+        Self::Laser,
+        Self::Minicannon { direction: Direction::Up, grass: false },
+        Self::Minicannon { direction: Direction::Down, grass: false },
+        Self::Minicannon { direction: Direction::Left, grass: false },
+        Self::Minicannon { direction: Direction::Right, grass: false },
+        Self::Minicannon { direction: Direction::Up, grass: true },
+        Self::Minicannon { direction: Direction::Down, grass: true },
+        Self::Minicannon { direction: Direction::Left, grass: true },
+        Self::Minicannon { direction: Direction::Right, grass: true },
+        Self::Volcano,
+        Self::BlackOnyx,
+        Self::Fortress,
+        Self::BlackArmageddon,
+        Self::BlackCannon { direction: Direction::Up },
+        Self::BlackCannon { direction: Direction::Down },
+        Self::BlackCannon { direction: Direction::Left },
+        Self::BlackCannon { direction: Direction::Right },
+        Self::BlackObelisk,
+    ];
+
+    pub fn can_have_nation(&self) -> bool {
+        !matches!(self, Self::MissileSilo | Self::MissileSiloEmpty)
+    }
+
+    /// Structures are owned by a nation, but they cannot be captured.
+    #[inline]
+    pub fn can_capture(&self) -> bool {
+        self.can_have_nation() && !self.is_structure()
+    }
+
+    /// Black Hole structures which block movement and act at the start of their owner's turn.
+    pub fn is_structure(&self) -> bool {
+        matches!(self,
+            Self::BlackCrystal |
+            Self::Laser |
+            Self::Minicannon { .. } |
+            Self::Volcano |
+            Self::BlackOnyx |
+            Self::Fortress |
+            Self::BlackArmageddon |
+            Self::BlackCannon { .. } |
+            Self::BlackObelisk)
+    }
+
+    /// The number of tiles (width, height) covered by the building, starting at the top-left tile.
+    pub fn size(&self) -> (u32, u32) {
+        match self {
+            Self::Volcano |
+            Self::BlackOnyx |
+            Self::Fortress |
+            Self::BlackArmageddon |
+            Self::BlackCannon { .. } => (3, 3),
+
+            Self::BlackObelisk => (2, 2),
+
+            _ => (1, 1),
+        }
+    }
+
+    /// The direction that the cannon is facing.
+    pub fn direction(&self) -> Option<Direction> {
+        match self {
+            Self::Minicannon { direction, .. } |
+            Self::BlackCannon { direction } => Some(*direction),
+            _ => None,
+        }
+    }
+
+    /// Maximum HP of the structure, or `None` if it cannot be attacked.
+    ///
+    /// Volcanoes cannot be destroyed.
+    pub fn max_hp(&self) -> Option<u32> {
+        if self.is_structure() && *self != Self::Volcano {
+            Some(STRUCTURE_HP)
+
+        } else {
+            None
+        }
+    }
+
+    /// What the structure does at the start of its owner's turn.
+    pub fn action(&self) -> StructureAction {
+        match self {
+            Self::BlackCrystal => StructureAction::Heal { hp: 20, range: None },
+            Self::BlackObelisk => StructureAction::Heal { hp: 20, range: Some(3) },
+            Self::Laser => StructureAction::Beam { damage: 50 },
+            Self::Minicannon { .. } => StructureAction::Cannon { range: 3, damage: 30 },
+            Self::BlackCannon { .. } => StructureAction::Cannon { range: 8, damage: 30 },
+            Self::Volcano => StructureAction::Eruption { shots: 6, range: 8, damage: 50 },
+            Self::BlackOnyx => StructureAction::Missile { radius: 2, damage: 30 },
+            Self::BlackArmageddon => StructureAction::Missile { radius: 2, damage: 50 },
+            _ => StructureAction::None,
+        }
+    }
+
+    pub fn is_hq(&self) -> bool {
+        matches!(self, Self::HQ1 | Self::HQ2 | Self::HQ3 | Self::HQ4 | Self::HQ5)
+    }
+
+    /// Each star gives +10% defense, scaled by the non-rounded HP of the defender.
+    pub fn defense_stars(&self) -> u32 {
+        match self {
+            Self::HQ1 | Self::HQ2 | Self::HQ3 | Self::HQ4 | Self::HQ5 => 4,
+
+            Self::City |
+            Self::Base |
+            Self::Airport |
+            Self::Port |
+            Self::ComTower |
+            Self::Lab |
+            Self::MissileSilo |
+            Self::MissileSiloEmpty => 3,
+
+            // Units cannot stand on structures
+            _ => 0,
+        }
+    }
+
+    /// Funds given to the owner at the start of their turn.
+    pub fn income(&self) -> u32 {
+        match self {
+            Self::HQ1 |
+            Self::HQ2 |
+            Self::HQ3 |
+            Self::HQ4 |
+            Self::HQ5 |
+            Self::City |
+            Self::Base |
+            Self::Airport |
+            Self::Port => 1000,
+
+            _ => 0,
+        }
+    }
+
+    /// Units which can be built by the owner of this building.
+    pub fn build_list(&self) -> &'static [UnitClass] {
+        match self {
+            Self::Base => &[
+                UnitClass::Infantry,
+                UnitClass::Mech,
+                UnitClass::Recon,
+                UnitClass::APC,
+                UnitClass::Artillery,
+                UnitClass::Tank,
+                UnitClass::AntiAir,
+                UnitClass::Missile,
+                UnitClass::Rocket,
+                UnitClass::MediumTank,
+                UnitClass::Piperunner,
+                UnitClass::Neotank,
+                UnitClass::MegaTank,
+            ],
+
+            Self::Airport => &[
+                UnitClass::BCopter,
+                UnitClass::TCopter,
+                UnitClass::Fighter,
+                UnitClass::Bomber,
+                UnitClass::Stealth,
+                UnitClass::BlackBomb,
+            ],
+
+            Self::Port => &[
+                UnitClass::Battleship,
+                UnitClass::Cruiser,
+                UnitClass::Submarine,
+                UnitClass::Lander,
+                UnitClass::Carrier,
+                UnitClass::BlackBoat,
+            ],
+
+            _ => &[],
+        }
+    }
+
+    /// Whether a unit standing on this building gets repaired and resupplied by the owner.
+    pub fn repairs(&self, class: UnitClass) -> bool {
+        use MovementType::*;
+
+        let movement = class.movement_type();
+
+        match self {
+            Self::HQ1 |
+            Self::HQ2 |
+            Self::HQ3 |
+            Self::HQ4 |
+            Self::HQ5 |
+            Self::City |
+            Self::Base => matches!(movement, Foot | Boots | Treads | Tires | Pipe),
+
+            Self::Airport => matches!(movement, Air),
+
+            Self::Port => matches!(movement, Sea | Lander),
+
+            _ => false,
+        }
+    }
+
+    /// Buildings replace the movement cost of the terrain underneath them.
+    pub fn movement_cost(&self, movement: MovementType) -> Option<u32> {
+        use MovementType::*;
+
+        // Structures block every unit, including air units
+        if self.is_structure() {
+            return None;
+        }
+
+        match movement {
+            Foot | Boots | Treads | Tires | Air => Some(1),
+
+            Sea | Lander => match self {
+                Self::Port => Some(1),
+                _ => None,
+            },
+
+            Pipe => match self {
+                Self::Base => Some(1),
+                _ => None,
+            },
+        }
+    }
+}
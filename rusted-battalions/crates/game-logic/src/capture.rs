@@ -0,0 +1,117 @@
+use crate::nation::{Nation};
+use crate::map::{Coord};
+use crate::building::{BuildingClass};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+use crate::victory::{Victory};
+
+
+/// Capture points of a property, this is 20.0 points stored in tenths.
+///
+/// Every turn the capturing unit reduces the points by its non-rounded HP,
+/// so a 55 HP infantry reduces the points by 5.5
+pub const CAPTURE_POINTS: u32 = 200;
+
+
+/// A property which is partially captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capture {
+    pub unit: UnitId,
+
+    /// Remaining capture points, in tenths.
+    pub points: u32,
+}
+
+
+impl GameState {
+    /// Returns the capture progress of the property.
+    pub fn capture_at(&self, coord: &Coord) -> Option<&Capture> {
+        self.captures.get(coord)
+    }
+
+    /// Resets the capture progress of any property that the unit is capturing.
+    pub(crate) fn reset_capture(&mut self, id: UnitId) {
+        self.captures.retain(|_, capture| capture.unit != id);
+    }
+
+    /// The unit captures the property that it is standing on.
+    ///
+    /// When the capture points reach 0 the property changes owner. If the property
+    /// is an HQ then the previous owner is defeated and all of their properties are
+    /// turned over to the capturing player. Capturing a Lab can also defeat its owner,
+    /// depending on the [`Victory`] rules.
+    pub fn capture_property(&mut self, id: UnitId) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        let coord = unit.coord;
+        let nation = unit.nation;
+        let hp = unit.hp;
+
+        let tile = self.map.get(&coord).ok_or(RuleError::CannotCapture(id))?;
+
+        let building = match tile.building {
+            Some(building) if unit.class.can_capture() && !unit.is_loaded() && building.can_capture() && !tile.owner.map(|owner| self.same_team(owner, nation)).unwrap_or(false) => building,
+            _ => return Err(RuleError::CannotCapture(id)),
+        };
+
+        let previous = tile.owner;
+
+        self.units.get_mut(&id).unwrap().waited = true;
+
+        let capture = self.captures.entry(coord).or_insert(Capture {
+            unit: id,
+            points: CAPTURE_POINTS,
+        });
+
+        // Only the unit standing on the property can be capturing it
+        if capture.unit != id {
+            *capture = Capture { unit: id, points: CAPTURE_POINTS };
+        }
+
+        capture.points = capture.points.saturating_sub(hp);
+
+        let points = capture.points;
+
+        let mut events = vec![Event::CaptureProgress { unit: id, coord, points }];
+
+        if points == 0 {
+            self.captures.remove(&coord);
+
+            self.map.get_mut(&coord).unwrap().owner = Some(nation);
+
+            events.push(Event::Captured { unit: id, coord, nation });
+
+            if let Some(previous) = previous {
+                if building.is_hq() && self.rules.victory.contains(&Victory::Hq) {
+                    self.defeat_by_hq(previous, nation, &mut events);
+
+                } else if building == BuildingClass::Lab && self.rules.victory.contains(&Victory::Lab) {
+                    self.defeat_player(previous, &mut events);
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// When an HQ is captured, the previous owner loses all of their properties and units.
+    fn defeat_by_hq(&mut self, loser: Nation, winner: Nation, events: &mut Vec<Event>) {
+        for tile in self.map.tiles_mut() {
+            if tile.owner == Some(loser) {
+                tile.owner = Some(winner);
+                events.push(Event::TurnedOver { coord: tile.coord, nation: winner });
+            }
+        }
+
+        self.defeat_player(loser, events);
+    }
+}
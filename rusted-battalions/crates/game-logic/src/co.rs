@@ -0,0 +1,958 @@
+use crate::nation::{Nation};
+use crate::map::{Tile};
+use crate::terrain::{TerrainClass};
+use crate::building::{BuildingClass};
+use crate::unit::{UnitClass};
+use crate::damage::{Attacker, Defender, Target};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+use crate::rules::{CoMode};
+use crate::weather::{Weather};
+
+
+/// Funds value of a single power star, before it is increased by using powers.
+pub const STAR_VALUE: u32 = 9000;
+
+/// After this many uses the stars stop getting more expensive.
+const MAX_USES: u32 = 10;
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Co {
+    Andy,
+    Max,
+    Kanbei,
+    Flak,
+    Jugger,
+    Sonja,
+    Koal,
+    Hachi,
+    Colin,
+    Javier,
+    Lash,
+    Grimm,
+    Olaf,
+}
+
+impl Co {
+    pub const ALL: &[Self] = &[
+        Self::Andy,
+        Self::Max,
+        Self::Kanbei,
+        Self::Flak,
+        Self::Jugger,
+        Self::Sonja,
+        Self::Koal,
+        Self::Hachi,
+        Self::Colin,
+        Self::Javier,
+        Self::Lash,
+        Self::Grimm,
+        Self::Olaf,
+    ];
+
+    pub fn definition(&self) -> &'static CoDefinition {
+        match self {
+            Self::Andy => &ANDY,
+            Self::Max => &MAX,
+            Self::Kanbei => &KANBEI,
+            Self::Flak => &FLAK,
+            Self::Jugger => &JUGGER,
+            Self::Sonja => &SONJA,
+            Self::Koal => &KOAL,
+            Self::Hachi => &HACHI,
+            Self::Colin => &COLIN,
+            Self::Javier => &JAVIER,
+            Self::Lash => &LASH,
+            Self::Grimm => &GRIMM,
+            Self::Olaf => &OLAF,
+        }
+    }
+}
+
+
+/// Which units a [`Modifier`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitFilter {
+    All,
+
+    /// Direct units which are not Infantry or Mech.
+    DirectVehicle,
+
+    Indirect,
+}
+
+impl UnitFilter {
+    pub fn matches(&self, class: UnitClass) -> bool {
+        match self {
+            Self::All => true,
+            Self::DirectVehicle => !class.is_indirect() && !matches!(class, UnitClass::Infantry | UnitClass::Mech),
+            Self::Indirect => class.is_indirect(),
+        }
+    }
+}
+
+
+/// Which tiles a [`Modifier`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerrainFilter {
+    Any,
+
+    /// Roads and bridges, ruined roads do not count.
+    Road,
+}
+
+impl TerrainFilter {
+    /// Modifiers which need a specific terrain never apply if there is no tile.
+    pub fn matches(&self, tile: Option<&Tile>) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Road => match tile {
+                Some(tile) => tile.building.is_none() && matches!(tile.class, TerrainClass::Road { ruins: false } | TerrainClass::Bridge { .. }),
+                None => false,
+            },
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Attack percentage, negative numbers are penalties.
+    Attack(i32),
+
+    /// Defense percentage, negative numbers are penalties.
+    Defense(i32),
+
+    /// Defense percentage against indirect attacks.
+    IndirectDefense(i32),
+
+    /// Attack percentage for every terrain star of the attacker's tile.
+    AttackPerStar(i32),
+
+    /// Attack percentage for every 1000 funds the player has.
+    AttackPerThousandFunds(i32),
+
+    Movement(i32),
+
+    /// Changes the maximum range of indirect units.
+    Range(i32),
+
+    Vision(i32),
+
+    /// Replaces the maximum good luck.
+    GoodLuck(u32),
+
+    /// Replaces the maximum bad luck.
+    BadLuck(u32),
+
+    /// Replaces the unit cost percentage.
+    Cost(u32),
+
+    /// Comtowers also give +10% defense.
+    ComtowerDefense,
+
+    /// Terrain stars are doubled when defending, they still scale with HP.
+    DoubleTerrainStars,
+
+    /// Movement costs are not changed by the weather.
+    IgnoreWeather(Weather),
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Modifier {
+    pub units: UnitFilter,
+    pub terrain: TerrainFilter,
+    pub effect: Effect,
+}
+
+impl Modifier {
+    const fn all(effect: Effect) -> Self {
+        Self { units: UnitFilter::All, terrain: TerrainFilter::Any, effect }
+    }
+
+    const fn units(units: UnitFilter, effect: Effect) -> Self {
+        Self { units, terrain: TerrainFilter::Any, effect }
+    }
+
+    const fn terrain(terrain: TerrainFilter, effect: Effect) -> Self {
+        Self { units: UnitFilter::All, terrain, effect }
+    }
+}
+
+
+/// One-time effects which happen when a power is activated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instant {
+    /// Heals all of the player's units.
+    Heal(u32),
+
+    /// Multiplies the player's funds by a percentage.
+    Funds(u32),
+
+    /// Changes the weather until the player's next turn.
+    Weather(Weather),
+
+    /// Damages all enemy units, but it cannot destroy them.
+    Damage(u32),
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerKind {
+    Power,
+    SuperPower,
+
+    /// Dual Strike, both COs use their Super CO Power on two consecutive turns.
+    Tag,
+}
+
+
+#[derive(Debug)]
+pub struct PowerDefinition {
+    pub name: &'static str,
+
+    /// Number of stars needed to activate the power.
+    pub stars: u32,
+
+    /// Added on top of the day-to-day modifiers while the power is active.
+    pub modifiers: &'static [Modifier],
+
+    pub instant: &'static [Instant],
+}
+
+
+#[derive(Debug)]
+pub struct CoDefinition {
+    pub name: &'static str,
+
+    /// Day-to-day modifiers which are always active.
+    pub modifiers: &'static [Modifier],
+
+    pub power: PowerDefinition,
+    pub super_power: PowerDefinition,
+}
+
+impl CoDefinition {
+    pub fn power(&self, kind: PowerKind) -> &PowerDefinition {
+        match kind {
+            PowerKind::Power => &self.power,
+            PowerKind::SuperPower | PowerKind::Tag => &self.super_power,
+        }
+    }
+}
+
+
+static ANDY: CoDefinition = CoDefinition {
+    name: "Andy",
+    modifiers: &[],
+    power: PowerDefinition {
+        name: "Hyper Repair",
+        stars: 3,
+        modifiers: &[],
+        instant: &[Instant::Heal(20)],
+    },
+    super_power: PowerDefinition {
+        name: "Hyper Upgrade",
+        stars: 6,
+        modifiers: &[
+            Modifier::all(Effect::Attack(10)),
+            Modifier::all(Effect::Movement(1)),
+        ],
+        instant: &[Instant::Heal(50)],
+    },
+};
+
+static MAX: CoDefinition = CoDefinition {
+    name: "Max",
+    modifiers: &[
+        Modifier::units(UnitFilter::DirectVehicle, Effect::Attack(20)),
+        Modifier::units(UnitFilter::Indirect, Effect::Attack(-10)),
+        Modifier::units(UnitFilter::Indirect, Effect::Range(-1)),
+    ],
+    power: PowerDefinition {
+        name: "Max Force",
+        stars: 3,
+        modifiers: &[
+            Modifier::units(UnitFilter::DirectVehicle, Effect::Attack(10)),
+            Modifier::units(UnitFilter::DirectVehicle, Effect::Movement(1)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Max Blast",
+        stars: 6,
+        modifiers: &[
+            Modifier::units(UnitFilter::DirectVehicle, Effect::Attack(30)),
+            Modifier::units(UnitFilter::DirectVehicle, Effect::Movement(2)),
+        ],
+        instant: &[],
+    },
+};
+
+static KANBEI: CoDefinition = CoDefinition {
+    name: "Kanbei",
+    modifiers: &[
+        Modifier::all(Effect::Attack(20)),
+        Modifier::all(Effect::Defense(20)),
+        Modifier::all(Effect::Cost(120)),
+    ],
+    power: PowerDefinition {
+        name: "Morale Boost",
+        stars: 4,
+        modifiers: &[
+            Modifier::all(Effect::Attack(10)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Samurai Spirit",
+        stars: 7,
+        modifiers: &[
+            Modifier::all(Effect::Attack(10)),
+            Modifier::all(Effect::Defense(20)),
+        ],
+        instant: &[],
+    },
+};
+
+static FLAK: CoDefinition = CoDefinition {
+    name: "Flak",
+    modifiers: &[
+        Modifier::all(Effect::GoodLuck(15)),
+        Modifier::all(Effect::BadLuck(10)),
+    ],
+    power: PowerDefinition {
+        name: "Brute Force",
+        stars: 3,
+        modifiers: &[
+            Modifier::all(Effect::GoodLuck(25)),
+            Modifier::all(Effect::BadLuck(20)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Barbaric Blow",
+        stars: 6,
+        modifiers: &[
+            Modifier::all(Effect::GoodLuck(40)),
+            Modifier::all(Effect::BadLuck(30)),
+        ],
+        instant: &[],
+    },
+};
+
+static JUGGER: CoDefinition = CoDefinition {
+    name: "Jugger",
+    modifiers: &[
+        Modifier::all(Effect::GoodLuck(30)),
+        Modifier::all(Effect::BadLuck(15)),
+    ],
+    power: PowerDefinition {
+        name: "Overclock",
+        stars: 3,
+        modifiers: &[
+            Modifier::all(Effect::GoodLuck(55)),
+            Modifier::all(Effect::BadLuck(25)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "System Crash",
+        stars: 7,
+        modifiers: &[
+            Modifier::all(Effect::GoodLuck(95)),
+            Modifier::all(Effect::BadLuck(45)),
+        ],
+        instant: &[],
+    },
+};
+
+static SONJA: CoDefinition = CoDefinition {
+    name: "Sonja",
+    modifiers: &[
+        Modifier::all(Effect::Vision(1)),
+        Modifier::all(Effect::BadLuck(9)),
+    ],
+    power: PowerDefinition {
+        name: "Enhanced Vision",
+        stars: 3,
+        modifiers: &[
+            Modifier::all(Effect::Vision(1)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Counter Break",
+        stars: 5,
+        modifiers: &[
+            Modifier::all(Effect::Vision(1)),
+        ],
+        instant: &[],
+    },
+};
+
+static KOAL: CoDefinition = CoDefinition {
+    name: "Koal",
+    modifiers: &[
+        Modifier::terrain(TerrainFilter::Road, Effect::Attack(10)),
+    ],
+    power: PowerDefinition {
+        name: "Forced March",
+        stars: 3,
+        modifiers: &[
+            Modifier::terrain(TerrainFilter::Road, Effect::Attack(10)),
+            Modifier::all(Effect::Movement(1)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Trail of Woe",
+        stars: 5,
+        modifiers: &[
+            Modifier::terrain(TerrainFilter::Road, Effect::Attack(20)),
+            Modifier::all(Effect::Movement(2)),
+        ],
+        instant: &[],
+    },
+};
+
+static HACHI: CoDefinition = CoDefinition {
+    name: "Hachi",
+    modifiers: &[
+        Modifier::all(Effect::Cost(90)),
+    ],
+    power: PowerDefinition {
+        name: "Barter",
+        stars: 3,
+        modifiers: &[
+            Modifier::all(Effect::Cost(50)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Merchant Union",
+        stars: 5,
+        modifiers: &[
+            Modifier::all(Effect::Cost(50)),
+        ],
+        instant: &[],
+    },
+};
+
+static COLIN: CoDefinition = CoDefinition {
+    name: "Colin",
+    modifiers: &[
+        Modifier::all(Effect::Attack(-10)),
+        Modifier::all(Effect::Cost(80)),
+    ],
+    power: PowerDefinition {
+        name: "Gold Rush",
+        stars: 2,
+        modifiers: &[],
+        instant: &[Instant::Funds(150)],
+    },
+    super_power: PowerDefinition {
+        name: "Power of Money",
+        stars: 6,
+        modifiers: &[
+            Modifier::all(Effect::AttackPerThousandFunds(3)),
+        ],
+        instant: &[],
+    },
+};
+
+static JAVIER: CoDefinition = CoDefinition {
+    name: "Javier",
+    modifiers: &[
+        Modifier::all(Effect::IndirectDefense(20)),
+        Modifier::all(Effect::ComtowerDefense),
+    ],
+    power: PowerDefinition {
+        name: "Tower Shield",
+        stars: 3,
+        modifiers: &[
+            Modifier::all(Effect::IndirectDefense(20)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Tower of Power",
+        stars: 6,
+        modifiers: &[
+            Modifier::all(Effect::IndirectDefense(40)),
+        ],
+        instant: &[],
+    },
+};
+
+static LASH: CoDefinition = CoDefinition {
+    name: "Lash",
+    modifiers: &[
+        Modifier::all(Effect::AttackPerStar(10)),
+    ],
+    power: PowerDefinition {
+        name: "Terrain Tactics",
+        stars: 4,
+        modifiers: &[
+            Modifier::all(Effect::DoubleTerrainStars),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Prime Tactics",
+        stars: 7,
+        modifiers: &[
+            Modifier::all(Effect::AttackPerStar(10)),
+            Modifier::all(Effect::DoubleTerrainStars),
+        ],
+        instant: &[],
+    },
+};
+
+static GRIMM: CoDefinition = CoDefinition {
+    name: "Grimm",
+    modifiers: &[
+        Modifier::all(Effect::Attack(30)),
+        Modifier::all(Effect::Defense(-20)),
+    ],
+    power: PowerDefinition {
+        name: "Knucklebuster",
+        stars: 3,
+        modifiers: &[
+            Modifier::all(Effect::Attack(20)),
+        ],
+        instant: &[],
+    },
+    super_power: PowerDefinition {
+        name: "Haymaker",
+        stars: 6,
+        modifiers: &[
+            Modifier::all(Effect::Attack(50)),
+        ],
+        instant: &[],
+    },
+};
+
+static OLAF: CoDefinition = CoDefinition {
+    name: "Olaf",
+    modifiers: &[
+        Modifier::all(Effect::IgnoreWeather(Weather::Snow)),
+    ],
+    power: PowerDefinition {
+        name: "Blizzard",
+        stars: 3,
+        modifiers: &[],
+        instant: &[Instant::Weather(Weather::Snow)],
+    },
+    super_power: PowerDefinition {
+        name: "Winter Fury",
+        stars: 7,
+        modifiers: &[],
+        instant: &[Instant::Weather(Weather::Snow), Instant::Damage(20)],
+    },
+};
+
+
+/// Splits a signed percentage into a bonus and a penalty.
+fn split(value: i32) -> (u32, u32) {
+    if value >= 0 {
+        (value as u32, 0)
+
+    } else {
+        (0, value.unsigned_abs())
+    }
+}
+
+fn adjust(value: u32, change: i32) -> u32 {
+    (value as i32 + change).max(0) as u32
+}
+
+
+/// The CO of a player, along with their power meter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerCo {
+    /// The active CO.
+    pub co: Co,
+
+    /// The second CO in tag matches, they swap places with the active CO.
+    pub partner: Option<Co>,
+
+    /// Charge of the power meter, in funds.
+    ///
+    /// In tag matches the meter is shared by both COs.
+    pub meter: u32,
+
+    /// The power which is currently active.
+    pub power: Option<PowerKind>,
+
+    /// Number of times a power was activated, which makes the stars more expensive.
+    pub uses: u32,
+
+    /// Whether the player is on the extra turn given by a Dual Strike.
+    pub tag_turn: bool,
+}
+
+impl PlayerCo {
+    pub fn new(co: Co) -> Self {
+        Self {
+            co,
+            partner: None,
+            meter: 0,
+            power: None,
+            uses: 0,
+            tag_turn: false,
+        }
+    }
+
+    /// Creates a tag-team of two COs, the first CO is active.
+    pub fn tag(co: Co, partner: Co) -> Self {
+        Self {
+            partner: Some(partner),
+            ..Self::new(co)
+        }
+    }
+
+    #[inline]
+    pub fn definition(&self) -> &'static CoDefinition {
+        self.co.definition()
+    }
+
+    /// The day-to-day modifiers, followed by the modifiers of the active power.
+    ///
+    /// During a Dual Strike the partner's day-to-day modifiers are combined with
+    /// the active CO's modifiers.
+    fn modifiers(&self) -> impl Iterator<Item = &'static Modifier> {
+        let definition = self.definition();
+
+        let partner = match (self.power, self.partner) {
+            (Some(PowerKind::Tag), Some(partner)) => partner.definition().modifiers,
+            _ => &[],
+        };
+
+        let power = match self.power {
+            Some(kind) => definition.power(kind).modifiers,
+            None => &[],
+        };
+
+        partner.iter().chain(definition.modifiers.iter()).chain(power.iter())
+    }
+
+    /// Effects which apply to the unit class standing on the tile.
+    fn effects<'a>(&self, class: UnitClass, tile: Option<&'a Tile>) -> impl Iterator<Item = Effect> + 'a {
+        self.modifiers()
+            .filter(move |modifier| modifier.units.matches(class) && modifier.terrain.matches(tile))
+            .map(|modifier| modifier.effect)
+    }
+
+    /// Funds value of a single star, it goes up by 20% every time a power is used.
+    pub fn star_value(&self) -> u32 {
+        STAR_VALUE + (STAR_VALUE / 5) * self.uses.min(MAX_USES)
+    }
+
+    /// A Dual Strike needs the stars of both Super CO Powers.
+    pub fn power_cost(&self, kind: PowerKind) -> u32 {
+        let stars = match (kind, self.partner) {
+            (PowerKind::Tag, Some(partner)) => self.definition().super_power.stars + partner.definition().super_power.stars,
+            _ => self.definition().power(kind).stars,
+        };
+
+        stars * self.star_value()
+    }
+
+    /// The meter is full when it can activate the Super CO Power, or the Dual Strike in tag matches.
+    pub fn max_meter(&self) -> u32 {
+        if self.partner.is_some() {
+            self.power_cost(PowerKind::Tag)
+
+        } else {
+            self.power_cost(PowerKind::SuperPower)
+        }
+    }
+
+    pub fn can_activate(&self, kind: PowerKind) -> bool {
+        let tag = kind == PowerKind::Tag;
+
+        self.power.is_none() && (!tag || self.partner.is_some()) && self.meter >= self.power_cost(kind)
+    }
+
+    /// Charges the meter with the funds value of the damage.
+    ///
+    /// Damage taken charges twice as much as damage dealt. The meter does
+    /// not charge while a power is active.
+    pub fn charge(&mut self, dealt: u32, taken: u32) {
+        if self.power.is_none() {
+            self.meter = (self.meter + dealt / 2 + taken).min(self.max_meter());
+        }
+    }
+
+    /// Attack percentage for the unit, not including the +10% from powers.
+    pub fn attack(&self, class: UnitClass, tile: Option<&Tile>, funds: u32) -> i32 {
+        let stars = tile.map(|tile| tile.defense_stars(class)).unwrap_or(0) as i32;
+
+        self.effects(class, tile).map(|effect| match effect {
+            Effect::Attack(value) => value,
+            Effect::AttackPerStar(value) => value * stars,
+            Effect::AttackPerThousandFunds(value) => value * (funds / 1000) as i32,
+            _ => 0,
+        }).sum()
+    }
+
+    /// Defense percentage for the unit when it is attacked by the attacker class.
+    pub fn defense(&self, class: UnitClass, tile: Option<&Tile>, attacker: UnitClass) -> i32 {
+        self.effects(class, tile).map(|effect| match effect {
+            Effect::Defense(value) => value,
+            Effect::IndirectDefense(value) if attacker.is_indirect() => value,
+            _ => 0,
+        }).sum()
+    }
+
+    /// Whether the player's comtowers also give a defense bonus.
+    pub fn comtower_defense(&self) -> bool {
+        self.modifiers().any(|modifier| modifier.effect == Effect::ComtowerDefense)
+    }
+
+    /// Whether the unit's terrain stars are doubled when defending.
+    pub fn double_terrain_stars(&self, class: UnitClass) -> bool {
+        self.effects(class, None).any(|effect| effect == Effect::DoubleTerrainStars)
+    }
+
+    pub fn movement(&self, class: UnitClass) -> u32 {
+        let change = self.effects(class, None).map(|effect| match effect {
+            Effect::Movement(value) => value,
+            _ => 0,
+        }).sum();
+
+        adjust(class.movement(), change).max(1)
+    }
+
+    /// Attack range, only the maximum range of indirect units can change.
+    pub fn attack_range(&self, class: UnitClass) -> (u32, u32) {
+        let (min, max) = class.attack_range();
+
+        if class.is_indirect() {
+            let change = self.effects(class, None).map(|effect| match effect {
+                Effect::Range(value) => value,
+                _ => 0,
+            }).sum();
+
+            (min, adjust(max, change).max(min))
+
+        } else {
+            (min, max)
+        }
+    }
+
+    /// Whether the weather does not change the movement costs of the unit.
+    pub fn ignores_weather(&self, class: UnitClass, weather: Weather) -> bool {
+        self.effects(class, None).any(|effect| effect == Effect::IgnoreWeather(weather))
+    }
+
+    /// Vision before any terrain bonuses.
+    pub fn vision(&self, class: UnitClass) -> u32 {
+        let change = self.effects(class, None).map(|effect| match effect {
+            Effect::Vision(value) => value,
+            _ => 0,
+        }).sum();
+
+        adjust(class.vision(), change).max(1)
+    }
+
+    /// Returns the maximum good luck and bad luck.
+    pub fn luck(&self, class: UnitClass) -> (u32, u32) {
+        self.effects(class, None).fold((9, 0), |(good, bad), effect| match effect {
+            Effect::GoodLuck(value) => (value, bad),
+            Effect::BadLuck(value) => (good, value),
+            _ => (good, bad),
+        })
+    }
+
+    /// Funds needed to build the unit.
+    pub fn cost(&self, class: UnitClass) -> u32 {
+        let percent = self.effects(class, None).fold(100, |percent, effect| match effect {
+            Effect::Cost(value) => value,
+            _ => percent,
+        });
+
+        class.cost() * percent / 100
+    }
+}
+
+
+impl GameState {
+    /// Number of comtowers owned by the nation.
+    pub fn comtowers(&self, nation: Nation) -> u32 {
+        self.map.tiles()
+            .filter(|tile| tile.owner == Some(nation) && tile.building == Some(BuildingClass::ComTower))
+            .count() as u32
+    }
+
+    /// Creates the [`Attacker`] for the unit, including the CO and comtower bonuses.
+    pub fn attacker(&self, id: UnitId) -> Option<Attacker> {
+        let unit = self.unit(id)?;
+        let player = self.player(unit.nation)?;
+
+        let mut attacker = Attacker {
+            hp: unit.hp,
+            ammo: unit.ammo,
+            comtowers: self.comtowers(unit.nation),
+            ..Attacker::new(unit.class)
+        };
+
+        if let Some(co) = &player.co {
+            let tile = self.map.get(&unit.coord);
+
+            let (bonus, penalty) = split(co.attack(unit.class, tile, player.funds));
+            let (good_luck, bad_luck) = co.luck(unit.class);
+
+            attacker.co_bonus = bonus;
+            attacker.co_penalty = penalty;
+            attacker.co_power = co.power.is_some();
+            attacker.good_luck = good_luck;
+            attacker.bad_luck = bad_luck;
+        }
+
+        Some(attacker)
+    }
+
+    /// Returns the attack range of the unit, including any CO modifiers and the weather.
+    pub fn attack_range(&self, id: UnitId) -> Option<(u32, u32)> {
+        let unit = self.unit(id)?;
+
+        let range = match &self.player(unit.nation)?.co {
+            Some(co) => co.attack_range(unit.class),
+            None => unit.class.attack_range(),
+        };
+
+        Some(self.current_weather().attack_range(range))
+    }
+
+    /// Returns the movement points of the unit, including any CO modifiers.
+    pub fn movement(&self, id: UnitId) -> Option<u32> {
+        let unit = self.unit(id)?;
+
+        match &self.player(unit.nation)?.co {
+            Some(co) => Some(co.movement(unit.class)),
+            None => Some(unit.class.movement()),
+        }
+    }
+
+    /// Creates the [`Defender`] for the unit when it is attacked by the attacker class.
+    pub fn defender(&self, id: UnitId, attacker: UnitClass) -> Option<Defender> {
+        let unit = self.unit(id)?;
+        let player = self.player(unit.nation)?;
+        let tile = self.map.get(&unit.coord);
+
+        let mut defender = Defender {
+            hp: unit.hp,
+            terrain_stars: tile.map(|tile| tile.defense_stars(unit.class)).unwrap_or(0),
+            ..Defender::new(Target::Unit(unit.class))
+        };
+
+        if let Some(co) = &player.co {
+            let (bonus, penalty) = split(co.defense(unit.class, tile, attacker));
+
+            defender.co_bonus = bonus;
+            defender.co_penalty = penalty;
+
+            if co.comtower_defense() {
+                defender.comtowers = self.comtowers(unit.nation);
+            }
+
+            if co.double_terrain_stars(unit.class) {
+                defender.terrain_stars *= 2;
+            }
+        }
+
+        Some(defender)
+    }
+
+    /// Activates the current player's CO power, the power lasts until the start of their next turn.
+    pub fn activate_power(&mut self, kind: PowerKind) -> Result<Vec<Event>, RuleError> {
+        let nation = self.current_player().nation;
+
+        if kind == PowerKind::Tag && self.rules.co_mode != CoMode::Tag {
+            return Err(RuleError::CannotActivatePower(nation));
+        }
+
+        let player = &mut self.players[self.current];
+
+        let co = match &mut player.co {
+            Some(co) if co.can_activate(kind) => co,
+            _ => return Err(RuleError::CannotActivatePower(nation)),
+        };
+
+        co.meter -= co.power_cost(kind);
+        co.uses += 1;
+        co.power = Some(kind);
+
+        let co = co.co;
+
+        let mut events = vec![Event::PowerActivated { nation, co, power: kind }];
+
+        self.power_instants(co, kind, &mut events);
+
+        Ok(events)
+    }
+
+    /// Applies the one-time effects of the power for the current player.
+    pub(crate) fn power_instants(&mut self, co: Co, kind: PowerKind, events: &mut Vec<Event>) {
+        let nation = self.current_player().nation;
+
+        for instant in co.definition().power(kind).instant {
+            match *instant {
+                Instant::Heal(hp) => {
+                    for (id, unit) in self.units.iter_mut() {
+                        if unit.nation == nation {
+                            let hp = hp.min(100 - unit.hp);
+
+                            if hp > 0 {
+                                unit.hp += hp;
+                                events.push(Event::Healed { unit: *id, hp });
+                            }
+                        }
+                    }
+                },
+
+                Instant::Funds(percent) => {
+                    let player = &mut self.players[self.current];
+
+                    let funds = player.funds * percent / 100 - player.funds;
+
+                    player.funds += funds;
+
+                    events.push(Event::FundsGained { nation, funds });
+                },
+
+                Instant::Weather(weather) => {
+                    self.set_power_weather(nation, weather, events);
+                },
+
+                Instant::Damage(hp) => {
+                    let team = self.team(nation);
+
+                    for (id, unit) in self.units.iter_mut() {
+                        if !team.contains(&unit.nation) {
+                            let damage = hp.min(unit.hp - 1);
+
+                            if damage > 0 {
+                                unit.hp -= damage;
+                                events.push(Event::PowerHit { unit: *id, damage });
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    /// Powers end when the player's next turn starts.
+    pub(crate) fn end_power(&mut self) {
+        if let Some(co) = &mut self.players[self.current].co {
+            co.power = None;
+            co.tag_turn = false;
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::damage::{CounterAttack, Forecast, calculate_damage, forecast};
+use crate::random::{Rng};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+/// The outcome of an attack, the renderer plays it back in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombatResult {
+    pub attacker: UnitId,
+    pub defender: UnitId,
+
+    /// HP removed from the defender.
+    pub damage: u32,
+
+    /// HP removed from the attacker, or `None` if the defender did not counter-attack.
+    pub counter: Option<u32>,
+
+    pub attacker_destroyed: bool,
+    pub defender_destroyed: bool,
+
+    pub events: Vec<Event>,
+}
+
+
+impl GameState {
+    /// Whether the unit is able to attack the target from its current tile.
+    fn check_attack(&self, id: UnitId, target: UnitId) -> Result<(), RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        let other = self.unit(target).ok_or(RuleError::UnitNotFound(target))?;
+
+        // Units can only attack what their team can see
+        if !self.vision(unit.nation).can_see(other) {
+            return Err(RuleError::TargetNotVisible(id));
+        }
+
+        let (min_range, max_range) = self.attack_range(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        let distance = unit.coord.distance(&other.coord);
+        let is_enemy = !self.same_team(other.nation, unit.nation);
+
+        let can_attack =
+            !unit.is_loaded() &&
+            !other.is_loaded() &&
+            is_enemy &&
+            other.can_be_attacked_by(unit.class) &&
+            !(unit.moved && unit.class.is_indirect()) &&
+            distance >= min_range &&
+            distance <= max_range;
+
+        if can_attack {
+            Ok(())
+
+        } else {
+            Err(RuleError::CannotAttack(id))
+        }
+    }
+
+    /// The unit attacks, returning the damage and whether the unit was destroyed.
+    ///
+    /// This also charges the power meters of both players.
+    fn hit_unit<R>(&mut self, id: UnitId, target: UnitId, rng: R, events: &mut Vec<Event>) -> Option<(u32, bool)> where R: Rng {
+        let attacker = self.attacker(id)?;
+        let defender = self.defender(target, self.units[&id].class)?;
+
+        let damage = calculate_damage(&attacker, &defender, rng)?;
+
+        let unit = self.units.get_mut(&id).unwrap();
+
+        unit.use_ammo(damage.weapon);
+
+        let attacker_nation = unit.nation;
+
+        let other = self.units.get_mut(&target).unwrap();
+
+        let damage = damage.damage.min(other.hp);
+
+        other.hp -= damage;
+
+        let hp = other.hp;
+        let value = other.build_cost * damage / 100;
+
+        let defender_nation = other.nation;
+
+        if let Some(co) = self.player_mut(attacker_nation).and_then(|player| player.co.as_mut()) {
+            co.charge(value, 0);
+        }
+
+        if let Some(co) = self.player_mut(defender_nation).and_then(|player| player.co.as_mut()) {
+            co.charge(0, value);
+        }
+
+        events.push(Event::Attacked { unit: id, target, damage, hp });
+
+        let destroyed = hp == 0;
+
+        if destroyed {
+            events.extend(self.destroy_unit_by(target, Some(attacker_nation)));
+        }
+
+        Some((damage, destroyed))
+    }
+
+    /// The unit attacks the target without moving.
+    ///
+    /// If the target survives, and it is a direct unit next to the attacker, then it
+    /// counter-attacks with its reduced HP.
+    ///
+    /// Indirect units cannot attack if they moved this turn, and they never counter-attack.
+    pub fn resolve_attack<R>(&mut self, id: UnitId, target: UnitId, mut rng: R) -> Result<CombatResult, RuleError> where R: Rng {
+        self.check_attack(id, target)?;
+
+        let mut events = vec![];
+
+        let (damage, defender_destroyed) = self.hit_unit(id, target, &mut rng, &mut events)
+            .ok_or(RuleError::CannotAttack(id))?;
+
+        self.units.get_mut(&id).unwrap().waited = true;
+
+        let mut counter = None;
+        let mut attacker_destroyed = false;
+
+        if !defender_destroyed && self.can_counter(target, id) {
+            if let Some((damage, destroyed)) = self.hit_unit(target, id, &mut rng, &mut events) {
+                counter = Some(damage);
+                attacker_destroyed = destroyed;
+            }
+        }
+
+        Ok(CombatResult {
+            attacker: id,
+            defender: target,
+            damage,
+            counter,
+            attacker_destroyed,
+            defender_destroyed,
+            events,
+        })
+    }
+
+    /// Forecasts the attack from the unit's current tile, including the counter-attack.
+    pub fn forecast_attack(&self, id: UnitId, target: UnitId) -> Result<Forecast, RuleError> {
+        self.check_attack(id, target)?;
+
+        let attacker = self.attacker(id).ok_or(RuleError::UnitNotFound(id))?;
+        let defender = self.defender(target, attacker.class).ok_or(RuleError::UnitNotFound(target))?;
+
+        let counter = if self.can_counter(target, id) {
+            Some(CounterAttack {
+                attacker: self.attacker(target).ok_or(RuleError::UnitNotFound(target))?,
+                defender: self.defender(id, self.units[&target].class).ok_or(RuleError::UnitNotFound(id))?,
+            })
+
+        } else {
+            None
+        };
+
+        forecast(&attacker, &defender, counter.as_ref()).ok_or(RuleError::CannotAttack(id))
+    }
+
+    /// Whether the defender can counter-attack the attacker.
+    fn can_counter(&self, id: UnitId, attacker: UnitId) -> bool {
+        let unit = &self.units[&id];
+        let other = &self.units[&attacker];
+
+        !unit.class.is_indirect() &&
+        unit.coord.distance(&other.coord) == 1 &&
+        other.can_be_attacked_by(unit.class)
+    }
+}
@@ -0,0 +1,336 @@
+use crate::map::{Coord};
+use crate::unit::{Unit, UnitClass};
+use crate::movement::{Occupant, Reachable, reachable_tiles_with};
+use crate::vision::{Vision};
+use crate::random::{Rng};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+use crate::co::{PowerKind};
+use crate::transport::{can_load_into};
+use crate::join::{can_join_into};
+
+
+/// Everything that a player can do on their turn.
+///
+/// Moving is a separate command, so "move and attack" is a [`Command::Move`] followed by a [`Command::Attack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// The path includes the unit's current tile.
+    Move {
+        unit: UnitId,
+        path: Vec<Coord>,
+    },
+
+    Attack {
+        unit: UnitId,
+        target: UnitId,
+    },
+
+    /// Attacks a pipeseam or any tile of a Black Hole structure.
+    AttackStructure {
+        unit: UnitId,
+        coord: Coord,
+    },
+
+    Capture {
+        unit: UnitId,
+    },
+
+    /// The unit must be on the transport's tile, [`Command::Move`] onto a transport already loads it.
+    Load {
+        unit: UnitId,
+        transport: UnitId,
+    },
+
+    Unload {
+        transport: UnitId,
+        unit: UnitId,
+        coord: Coord,
+    },
+
+    /// The unit must be on the target's tile, [`Command::Move`] onto the target already joins it.
+    Join {
+        unit: UnitId,
+        target: UnitId,
+    },
+
+    Supply {
+        unit: UnitId,
+    },
+
+    /// A Stealth hides or a Submarine dives.
+    Hide {
+        unit: UnitId,
+    },
+
+    /// A Stealth appears or a Submarine surfaces.
+    Appear {
+        unit: UnitId,
+    },
+
+    /// Launches the missile from the silo that the unit is standing on.
+    Launch {
+        unit: UnitId,
+        target: Coord,
+    },
+
+    Engulf {
+        unit: UnitId,
+        target: UnitId,
+    },
+
+    Explode {
+        unit: UnitId,
+    },
+
+    Wait {
+        unit: UnitId,
+    },
+
+    /// Builds a unit on the current player's Base, Airport, or Port.
+    Build {
+        coord: Coord,
+        class: UnitClass,
+    },
+
+    ActivatePower {
+        power: PowerKind,
+    },
+
+    SwitchCo,
+
+    EndTurn,
+
+    /// The current player gives up.
+    Resign,
+}
+
+
+impl GameState {
+    /// Whether an enemy which the unit's team cannot see is standing on the tile.
+    fn is_hidden_enemy(&self, unit: &Unit, vision: &Vision, coord: &Coord) -> bool {
+        self.unit_at(coord)
+            .map(|(_, other)| !self.same_team(other.nation, unit.nation) && !vision.can_see(other))
+            .unwrap_or(false)
+    }
+
+    /// Returns the movement cost of the path, or `None` if the unit cannot move along it.
+    ///
+    /// Enemies which the unit's team cannot see do not block the path, they trap the unit instead.
+    fn path_cost(&self, unit: &Unit, path: &[Coord], vision: &Vision) -> Option<u32> {
+        let (start, rest) = path.split_first()?;
+
+        if *start != unit.coord || rest.is_empty() {
+            return None;
+        }
+
+        let movement = unit.class.movement_type();
+        let weather = self.unit_weather(unit);
+
+        let mut cost = 0;
+        let mut previous = start;
+
+        for coord in rest {
+            if previous.distance(coord) != 1 {
+                return None;
+            }
+
+            cost += weather.movement_cost(movement, self.map.get(coord)?)?;
+
+            if let Some((_, other)) = self.unit_at(coord) {
+                if !self.same_team(other.nation, unit.nation) && vision.can_see(other) {
+                    return None;
+                }
+            }
+
+            previous = coord;
+        }
+
+        // Units can move through allies, but they can only stop on transports which can carry them
+        // or on units which they can join
+        if let Some((_, other)) = self.unit_at(previous) {
+            if !can_load_into(unit, other) && !can_join_into(unit, other) && !self.is_hidden_enemy(unit, vision, previous) {
+                return None;
+            }
+        }
+
+        Some(cost)
+    }
+
+    /// Returns every tile that the unit can move to with [`Command::Move`].
+    ///
+    /// This includes the CO modifiers, the weather, and the unit's fuel.
+    /// Enemies which the unit's team cannot see are ignored.
+    pub fn reachable(&self, id: UnitId) -> Option<Reachable> {
+        let unit = self.unit(id)?;
+        let max_cost = self.movement(id)?.min(unit.fuel);
+        let vision = self.vision(unit.nation);
+
+        Some(reachable_tiles_with(unit, &self.map, self.unit_weather(unit), max_cost, |coord| {
+            self.unit_at(coord).filter(|(_, other)| vision.can_see(other)).map(|(_, other)| {
+                if can_load_into(unit, other) {
+                    Occupant::Transport
+
+                } else if can_join_into(unit, other) {
+                    Occupant::Join
+
+                } else if self.same_team(other.nation, unit.nation) {
+                    Occupant::Ally
+
+                } else {
+                    Occupant::Enemy
+                }
+            })
+        }))
+    }
+
+    /// The unit moves along the path, using up fuel for the movement cost.
+    ///
+    /// Units can only move once per turn, and they cannot move through enemies.
+    /// If the path ends on an allied transport then the unit is loaded into it,
+    /// and if it ends on an allied unit of the same class then the unit joins it.
+    ///
+    /// If there is an enemy on the path which the unit's team cannot see, then the unit
+    /// is trapped: it stops on the last empty tile before the enemy and its action ends.
+    pub fn move_along(&mut self, id: UnitId, path: &[Coord]) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        if unit.moved || unit.is_loaded() {
+            return Err(RuleError::CannotMove(id));
+        }
+
+        let max_cost = self.movement(id).unwrap().min(unit.fuel);
+        let vision = self.vision(unit.nation);
+
+        let mut cost = self.path_cost(unit, path, &vision)
+            .filter(|cost| *cost <= max_cost)
+            .ok_or(RuleError::CannotMove(id))?;
+
+        let trap = path.iter().position(|coord| self.is_hidden_enemy(unit, &vision, coord));
+
+        let mut moved = path;
+
+        if let Some(index) = trap {
+            let mut end = index;
+
+            while end > 1 && self.unit_at(&path[end - 1]).is_some() {
+                end -= 1;
+            }
+
+            moved = &path[..end];
+            cost = self.path_cost(unit, moved, &vision).unwrap_or(0);
+        }
+
+        let destination = *moved.last().unwrap();
+
+        let occupant = match trap {
+            Some(_) => None,
+            None => self.unit_at(&destination).map(|(other, _)| other),
+        };
+
+        self.units.get_mut(&id).unwrap().fuel -= cost;
+        self.move_unit(id, destination)?;
+
+        let mut events = vec![];
+
+        if moved.len() > 1 {
+            events.push(Event::Moved { unit: id, path: moved.to_vec() });
+        }
+
+        if let Some(index) = trap {
+            self.units.get_mut(&id).unwrap().waited = true;
+
+            events.push(Event::Trapped { unit: id, coord: path[index] });
+        }
+
+        if let Some(other) = occupant {
+            if can_load_into(self.unit(id).unwrap(), self.unit(other).unwrap()) {
+                events.extend(self.load_unit(id, other)?);
+
+            } else {
+                events.extend(self.join_unit(id, other)?);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// The unit ends its action without doing anything.
+    pub fn wait_unit(&mut self, id: UnitId) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        self.units.get_mut(&id).unwrap().waited = true;
+
+        Ok(vec![Event::Waited { unit: id }])
+    }
+
+    /// Checks the command against the rules and then runs it.
+    ///
+    /// If the command is not allowed then the state is not changed. The UI and network
+    /// players should use this rather than calling the individual actions.
+    ///
+    /// The victory conditions are checked after every command.
+    pub fn apply<R>(&mut self, command: Command, rng: R) -> Result<Vec<Event>, RuleError> where R: Rng {
+        if self.game_over {
+            return Err(RuleError::GameOver);
+        }
+
+        let mut events = self.run_command(command, rng)?;
+
+        self.check_victory(&mut events);
+
+        Ok(events)
+    }
+
+    fn run_command<R>(&mut self, command: Command, rng: R) -> Result<Vec<Event>, RuleError> where R: Rng {
+        match command {
+            Command::Move { unit, path } => self.move_along(unit, &path),
+            Command::Attack { unit, target } => self.resolve_attack(unit, target, rng).map(|result| result.events),
+
+            Command::AttackStructure { unit, coord } => {
+                let is_building = self.map.get(&coord).map(|tile| tile.building.is_some()).unwrap_or(false);
+
+                if is_building {
+                    self.attack_structure(unit, coord, rng)
+
+                } else {
+                    self.attack_pipeseam(unit, coord, rng)
+                }
+            },
+
+            Command::Capture { unit } => self.capture_property(unit),
+            Command::Load { unit, transport } => self.load_unit(unit, transport),
+            Command::Unload { transport, unit, coord } => self.unload_unit(transport, unit, coord),
+            Command::Join { unit, target } => self.join_unit(unit, target),
+            Command::Supply { unit } => self.supply_units(unit),
+            Command::Hide { unit } => self.hide_unit(unit),
+            Command::Appear { unit } => self.appear_unit(unit),
+            Command::Launch { unit, target } => self.launch_missile(unit, target),
+            Command::Engulf { unit, target } => self.engulf_unit(unit, target),
+            Command::Explode { unit } => self.explode_unit(unit),
+            Command::Wait { unit } => self.wait_unit(unit),
+            Command::Build { coord, class } => self.build_unit(coord, class),
+            Command::ActivatePower { power } => self.activate_power(power),
+            Command::SwitchCo => self.switch_co(),
+            Command::EndTurn => Ok(self.end_turn()),
+            Command::Resign => Ok(self.resign()),
+        }
+    }
+}
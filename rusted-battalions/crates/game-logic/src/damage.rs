@@ -0,0 +1,527 @@
+use crate::unit::{UnitClass};
+use crate::random::{Rng};
+use crate::luck::{LuckMode};
+
+
+/// Base damage for every [`Target`], in the same order as `Target::index`.
+///
+/// A value of 0 means that the weapon cannot attack that target.
+type DamageTable = [u32; 27];
+
+const INFANTRY_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       55,   45,   12,    5,    1,    1,    1,   14,   15,   25,    5,   25,    5,    0,    0,    7,   30,    0,    0,    0,    0,    0,    0,    0,    0,    1,   20,
+];
+
+const MECH_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,   85,   55,   15,   15,    5,   75,   70,   85,   65,   85,   55,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,   15,   20,
+];
+
+const MECH_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       65,   55,   18,    6,    1,    1,    1,   20,   32,   35,    6,   35,    6,    0,    0,    9,   35,    0,    0,    0,    0,    0,    0,    0,    0,    1,   20,
+];
+
+const RECON_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       70,   65,   35,    6,    1,    1,    1,   45,   45,   55,    4,   28,    6,    0,    0,   10,   35,    0,    0,    0,    0,    0,    0,    0,    0,    1,   30,
+];
+
+const TANK_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,   85,   55,   15,   15,   10,   75,   70,   85,   65,   85,   55,    0,    0,    0,    0,    0,    0,    1,    5,   10,    1,   10,    1,   15,   20,
+];
+
+const TANK_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       75,   70,   40,    6,    1,    1,    1,   45,   45,   55,    5,   30,    6,    0,    0,   10,   40,    0,    0,    0,    0,    0,    0,    0,    0,    1,   20,
+];
+
+const MEDIUM_TANK_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,  105,   85,   55,   45,   25,  105,  105,  105,  105,  105,   85,    0,    0,    0,    0,    0,    0,   10,   30,   35,   10,   35,   10,   55,   25,
+];
+
+const MEDIUM_TANK_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+      105,   95,   45,    8,    1,    1,    1,   45,   45,   55,    7,   35,    8,    0,    0,   12,   45,    0,    0,    0,    0,    0,    0,    0,    0,    1,   20,
+];
+
+const NEOTANK_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,  125,  105,   75,   55,   35,  125,  115,  125,  115,  125,  105,    0,    0,    0,    0,    0,    0,   15,   30,   40,   15,   40,   15,   75,   30,
+];
+
+const NEOTANK_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+      125,  115,   65,   10,    1,    1,    1,   65,   65,   75,   17,   55,   10,    0,    0,   22,   55,    0,    0,    0,    0,    0,    0,    0,    0,    1,   20,
+];
+
+const MEGA_TANK_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,  195,  180,  125,  115,   65,  195,  195,  195,  195,  195,  180,    0,    0,    0,    0,    0,    0,   45,   65,   75,   45,  105,   45,  125,   45,
+];
+
+const MEGA_TANK_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+      135,  125,   65,   10,    1,    1,    1,   65,   65,   75,   17,   55,   10,    0,    0,   22,   55,    0,    0,    0,    0,    0,    0,    0,    0,    1,   20,
+];
+
+const ARTILLERY_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       90,   85,   80,   70,   45,   40,   15,   70,   75,   80,   75,   80,   70,    0,    0,    0,    0,    0,    0,   40,   50,   55,   60,   55,   45,   45,   20,
+];
+
+const ROCKET_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       95,   90,   90,   80,   55,   50,   25,   80,   80,   85,   85,   90,   80,    0,    0,    0,    0,    0,    0,   55,   60,   60,   85,   60,   60,   55,   25,
+];
+
+const ANTI_AIR_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+      105,  105,   60,   25,   10,    5,    1,   50,   50,   55,   45,   55,   25,   65,   75,  105,  105,   75,  120,    0,    0,    0,    0,    0,    0,   10,   30,
+];
+
+const MISSILE_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,  100,  100,  115,  115,  100,  120,    0,    0,    0,    0,    0,    0,    0,    0,
+];
+
+const PIPERUNNER_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       95,   90,   90,   80,   55,   50,   25,   80,   80,   85,   85,   90,   80,   65,   75,  105,  105,   75,  120,   55,   60,   60,   85,   60,   60,   55,   25,
+];
+
+const FIGHTER_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,   55,  100,  120,  120,   85,  120,    0,    0,    0,    0,    0,    0,    0,    0,
+];
+
+const BOMBER_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+      110,  110,  105,  105,   95,   90,   35,  105,  105,  105,   95,  105,  105,    0,    0,    0,    0,    0,    0,   75,   50,   95,   95,   95,   75,   95,   35,
+];
+
+const B_COPTER_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,   55,   55,   25,   20,   10,   60,   65,   65,   25,   65,   55,    0,    0,    0,    0,    0,    0,   25,   25,   25,   25,   25,   25,   25,   20,
+];
+
+const B_COPTER_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       75,   75,   30,    6,    1,    1,    1,   20,   25,   35,    6,   35,    6,    0,    0,   65,   95,    0,    0,    0,    0,    0,    0,    0,    0,    1,    5,
+];
+
+const STEALTH_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       90,   90,   85,   75,   70,   60,   15,   85,   75,   85,   50,   85,   80,   45,   70,   85,   95,   55,  120,   45,   35,   65,   55,   65,   45,   70,   30,
+];
+
+const BATTLESHIP_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+       95,   90,   90,   80,   55,   50,   25,   80,   80,   85,   85,   90,   80,    0,    0,    0,    0,    0,    0,   50,   95,   95,   95,   95,   60,   55,   30,
+];
+
+const CRUISER_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    5,   25,   25,   90,   25,    5,    0,    0,
+];
+
+const CRUISER_SECONDARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,   85,  100,  105,  105,  100,  120,    0,    0,    0,    0,    0,    0,    0,    0,
+];
+
+const SUBMARINE_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,   65,   25,   95,   55,   95,   75,    0,    0,
+];
+
+const CARRIER_PRIMARY: DamageTable = [
+    // Inf  Mech Recon  Tank MdTnk   Neo  Mega   APC   Art   Rkt    AA   Msl  Pipe   Ftr  Bmbr  BCop  TCop Stlth BBomb BShip  Crsr  Lndr   Sub BBoat  Crrr  Seam  Ooze
+        0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,  100,  100,  115,  115,  100,  120,    0,    0,    0,    0,    0,    0,    0,    0,
+];
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weapon {
+    /// The main weapon of the unit, it uses up ammo.
+    Primary,
+
+    /// The backup weapon of the unit, it has unlimited ammo.
+    Secondary,
+}
+
+
+/// Something which can be attacked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    Unit(UnitClass),
+    Pipeseam,
+}
+
+impl Target {
+    fn index(&self) -> usize {
+        match self {
+            Self::Unit(class) => match class {
+                UnitClass::Infantry => 0,
+                UnitClass::Mech => 1,
+                UnitClass::Recon => 2,
+                UnitClass::Tank => 3,
+                UnitClass::MediumTank => 4,
+                UnitClass::Neotank => 5,
+                UnitClass::MegaTank => 6,
+                UnitClass::APC => 7,
+                UnitClass::Artillery => 8,
+                UnitClass::Rocket => 9,
+                UnitClass::AntiAir => 10,
+                UnitClass::Missile => 11,
+                UnitClass::Piperunner => 12,
+                UnitClass::Fighter => 13,
+                UnitClass::Bomber => 14,
+                UnitClass::BCopter => 15,
+                UnitClass::TCopter => 16,
+                UnitClass::Stealth => 17,
+                UnitClass::BlackBomb => 18,
+                UnitClass::Battleship => 19,
+                UnitClass::Cruiser => 20,
+                UnitClass::Lander => 21,
+                UnitClass::Submarine => 22,
+                UnitClass::BlackBoat => 23,
+                UnitClass::Carrier => 24,
+                UnitClass::Oozium => 26,
+            },
+            Self::Pipeseam => 25,
+        }
+    }
+}
+
+
+fn damage_table(attacker: UnitClass, weapon: Weapon) -> Option<&'static DamageTable> {
+    match weapon {
+        Weapon::Primary => match attacker {
+            UnitClass::Mech => Some(&MECH_PRIMARY),
+            UnitClass::Tank => Some(&TANK_PRIMARY),
+            UnitClass::MediumTank => Some(&MEDIUM_TANK_PRIMARY),
+            UnitClass::Neotank => Some(&NEOTANK_PRIMARY),
+            UnitClass::MegaTank => Some(&MEGA_TANK_PRIMARY),
+            UnitClass::Artillery => Some(&ARTILLERY_PRIMARY),
+            UnitClass::Rocket => Some(&ROCKET_PRIMARY),
+            UnitClass::AntiAir => Some(&ANTI_AIR_PRIMARY),
+            UnitClass::Missile => Some(&MISSILE_PRIMARY),
+            UnitClass::Piperunner => Some(&PIPERUNNER_PRIMARY),
+            UnitClass::Fighter => Some(&FIGHTER_PRIMARY),
+            UnitClass::Bomber => Some(&BOMBER_PRIMARY),
+            UnitClass::BCopter => Some(&B_COPTER_PRIMARY),
+            UnitClass::Stealth => Some(&STEALTH_PRIMARY),
+            UnitClass::Battleship => Some(&BATTLESHIP_PRIMARY),
+            UnitClass::Cruiser => Some(&CRUISER_PRIMARY),
+            UnitClass::Submarine => Some(&SUBMARINE_PRIMARY),
+            UnitClass::Carrier => Some(&CARRIER_PRIMARY),
+            _ => None,
+        },
+        Weapon::Secondary => match attacker {
+            UnitClass::Infantry => Some(&INFANTRY_SECONDARY),
+            UnitClass::Mech => Some(&MECH_SECONDARY),
+            UnitClass::Recon => Some(&RECON_SECONDARY),
+            UnitClass::Tank => Some(&TANK_SECONDARY),
+            UnitClass::MediumTank => Some(&MEDIUM_TANK_SECONDARY),
+            UnitClass::Neotank => Some(&NEOTANK_SECONDARY),
+            UnitClass::MegaTank => Some(&MEGA_TANK_SECONDARY),
+            UnitClass::BCopter => Some(&B_COPTER_SECONDARY),
+            UnitClass::Cruiser => Some(&CRUISER_SECONDARY),
+            _ => None,
+        },
+    }
+}
+
+
+/// Returns the base damage of the weapon against the target.
+///
+/// Returns `None` if the weapon cannot attack the target.
+pub fn base_damage(attacker: UnitClass, weapon: Weapon, target: Target) -> Option<u32> {
+    let table = damage_table(attacker, weapon)?;
+
+    match table[target.index()] {
+        0 => None,
+        damage => Some(damage),
+    }
+}
+
+
+/// Chooses which weapon the attacker will use against the target.
+///
+/// The primary weapon is always used if it can attack the target and it has ammo,
+/// otherwise it falls back to the secondary weapon.
+pub fn select_weapon(attacker: UnitClass, ammo: u32, target: Target) -> Option<(Weapon, u32)> {
+    if ammo > 0 {
+        if let Some(damage) = base_damage(attacker, Weapon::Primary, target) {
+            return Some((Weapon::Primary, damage));
+        }
+    }
+
+    base_damage(attacker, Weapon::Secondary, target).map(|damage| (Weapon::Secondary, damage))
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Attacker {
+    pub class: UnitClass,
+
+    /// Non-rounded HP, from 0 to 100.
+    pub hp: u32,
+
+    /// Ammo for the primary weapon. When it is 0 the secondary weapon is used.
+    pub ammo: u32,
+
+    /// Attack bonuses like Max directs, as a percentage.
+    pub co_bonus: u32,
+
+    /// Attack penalties like Max indirects, as a percentage.
+    pub co_penalty: u32,
+
+    /// Whether the CO power is active or not.
+    pub co_power: bool,
+
+    /// Number of comtowers.
+    pub comtowers: u32,
+
+    /// Defaults to 9 damage.
+    pub good_luck: u32,
+
+    /// Defaults to 0 damage.
+    pub bad_luck: u32,
+
+    /// How the good luck and bad luck are rolled.
+    pub luck_mode: LuckMode,
+}
+
+impl Attacker {
+    pub fn new(class: UnitClass) -> Self {
+        Self {
+            class,
+            hp: 100,
+            ammo: 1,
+            co_bonus: 0,
+            co_penalty: 0,
+            co_power: false,
+            comtowers: 0,
+            good_luck: 9,
+            bad_luck: 0,
+            luck_mode: LuckMode::SingleRoll,
+        }
+    }
+
+    /// Returns the total attack as a percentage.
+    pub fn attack(&self) -> i64 {
+        // All COs get a +10% attack during their CO power
+        let power = if self.co_power { 10 } else { 0 };
+
+        // 10% attack bonus per comtower
+        let comtowers = self.comtowers as i64 * 10;
+
+        100 + (self.co_bonus as i64) - (self.co_penalty as i64) + power + comtowers
+    }
+
+    /// Returns the attack damage before luck, HP, and defense.
+    pub fn attack_damage(&self, base_damage: u32) -> i64 {
+        // Truncated to the nearest integer, minimum 1% damage.
+        //
+        // The truncation happens before the defense is applied, which is
+        // needed to match the comtower damage in "Dual Strike CO Bonus.md".
+        ((base_damage as i64 * self.attack()) / 100).max(1)
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Defender {
+    pub target: Target,
+
+    /// Non-rounded HP, from 0 to 100.
+    pub hp: u32,
+
+    /// Defense bonuses like Kanbei, as a percentage.
+    pub co_bonus: u32,
+
+    /// Defense penalties like Grimm, as a percentage.
+    pub co_penalty: u32,
+
+    /// For Javier only.
+    pub comtowers: u32,
+
+    /// 10% for each terrain star.
+    pub terrain_stars: u32,
+}
+
+impl Defender {
+    pub fn new(target: Target) -> Self {
+        Self {
+            target,
+            hp: 100,
+            co_bonus: 0,
+            co_penalty: 0,
+            comtowers: 0,
+            terrain_stars: 0,
+        }
+    }
+
+    /// Returns the defense bonus from the terrain, from 0 to 1000.
+    pub fn terrain_defense(&self) -> i64 {
+        // 10% defense bonus per terrain star, but scaled by the non-rounded HP.
+        // Terrain stars are doubled by Lash's powers before they get here, so they still scale with HP.
+        self.terrain_stars as i64 * self.hp as i64
+    }
+
+    /// Returns how much of the damage gets through the defense, from 0 to 1000.
+    pub fn defense(&self) -> i64 {
+        let terrain_defense = self.terrain_defense();
+
+        // 10% defense bonus per comtower but only for Javier
+        let comtowers = self.comtowers as i64 * 10;
+
+        let bonus_defense = (self.co_bonus as i64) - (self.co_penalty as i64) + comtowers;
+
+        (1000 - (bonus_defense * 10) - terrain_defense).max(0)
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Damage {
+    pub weapon: Weapon,
+
+    /// Amount of HP reduced from the defender, it can be higher than the defender's HP.
+    pub damage: u32,
+}
+
+
+/// Calculates the damage for a specific luck value.
+pub fn damage_with_luck(attacker: &Attacker, defender: &Defender, base_damage: u32, luck: i32) -> u32 {
+    let attack_damage = attacker.attack_damage(base_damage);
+
+    // Attack damage and luck are scaled by the unit's non-rounded HP,
+    // damage cannot go below 0
+    let total_damage = ((attack_damage + luck as i64) * attacker.hp as i64).max(0);
+
+    // Damage is truncated to the nearest integer
+    ((total_damage * defender.defense()) / 100_000) as u32
+}
+
+
+/// Returns `None` if the attacker cannot attack the defender.
+pub fn calculate_damage<R>(attacker: &Attacker, defender: &Defender, mut rng: R) -> Option<Damage> where R: Rng {
+    let (weapon, base_damage) = select_weapon(attacker.class, attacker.ammo, defender.target)?;
+
+    let luck = attacker.luck_mode.roll(attacker.good_luck, attacker.bad_luck, &mut rng);
+
+    Some(Damage {
+        weapon,
+        damage: damage_with_luck(attacker, defender, base_damage, luck),
+    })
+}
+
+
+/// The defender's side of the battle, used to forecast the counter-attack.
+#[derive(Debug, Clone)]
+pub struct CounterAttack {
+    /// The defender when it counter-attacks, its HP is replaced with the HP after the attack.
+    pub attacker: Attacker,
+
+    /// The original attacker when it is counter-attacked.
+    pub defender: Defender,
+}
+
+
+/// The range of possible outcomes of an attack, before luck is rolled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Forecast {
+    pub weapon: Weapon,
+
+    /// Lowest HP removed from the defender, it is never higher than the defender's HP.
+    pub min_damage: u32,
+
+    /// Highest HP removed from the defender, it is never higher than the defender's HP.
+    pub max_damage: u32,
+
+    /// Lowest and highest HP removed from the attacker by the counter-attack.
+    ///
+    /// The lowest is 0 if the defender might be destroyed before it can counter-attack,
+    /// and both are 0 if the defender is always destroyed.
+    ///
+    /// It is `None` if the defender cannot counter-attack the attacker.
+    pub counter: Option<(u32, u32)>,
+}
+
+impl Forecast {
+    /// Whether the defender might be destroyed by the attack.
+    #[inline]
+    pub fn can_destroy(&self, defender_hp: u32) -> bool {
+        self.max_damage >= defender_hp
+    }
+}
+
+
+/// Returns the lowest and highest damage for every possible luck roll.
+fn damage_range(attacker: &Attacker, defender: &Defender) -> Option<(Weapon, u32, u32)> {
+    let (weapon, base_damage) = select_weapon(attacker.class, attacker.ammo, defender.target)?;
+
+    let (min_luck, max_luck) = attacker.luck_mode.range(attacker.good_luck, attacker.bad_luck);
+
+    let min = damage_with_luck(attacker, defender, base_damage, min_luck).min(defender.hp);
+    let max = damage_with_luck(attacker, defender, base_damage, max_luck).min(defender.hp);
+
+    Some((weapon, min, max))
+}
+
+
+/// Forecasts the attack and the counter-attack, using the same formula as [`calculate_damage`].
+///
+/// The counter-attack is `None` if the defender cannot counter-attack, for example
+/// because it is an indirect unit or because it is not next to the attacker.
+/// It is `Some((0, 0))` if the defender is always destroyed before it can counter-attack.
+///
+/// Returns `None` if the attacker cannot attack the defender.
+pub fn forecast(attacker: &Attacker, defender: &Defender, counter: Option<&CounterAttack>) -> Option<Forecast> {
+    let (weapon, min_damage, max_damage) = damage_range(attacker, defender)?;
+
+    let counter = counter.and_then(|counter| {
+        // The defender does not have a weapon which can hit the attacker
+        select_weapon(counter.attacker.class, counter.attacker.ammo, counter.defender.target)?;
+
+        // The defender is always destroyed before it can counter-attack
+        if min_damage == defender.hp {
+            return Some((0, 0));
+        }
+
+        // The counter-attack is strongest when the defender took the least damage
+        let strongest = Attacker {
+            hp: defender.hp - min_damage,
+            ..counter.attacker.clone()
+        };
+
+        let weakest = Attacker {
+            hp: defender.hp - max_damage,
+            ..counter.attacker.clone()
+        };
+
+        let (_, _, max) = damage_range(&strongest, &counter.defender)?;
+
+        let min = if weakest.hp == 0 {
+            0
+
+        } else {
+            damage_range(&weakest, &counter.defender)?.1
+        };
+
+        Some((min, max))
+    });
+
+    Some(Forecast {
+        weapon,
+        min_damage,
+        max_damage,
+        counter,
+    })
+}
@@ -0,0 +1,101 @@
+use crate::nation::{Nation};
+use crate::map::{Coord};
+use crate::state::{UnitId};
+
+
+/// Why an action is not allowed by the rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleError {
+    /// The match has ended.
+    GameOver,
+
+    /// The unit does not exist, or it has already been destroyed.
+    UnitNotFound(UnitId),
+
+    /// The unit belongs to a different player than the current player.
+    NotYourUnit(UnitId),
+
+    /// The unit has already acted this turn.
+    UnitWaited(UnitId),
+
+    /// The path is not connected, it is blocked, or it costs too much movement or fuel.
+    CannotMove(UnitId),
+
+    /// The unit is not able to capture, or the tile does not have an enemy / neutral property.
+    CannotCapture(UnitId),
+
+    /// The unit cannot be loaded into the transport.
+    CannotLoad(UnitId),
+
+    /// The unit cannot be unloaded onto the tile.
+    CannotUnload(UnitId),
+
+    /// The unit cannot join with the other unit.
+    CannotJoin(UnitId),
+
+    /// The unit cannot attack the target.
+    CannotAttack(UnitId),
+
+    /// The target is hidden in the Fog of War, or it is a hidden unit which is not next to the unit's team.
+    TargetNotVisible(UnitId),
+
+    /// The unit is not able to launch, or it is not standing on a missile silo.
+    CannotLaunch(UnitId),
+
+    /// The unit is not able to self-destruct.
+    CannotExplode(UnitId),
+
+    /// The unit is not able to supply, or there are no allied units next to it.
+    CannotSupply(UnitId),
+
+    /// The unit cannot hide / dive, or it is already in that state.
+    CannotHide(UnitId),
+
+    /// The tile is not the current player's Base / Airport / Port, it is occupied, or the unit cannot be built.
+    CannotBuild(Coord),
+
+    /// The player cannot afford the unit.
+    NotEnoughFunds(Nation),
+
+    /// The player does not have a CO, a power is already active, or the meter is not charged enough.
+    CannotActivatePower(Nation),
+
+    /// The match is not a tag match, the player does not have a partner, or a Dual Strike is active.
+    CannotSwitchCo(Nation),
+
+    /// There is nothing to undo, or hidden information was revealed since the last undo point.
+    CannotUndo,
+
+    /// There are no undone commands.
+    CannotRedo,
+}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GameOver => write!(f, "The match has ended"),
+            Self::UnitNotFound(id) => write!(f, "Unit {} does not exist", id.0),
+            Self::NotYourUnit(id) => write!(f, "Unit {} belongs to a different player", id.0),
+            Self::UnitWaited(id) => write!(f, "Unit {} has already acted this turn", id.0),
+            Self::CannotMove(id) => write!(f, "Unit {} cannot move along this path", id.0),
+            Self::CannotCapture(id) => write!(f, "Unit {} cannot capture this tile", id.0),
+            Self::CannotLoad(id) => write!(f, "Unit {} cannot be loaded into the transport", id.0),
+            Self::CannotUnload(id) => write!(f, "Unit {} cannot be unloaded onto this tile", id.0),
+            Self::CannotJoin(id) => write!(f, "Unit {} cannot join with the other unit", id.0),
+            Self::CannotAttack(id) => write!(f, "Unit {} cannot attack the target", id.0),
+            Self::TargetNotVisible(id) => write!(f, "Unit {} cannot see the target", id.0),
+            Self::CannotLaunch(id) => write!(f, "Unit {} cannot launch a missile", id.0),
+            Self::CannotExplode(id) => write!(f, "Unit {} cannot self-destruct", id.0),
+            Self::CannotSupply(id) => write!(f, "Unit {} cannot supply any units", id.0),
+            Self::CannotHide(id) => write!(f, "Unit {} cannot hide or appear", id.0),
+            Self::CannotBuild(coord) => write!(f, "Cannot build this unit at ({}, {})", coord.x, coord.y),
+            Self::NotEnoughFunds(nation) => write!(f, "{:?} does not have enough funds", nation),
+            Self::CannotActivatePower(nation) => write!(f, "{:?} cannot activate their CO power", nation),
+            Self::CannotSwitchCo(nation) => write!(f, "{:?} cannot switch their CO", nation),
+            Self::CannotUndo => write!(f, "There is nothing which can be undone"),
+            Self::CannotRedo => write!(f, "There is nothing to redo"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
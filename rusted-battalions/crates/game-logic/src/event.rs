@@ -0,0 +1,251 @@
+use crate::nation::{Nation};
+use crate::map::{Coord};
+use crate::state::{UnitId};
+use crate::co::{Co, PowerKind};
+use crate::weather::{Weather};
+
+
+/// Something which happened in the game, the renderer uses these to play animations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    TurnStarted {
+        nation: Nation,
+        day: u32,
+    },
+
+    /// Funds given by the properties at the start of the turn.
+    Income {
+        nation: Nation,
+        funds: u32,
+    },
+
+    /// The unit was repaired by a property.
+    Repaired {
+        unit: UnitId,
+        hp: u32,
+        cost: u32,
+    },
+
+    /// The fuel and ammo of the unit was refilled.
+    Resupplied {
+        unit: UnitId,
+    },
+
+    /// Daily fuel was used up at the start of the turn.
+    FuelUsed {
+        unit: UnitId,
+        fuel: u32,
+    },
+
+    /// The unit reduced the capture points of the property, the points are in tenths.
+    CaptureProgress {
+        unit: UnitId,
+        coord: Coord,
+        points: u32,
+    },
+
+    /// The property is now owned by the nation.
+    Captured {
+        unit: UnitId,
+        coord: Coord,
+        nation: Nation,
+    },
+
+    /// The property was given to the nation because its owner's HQ was captured.
+    TurnedOver {
+        coord: Coord,
+        nation: Nation,
+    },
+
+    /// The player has lost the game.
+    Defeated {
+        nation: Nation,
+    },
+
+    /// The unit was built by the current player, the cost was taken from their funds.
+    Built {
+        unit: UnitId,
+        coord: Coord,
+        cost: u32,
+    },
+
+    /// The unit moved along the path, the path includes the starting tile.
+    Moved {
+        unit: UnitId,
+        path: Vec<Coord>,
+    },
+
+    /// The unit was stopped by an enemy which it could not see, this ends the unit's action.
+    Trapped {
+        unit: UnitId,
+
+        /// Where the hidden enemy is standing.
+        coord: Coord,
+    },
+
+    /// The unit ended its action without doing anything else.
+    Waited {
+        unit: UnitId,
+    },
+
+    /// Only 1 team is left, the winners are first and then the most recently defeated players.
+    GameOver {
+        ranking: Vec<Nation>,
+    },
+
+    /// The unit was loaded into the transport.
+    Loaded {
+        unit: UnitId,
+        transport: UnitId,
+    },
+
+    /// The unit was unloaded from the transport onto the tile.
+    Unloaded {
+        unit: UnitId,
+        transport: UnitId,
+        coord: Coord,
+    },
+
+    /// The Stealth hid or the Submarine dived.
+    Hidden {
+        unit: UnitId,
+    },
+
+    /// The Stealth appeared or the Submarine surfaced.
+    Appeared {
+        unit: UnitId,
+    },
+
+    /// The unit was merged into the target, any HP above 100 was refunded.
+    Joined {
+        unit: UnitId,
+        target: UnitId,
+        hp: u32,
+        refund: u32,
+    },
+
+    /// The unit attacked the pipeseam, the HP is the pipeseam's remaining HP.
+    PipeseamDamaged {
+        unit: UnitId,
+        coord: Coord,
+        hp: u32,
+    },
+
+    /// The pipeseam reached 0 HP and it is now a plain tile.
+    PipeseamDestroyed {
+        coord: Coord,
+    },
+
+    /// The unit attacked the structure, the coordinate is the top-left tile of the structure.
+    StructureDamaged {
+        unit: UnitId,
+        coord: Coord,
+        hp: u32,
+    },
+
+    /// The structure reached 0 HP and was removed from the map.
+    StructureDestroyed {
+        coord: Coord,
+    },
+
+    /// The structure attacked at the start of its owner's turn.
+    StructureFired {
+        coord: Coord,
+        target: Coord,
+    },
+
+    /// The unit was hit by a structure.
+    StructureHit {
+        unit: UnitId,
+        damage: u32,
+    },
+
+    /// The unit launched the missile from the silo, the silo is now empty.
+    MissileLaunched {
+        unit: UnitId,
+        silo: Coord,
+        target: Coord,
+    },
+
+    /// The unit was hit by a missile.
+    MissileHit {
+        unit: UnitId,
+        damage: u32,
+    },
+
+    /// The Oozium engulfed the target, the target is destroyed and the Oozium moves onto its tile.
+    Engulfed {
+        unit: UnitId,
+        target: UnitId,
+    },
+
+    /// The Black Bomb self-destructed and was removed from the map.
+    Exploded {
+        unit: UnitId,
+        coord: Coord,
+    },
+
+    /// The unit was hit by a Black Bomb explosion.
+    BombHit {
+        unit: UnitId,
+        damage: u32,
+    },
+
+    /// The unit attacked or counter-attacked the target, the HP is the target's remaining HP.
+    Attacked {
+        unit: UnitId,
+        target: UnitId,
+        damage: u32,
+        hp: u32,
+    },
+
+    /// The unit was destroyed, the value is used for the power meter.
+    Destroyed {
+        unit: UnitId,
+        coord: Coord,
+        nation: Nation,
+        value: u32,
+    },
+
+    /// The player activated their CO power.
+    PowerActivated {
+        nation: Nation,
+        co: Co,
+        power: PowerKind,
+    },
+
+    /// The player's partner CO became the active CO.
+    CoSwitched {
+        nation: Nation,
+        co: Co,
+    },
+
+    /// The unit was healed by a CO power or a structure.
+    Healed {
+        unit: UnitId,
+        hp: u32,
+    },
+
+    /// The unit was damaged by a CO power.
+    PowerHit {
+        unit: UnitId,
+        damage: u32,
+    },
+
+    /// The weather changed at the start of the day or because of a CO power.
+    WeatherChanged {
+        weather: Weather,
+    },
+
+    /// Funds given by a CO power.
+    FundsGained {
+        nation: Nation,
+        funds: u32,
+    },
+
+    /// The unit ran out of fuel, it is followed by [`Event::Destroyed`] for the unit and its cargo.
+    Crashed {
+        unit: UnitId,
+        coord: Coord,
+    },
+}
@@ -0,0 +1,44 @@
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+impl GameState {
+    /// Changes whether the unit is hidden, this uses up the unit's action for the turn.
+    fn set_hidden(&mut self, id: UnitId, hidden: bool) -> Result<(), RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        if !unit.class.can_hide() || unit.hidden == hidden || unit.is_loaded() {
+            return Err(RuleError::CannotHide(id));
+        }
+
+        let unit = self.units.get_mut(&id).unwrap();
+
+        unit.hidden = hidden;
+        unit.waited = true;
+
+        Ok(())
+    }
+
+    /// A Stealth hides or a Submarine dives.
+    ///
+    /// Hidden units can only be seen by adjacent enemy units, and they use up more fuel.
+    pub fn hide_unit(&mut self, id: UnitId) -> Result<Vec<Event>, RuleError> {
+        self.set_hidden(id, true)?;
+        Ok(vec![Event::Hidden { unit: id }])
+    }
+
+    /// A hidden Stealth appears or a dived Submarine surfaces.
+    pub fn appear_unit(&mut self, id: UnitId) -> Result<Vec<Event>, RuleError> {
+        self.set_hidden(id, false)?;
+        Ok(vec![Event::Appeared { unit: id }])
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::{BTreeSet};
+
+use crate::nation::{Nation};
+use crate::map::{Coord};
+use crate::random::{Rng, SeededRng};
+use crate::state::{GameState, UnitId};
+use crate::command::{Command};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+/// Counts how many random numbers were rolled by a command.
+struct CountingRng<'a> {
+    rng: &'a mut SeededRng,
+    rolls: u32,
+}
+
+impl<'a> Rng for CountingRng<'a> {
+    fn range(&mut self, min: i32, max: i32) -> i32 {
+        self.rolls += 1;
+        self.rng.range(min, max)
+    }
+}
+
+
+/// Everything which is needed to go back to an earlier point in the match.
+///
+/// The state contains the units, buildings, funds, and CO meters. The Fog of War
+/// is calculated from the units, so it is also restored.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    state: GameState,
+    rng: SeededRng,
+}
+
+
+/// Runs a match from a starting state and a list of [`Command`]s, with undo and redo.
+///
+/// The luck is rolled from [`Rules::seed`](crate::Rules::seed), which also picks the weather,
+/// so the same starting state and commands will always produce the same match.
+/// A match can be saved or shared as only its starting state and commands.
+///
+/// Undo is not allowed after a command reveals hidden information, such as rolling
+/// the luck for an attack, ending the turn, or seeing an enemy unit in the fog
+/// (including running into one).
+/// In [`Rules::sandbox`](crate::Rules::sandbox) matches every command can be undone.
+#[derive(Debug, Clone)]
+pub struct History {
+    state: GameState,
+    rng: SeededRng,
+
+    /// Every command which was applied, excluding undone commands.
+    commands: Vec<Command>,
+
+    undo: Vec<Snapshot>,
+
+    /// Undone commands, the most recently undone command is last.
+    redo: Vec<Command>,
+}
+
+impl History {
+    /// The match starts from the state, which should already have its first turn started.
+    pub fn new(state: GameState) -> Self {
+        let rng = SeededRng::new(state.rules.seed);
+
+        Self {
+            state,
+            rng,
+            commands: vec![],
+            undo: vec![],
+            redo: vec![],
+        }
+    }
+
+    /// Applies every command in order, stopping at the first command which is not allowed.
+    pub fn replay(state: GameState, commands: &[Command]) -> Result<Self, RuleError> {
+        let mut history = Self::new(state);
+
+        for command in commands {
+            history.apply(command.clone())?;
+        }
+
+        Ok(history)
+    }
+
+    #[inline]
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// The commands which produced the current state.
+    #[inline]
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Runs the command with [`GameState::apply`], this clears the redo stack.
+    pub fn apply(&mut self, command: Command) -> Result<Vec<Event>, RuleError> {
+        let events = self.run(command)?;
+
+        self.redo.clear();
+
+        Ok(events)
+    }
+
+    /// Goes back to the state before the most recent command.
+    pub fn undo(&mut self) -> Result<(), RuleError> {
+        let snapshot = self.undo.pop().ok_or(RuleError::CannotUndo)?;
+
+        self.state = snapshot.state;
+        self.rng = snapshot.rng;
+
+        self.redo.push(self.commands.pop().unwrap());
+
+        Ok(())
+    }
+
+    /// Runs the most recently undone command again, it has the same result as before.
+    pub fn redo(&mut self) -> Result<Vec<Event>, RuleError> {
+        let command = self.redo.pop().ok_or(RuleError::CannotRedo)?;
+
+        self.run(command)
+    }
+
+    fn run(&mut self, command: Command) -> Result<Vec<Event>, RuleError> {
+        let snapshot = Snapshot {
+            state: self.state.clone(),
+            rng: self.rng.clone(),
+        };
+
+        let nation = self.state.current_player().nation;
+        let seen = visible_enemies(&self.state, nation);
+
+        let mut rng = CountingRng { rng: &mut self.rng, rolls: 0 };
+
+        let events = self.state.apply(command.clone(), &mut rng)?;
+
+        let reveals = rng.rolls > 0 ||
+            matches!(command, Command::EndTurn | Command::Resign) ||
+            events.iter().any(|event| matches!(event, Event::Trapped { .. })) ||
+            !visible_enemies(&self.state, nation).is_subset(&seen);
+
+        self.commands.push(command);
+
+        if reveals && !self.state.rules.sandbox {
+            self.undo.clear();
+
+        } else {
+            self.undo.push(snapshot);
+        }
+
+        Ok(events)
+    }
+}
+
+
+/// The enemy units that the player's team can see, and where they are.
+fn visible_enemies(state: &GameState, nation: Nation) -> BTreeSet<(UnitId, Coord)> {
+    let vision = state.vision(nation);
+
+    state.units()
+        .filter(|(_, unit)| !state.same_team(unit.nation, nation) && !unit.is_loaded() && vision.can_see(unit))
+        .map(|(id, unit)| (id, unit.coord))
+        .collect()
+}
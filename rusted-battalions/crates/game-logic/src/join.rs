@@ -0,0 +1,80 @@
+use crate::unit::{Unit};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+/// Funds refunded when joining, for every HP above 100.
+///
+/// This uses the non-rounded HP and the build cost of the unit which is joining.
+pub fn join_refund(unit: &Unit, target: &Unit) -> u32 {
+    let hp = unit.hp + target.hp;
+
+    if hp > 100 {
+        (hp - 100) * unit.build_cost / 100
+
+    } else {
+        0
+    }
+}
+
+/// Whether the unit can join the target, ignoring where they are standing.
+///
+/// The HP does not matter, units can always join.
+pub fn can_join_into(unit: &Unit, target: &Unit) -> bool {
+    unit.class == target.class &&
+    unit.nation == target.nation &&
+    unit.cargo.is_empty() &&
+    !unit.is_loaded() &&
+    !target.is_loaded()
+}
+
+
+impl GameState {
+    /// The unit merges with the target, the unit must be on the same tile as the target.
+    ///
+    /// Units get onto the target's tile with [`Command::Move`](crate::Command::Move), which joins them.
+    ///
+    /// Units can always join, regardless of their HP. The HP, fuel, and ammo are added
+    /// together, and any HP above 100 is refunded.
+    pub fn join_unit(&mut self, id: UnitId, target_id: UnitId) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+        let target = self.unit(target_id).ok_or(RuleError::UnitNotFound(target_id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        let can_join =
+            id != target_id &&
+            unit.coord == target.coord &&
+            can_join_into(unit, target);
+
+        if !can_join {
+            return Err(RuleError::CannotJoin(id));
+        }
+
+        let refund = join_refund(unit, target);
+
+        let hp = (unit.hp + target.hp).min(100);
+        let fuel = (unit.fuel + target.fuel).min(unit.class.max_fuel());
+        let ammo = (unit.ammo + target.ammo).min(unit.class.max_ammo());
+
+        self.remove_unit(id);
+
+        let target = self.units.get_mut(&target_id).unwrap();
+
+        target.hp = hp;
+        target.fuel = fuel;
+        target.ammo = ammo;
+        target.waited = true;
+
+        self.players[self.current].funds += refund;
+
+        Ok(vec![Event::Joined { unit: id, target: target_id, hp, refund }])
+    }
+}
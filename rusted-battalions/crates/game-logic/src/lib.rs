@@ -0,0 +1,61 @@
+mod nation;
+mod map;
+mod terrain;
+mod building;
+mod unit;
+mod movement;
+mod vision;
+mod random;
+mod luck;
+mod damage;
+mod state;
+mod event;
+mod turn;
+mod repair;
+mod error;
+mod capture;
+mod transport;
+mod join;
+mod co;
+mod rules;
+mod tag;
+mod hide;
+mod structure;
+mod silo;
+mod oozium;
+mod black_bomb;
+mod combat;
+mod supply;
+mod command;
+mod production;
+mod victory;
+mod weather;
+mod history;
+
+pub use nation::{Nation};
+pub use map::{Coord, Map, Tile};
+pub use terrain::{TerrainClass, Orientation};
+pub use building::{BuildingClass, Direction};
+pub use unit::{Unit, UnitClass};
+pub use movement::{MovementType, Occupant, Reachable, ReachableTile, reachable_tiles, reachable_tiles_with};
+pub use vision::{Vision, vision_range, terrain_vision};
+pub use random::{Rng, SeededRng};
+pub use luck::{LuckMode};
+pub use damage::{Weapon, Target, Attacker, Defender, Damage, base_damage, select_weapon, damage_with_luck, calculate_damage, CounterAttack, Forecast, forecast};
+pub use state::{GameState, Player, UnitId};
+pub use event::{Event};
+pub use repair::{repair_cost, affordable_repair, PROPERTY_REPAIR};
+pub use error::{RuleError};
+pub use capture::{Capture, CAPTURE_POINTS};
+pub use transport::{can_unload_from, can_load_into};
+pub use join::{join_refund, can_join_into};
+pub use co::{Co, CoDefinition, PowerDefinition, PowerKind, PlayerCo, Modifier, UnitFilter, TerrainFilter, Effect, Instant, STAR_VALUE};
+pub use rules::{Rules, CoMode};
+pub use victory::{Victory};
+pub use weather::{Weather};
+pub use structure::{StructureAction, STRUCTURE_HP};
+pub use silo::{MISSILE_DAMAGE, MISSILE_RADIUS};
+pub use black_bomb::{BLACK_BOMB_DAMAGE, BLACK_BOMB_RADIUS};
+pub use combat::{CombatResult};
+pub use command::{Command};
+pub use history::{History};
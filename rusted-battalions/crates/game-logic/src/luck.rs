@@ -0,0 +1,38 @@
+use crate::random::{Rng};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LuckMode {
+    /// Advance Wars rolls 2 numbers, 1 number for the good luck, and 1 number
+    /// for the bad luck, and then subtracts them.
+    ///
+    /// This means that good luck and bad luck tend to cancel each other out.
+    Classic,
+
+    /// Rusted Battalions rolls 1 number from -bad_luck to +good_luck.
+    ///
+    /// This gives bad luck COs like Flak / Jugger / Sonja wildly varying luck.
+    #[default]
+    SingleRoll,
+}
+
+impl LuckMode {
+    /// Returns the lowest and highest possible luck, this is the same for every mode.
+    #[inline]
+    pub fn range(&self, good_luck: u32, bad_luck: u32) -> (i32, i32) {
+        (-(bad_luck as i32), good_luck as i32)
+    }
+
+    /// Returns the luck for a single attack.
+    pub fn roll<R>(&self, good_luck: u32, bad_luck: u32, mut rng: R) -> i32 where R: Rng {
+        match self {
+            Self::Classic => {
+                rng.range(0, good_luck as i32) - rng.range(0, bad_luck as i32)
+            },
+            Self::SingleRoll => {
+                let (min, max) = self.range(good_luck, bad_luck);
+                rng.range(min, max)
+            },
+        }
+    }
+}
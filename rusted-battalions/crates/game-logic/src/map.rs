@@ -0,0 +1,174 @@
+use crate::nation::{Nation};
+use crate::terrain::{TerrainClass};
+use crate::building::{BuildingClass};
+use crate::unit::{UnitClass};
+use crate::movement::{MovementType};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl Coord {
+    #[inline]
+    pub const fn new(x: u32, y: u32) -> Self {
+        Self { x, y }
+    }
+
+    /// Number of tiles between the two coordinates, without moving diagonally.
+    #[inline]
+    pub fn distance(&self, other: &Self) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Returns the up / down / left / right coordinates.
+    ///
+    /// This does not check the width / height of the map, use [`Map::adjacent`] for that.
+    pub fn adjacent(&self) -> impl Iterator<Item = Self> {
+        let Self { x, y } = *self;
+
+        [
+            y.checked_sub(1).map(|y| Self { x, y }),
+            y.checked_add(1).map(|y| Self { x, y }),
+            x.checked_sub(1).map(|x| Self { x, y }),
+            x.checked_add(1).map(|x| Self { x, y }),
+        ].into_iter().flatten()
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Tile {
+    pub coord: Coord,
+    pub class: TerrainClass,
+    pub building: Option<BuildingClass>,
+
+    /// The nation which owns the building.
+    pub owner: Option<Nation>,
+
+    /// The top-left tile of the building, for buildings which cover multiple tiles.
+    pub origin: Coord,
+}
+
+impl Tile {
+    /// Returns the terrain stars for a unit standing on this tile.
+    ///
+    /// Buildings replace the stars of the terrain underneath them,
+    /// and air units never get any terrain stars.
+    pub fn defense_stars(&self, class: UnitClass) -> u32 {
+        if let MovementType::Air = class.movement_type() {
+            0
+
+        } else if let Some(building) = self.building {
+            building.defense_stars()
+
+        } else {
+            self.class.defense_stars()
+        }
+    }
+}
+
+
+/// The static layout of a map, it does not contain any units.
+#[derive(Debug, Clone)]
+pub struct Map {
+    pub width: u32,
+    pub height: u32,
+    tiles: Vec<Tile>,
+}
+
+impl Map {
+    pub fn new(width: u32, height: u32) -> Self {
+        let mut tiles = Vec::with_capacity(width as usize * height as usize);
+
+        for y in 0..height {
+            for x in 0..width {
+                tiles.push(Tile {
+                    coord: Coord { x, y },
+                    class: TerrainClass::Empty,
+                    building: None,
+                    owner: None,
+                    origin: Coord { x, y },
+                });
+            }
+        }
+
+        Self {
+            width,
+            height,
+            tiles,
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, coord: &Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    fn get_index(&self, coord: &Coord) -> Option<usize> {
+        if self.contains(coord) {
+            Some(((coord.y * self.width) + coord.x) as usize)
+
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, coord: &Coord) -> Option<&Tile> {
+        self.get_index(coord).map(|index| &self.tiles[index])
+    }
+
+    pub fn get_mut(&mut self, coord: &Coord) -> Option<&mut Tile> {
+        self.get_index(coord).map(|index| &mut self.tiles[index])
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = &Tile> {
+        self.tiles.iter()
+    }
+
+    pub fn tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile> {
+        self.tiles.iter_mut()
+    }
+
+    /// Places the building on every tile that it covers, starting at the top-left tile.
+    pub fn set_building(&mut self, origin: Coord, building: BuildingClass, owner: Option<Nation>) {
+        let (width, height) = building.size();
+
+        assert!(origin.x + width <= self.width && origin.y + height <= self.height, "Building {:?} does not fit in the map", building);
+
+        for y in origin.y..(origin.y + height) {
+            for x in origin.x..(origin.x + width) {
+                let tile = self.get_mut(&Coord { x, y }).unwrap();
+                tile.building = Some(building);
+                tile.owner = owner;
+                tile.origin = origin;
+            }
+        }
+    }
+
+    /// Removes the building from every tile that it covers.
+    pub fn remove_building(&mut self, coord: &Coord) {
+        if let Some(tile) = self.get(coord) {
+            if let Some(building) = tile.building {
+                let origin = tile.origin;
+                let (width, height) = building.size();
+
+                for y in origin.y..(origin.y + height) {
+                    for x in origin.x..(origin.x + width) {
+                        let tile = self.get_mut(&Coord { x, y }).unwrap();
+                        tile.building = None;
+                        tile.owner = None;
+                        tile.origin = tile.coord;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the up / down / left / right coordinates which are inside of the map.
+    pub fn adjacent<'a>(&'a self, coord: &Coord) -> impl Iterator<Item = Coord> + 'a {
+        coord.adjacent().filter(move |coord| self.contains(coord))
+    }
+}
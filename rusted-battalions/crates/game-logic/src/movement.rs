@@ -0,0 +1,187 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+
+use crate::map::{Coord, Map, Tile};
+use crate::unit::{Unit};
+use crate::weather::{Weather};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MovementType {
+    Foot,
+    Boots,
+    Treads,
+    Tires,
+    Air,
+    Sea,
+    Lander,
+    Pipe,
+}
+
+impl MovementType {
+    /// Returns the number of movement points needed to enter the tile.
+    ///
+    /// Returns `None` if the tile cannot be entered.
+    pub fn cost(&self, tile: &Tile) -> Option<u32> {
+        match tile.building {
+            Some(building) => building.movement_cost(*self),
+            None => tile.class.movement_cost(*self),
+        }
+    }
+}
+
+
+/// Who is standing on a tile, relative to the moving unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Occupant {
+    /// Units can move through allies, but they cannot stop on them.
+    Ally,
+
+    /// Units cannot move through enemies.
+    Enemy,
+
+    /// An allied transport which can carry the unit, the unit can stop on it to load.
+    Transport,
+
+    /// An allied unit of the same class, the unit can stop on it to join.
+    Join,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReachableTile {
+    /// Total movement points needed to reach this tile.
+    pub cost: u32,
+
+    /// The previous tile in the cheapest path, or `None` for the starting tile.
+    pub parent: Option<Coord>,
+
+    /// Whether an allied unit is standing on this tile, and the unit cannot stop on it.
+    pub occupied: bool,
+}
+
+
+/// All of the tiles that a unit can reach this turn.
+#[derive(Debug, Clone)]
+pub struct Reachable {
+    pub start: Coord,
+    tiles: BTreeMap<Coord, ReachableTile>,
+}
+
+impl Reachable {
+    #[inline]
+    pub fn get(&self, coord: &Coord) -> Option<&ReachableTile> {
+        self.tiles.get(coord)
+    }
+
+    #[inline]
+    pub fn contains(&self, coord: &Coord) -> bool {
+        self.tiles.contains_key(coord)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&Coord, &ReachableTile)> {
+        self.tiles.iter()
+    }
+
+    /// Returns the tiles which the unit is allowed to stop on.
+    pub fn destinations(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.tiles.iter()
+            .filter(|(_, tile)| !tile.occupied)
+            .map(|(coord, _)| *coord)
+    }
+
+    /// Returns the cheapest path from the starting tile to `to`.
+    ///
+    /// The path includes both the starting tile and `to`.
+    pub fn path(&self, to: &Coord) -> Option<Vec<Coord>> {
+        let mut tile = self.tiles.get(to)?;
+
+        let mut path = vec![*to];
+
+        while let Some(parent) = tile.parent {
+            path.push(parent);
+            tile = &self.tiles[&parent];
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+
+/// Uses Dijkstra's algorithm to find every tile that the unit can move to.
+///
+/// The `occupied` function is called to check whether another unit is standing on a tile.
+#[inline]
+pub fn reachable_tiles<F>(unit: &Unit, map: &Map, occupied: F) -> Reachable
+    where F: FnMut(&Coord) -> Option<Occupant> {
+
+    reachable_tiles_with(unit, map, Weather::Clear, unit.class.movement(), occupied)
+}
+
+/// Same as [`reachable_tiles`], except with the weather and the maximum movement points.
+pub fn reachable_tiles_with<F>(unit: &Unit, map: &Map, weather: Weather, max_cost: u32, mut occupied: F) -> Reachable
+    where F: FnMut(&Coord) -> Option<Occupant> {
+
+    let movement = unit.class.movement_type();
+
+    let mut tiles = BTreeMap::new();
+    let mut queue = BinaryHeap::new();
+
+    tiles.insert(unit.coord, ReachableTile {
+        cost: 0,
+        parent: None,
+        occupied: false,
+    });
+
+    queue.push(Reverse((0, unit.coord)));
+
+    while let Some(Reverse((cost, coord))) = queue.pop() {
+        if cost > tiles[&coord].cost {
+            continue;
+        }
+
+        for next in map.adjacent(&coord) {
+            let Some(tile) = map.get(&next) else {
+                continue;
+            };
+
+            let Some(tile_cost) = weather.movement_cost(movement, tile) else {
+                continue;
+            };
+
+            let next_cost = cost + tile_cost;
+
+            if next_cost > max_cost {
+                continue;
+            }
+
+            if let Some(old) = tiles.get(&next) {
+                if old.cost <= next_cost {
+                    continue;
+                }
+            }
+
+            let occupant = occupied(&next);
+
+            if let Some(Occupant::Enemy) = occupant {
+                continue;
+            }
+
+            tiles.insert(next, ReachableTile {
+                cost: next_cost,
+                parent: Some(coord),
+                occupied: occupant == Some(Occupant::Ally),
+            });
+
+            queue.push(Reverse((next_cost, next)));
+        }
+    }
+
+    Reachable {
+        start: unit.coord,
+        tiles,
+    }
+}
@@ -0,0 +1,18 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Nation {
+    OrangeStar,
+    BlueMoon,
+    GreenEarth,
+    YellowComet,
+    BlackHole,
+}
+
+impl Nation {
+    pub const ALL: &[Self] = &[
+        Self::OrangeStar,
+        Self::BlueMoon,
+        Self::GreenEarth,
+        Self::YellowComet,
+        Self::BlackHole,
+    ];
+}
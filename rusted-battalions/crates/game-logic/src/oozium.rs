@@ -0,0 +1,59 @@
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+impl GameState {
+    /// The Oozium engulfs the adjacent enemy ground unit, destroying it and moving onto its tile.
+    ///
+    /// This ignores the HP of both units, and the target cannot counter-attack.
+    pub fn engulf_unit(&mut self, id: UnitId, target: UnitId) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        let other = self.unit(target).ok_or(RuleError::UnitNotFound(target))?;
+
+        let can_engulf =
+            unit.class.can_engulf() &&
+            !unit.is_loaded() &&
+            !other.is_loaded() &&
+            !self.same_team(other.nation, unit.nation) &&
+            other.class.is_ground() &&
+            other.coord.distance(&unit.coord) == 1;
+
+        if !can_engulf {
+            return Err(RuleError::CannotAttack(id));
+        }
+
+        let coord = other.coord;
+        let nation = unit.nation;
+
+        let value = other.value();
+        let target_nation = other.nation;
+
+        // The target loses all of its HP, which charges the power meters the same as an attack
+        if let Some(co) = self.player_mut(nation).and_then(|player| player.co.as_mut()) {
+            co.charge(value, 0);
+        }
+
+        if let Some(co) = self.player_mut(target_nation).and_then(|player| player.co.as_mut()) {
+            co.charge(0, value);
+        }
+
+        let mut events = vec![Event::Engulfed { unit: id, target }];
+
+        events.extend(self.destroy_unit_by(target, Some(nation)));
+
+        self.move_unit(id, coord)?;
+        self.units.get_mut(&id).unwrap().waited = true;
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::nation::{Nation};
+use crate::map::{Coord};
+use crate::building::{BuildingClass};
+use crate::unit::{Unit, UnitClass};
+use crate::state::{GameState};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+impl GameState {
+    /// Funds needed by the nation to build the unit, including any CO modifiers.
+    pub fn build_cost(&self, nation: Nation, class: UnitClass) -> u32 {
+        match self.player(nation).and_then(|player| player.co.as_ref()) {
+            Some(co) => co.cost(class),
+            None => class.cost(),
+        }
+    }
+
+    /// Whether the nation owns at least 1 Lab.
+    pub fn has_lab(&self, nation: Nation) -> bool {
+        self.map.tiles().any(|tile| tile.owner == Some(nation) && tile.building == Some(BuildingClass::Lab))
+    }
+
+    /// Returns the units that the current player can build on the tile, and their costs.
+    ///
+    /// This includes units which the player cannot afford, so the UI can show them.
+    pub fn build_options(&self, coord: &Coord) -> Vec<(UnitClass, u32)> {
+        let nation = self.current_player().nation;
+
+        let Some(tile) = self.map.get(coord) else {
+            return vec![];
+        };
+
+        let Some(building) = tile.building else {
+            return vec![];
+        };
+
+        if tile.owner != Some(nation) || self.unit_at(coord).is_some() {
+            return vec![];
+        }
+
+        let has_lab = self.has_lab(nation);
+
+        building.build_list().iter()
+            .filter(|class| !self.rules.banned_units.contains(class))
+            .filter(|class| !(self.rules.lab_units && class.requires_lab() && !has_lab))
+            .map(|class| (*class, self.build_cost(nation, *class)))
+            .collect()
+    }
+
+    /// The current player builds the unit on their Base, Airport, or Port.
+    ///
+    /// The new unit cannot act until the next turn.
+    pub fn build_unit(&mut self, coord: Coord, class: UnitClass) -> Result<Vec<Event>, RuleError> {
+        let nation = self.current_player().nation;
+
+        let (_, cost) = self.build_options(&coord).into_iter()
+            .find(|(option, _)| *option == class)
+            .ok_or(RuleError::CannotBuild(coord))?;
+
+        let player = &mut self.players[self.current];
+
+        if player.funds < cost {
+            return Err(RuleError::NotEnoughFunds(nation));
+        }
+
+        player.funds -= cost;
+
+        let unit = self.add_unit(Unit {
+            waited: true,
+            build_cost: cost,
+            ..Unit::new(coord, class, nation)
+        });
+
+        Ok(vec![Event::Built { unit, coord, cost }])
+    }
+}
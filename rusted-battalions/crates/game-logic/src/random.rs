@@ -0,0 +1,67 @@
+/// Source of randomness for the game rules.
+///
+/// The rules never use global randomness, so that the same
+/// inputs will always produce the same outputs.
+pub trait Rng {
+    /// Returns a random number from `min` to `max` (inclusive).
+    fn range(&mut self, min: i32, max: i32) -> i32;
+}
+
+impl<A> Rng for &mut A where A: Rng + ?Sized {
+    #[inline]
+    fn range(&mut self, min: i32, max: i32) -> i32 {
+        (**self).range(min, max)
+    }
+}
+
+
+/// Deterministic random number generator, using the SplitMix64 algorithm.
+///
+/// The same seed will always produce the same numbers on every platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Creates a generator for a random event in a match, such as the daily weather.
+    ///
+    /// The same match seed and key always produce the same numbers, and different
+    /// match seeds produce different numbers for the same key.
+    pub fn with_key(seed: u64, key: u64) -> Self {
+        Self::new(Self::new(seed).next_u64() ^ key)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl Rng for SeededRng {
+    fn range(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min <= max, "Invalid range {}..={}", min, max);
+
+        let size = (max as i64 - min as i64 + 1) as u64;
+
+        // Rejects the numbers at the end which would cause a bias towards the lower numbers
+        let zone = u64::MAX - (u64::MAX % size);
+
+        loop {
+            let value = self.next_u64();
+
+            if value < zone {
+                return (min as i64 + (value % size) as i64) as i32;
+            }
+        }
+    }
+}
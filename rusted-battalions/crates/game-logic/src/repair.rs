@@ -0,0 +1,72 @@
+use crate::unit::{Unit};
+use crate::state::{GameState};
+use crate::event::{Event};
+
+
+/// Maximum HP that a property can repair every turn.
+pub const PROPERTY_REPAIR: u32 = 20;
+
+
+/// Funds needed to repair the unit by `hp`, based on the unit's build cost.
+///
+/// This is rounded up, so repairs are never free.
+pub fn repair_cost(unit: &Unit, hp: u32) -> u32 {
+    (unit.build_cost * hp).div_ceil(100)
+}
+
+/// Returns how much HP the property will repair, limited by the funds.
+///
+/// Dual Strike skips the repair if the full +20 HP cannot be afforded, but
+/// Rusted Battalions repairs as much HP as the funds can pay for.
+pub fn affordable_repair(unit: &Unit, funds: u32) -> u32 {
+    let hp = PROPERTY_REPAIR.min(100 - unit.hp);
+
+    if unit.build_cost == 0 {
+        hp
+
+    } else {
+        let affordable = (funds as u64 * 100) / unit.build_cost as u64;
+        hp.min(affordable as u32)
+    }
+}
+
+
+impl GameState {
+    /// Repairs and resupplies the current player's units which are on owned properties.
+    ///
+    /// Units are repaired in order of their [`UnitId`], which means that older units
+    /// are repaired first when there are not enough funds to repair every unit.
+    pub(crate) fn repair_phase(&mut self, events: &mut Vec<Event>) {
+        let nation = self.current_player().nation;
+
+        for id in self.current_units() {
+            let unit = &self.units[&id];
+
+            let repairs = self.map.get(&unit.coord).map(|tile| {
+                tile.owner == Some(nation) && tile.building.map(|building| building.repairs(unit.class)).unwrap_or(false)
+            }).unwrap_or(false);
+
+            if !repairs {
+                continue;
+            }
+
+            let player = &mut self.players[self.current];
+            let unit = self.units.get_mut(&id).unwrap();
+
+            let hp = affordable_repair(unit, player.funds);
+
+            if hp > 0 {
+                let cost = repair_cost(unit, hp);
+
+                player.funds -= cost;
+                unit.hp += hp;
+
+                events.push(Event::Repaired { unit: id, hp, cost });
+            }
+
+            if unit.resupply() {
+                events.push(Event::Resupplied { unit: id });
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::unit::{UnitClass};
+use crate::victory::{Victory};
+
+
+/// Whether each player has one CO or a tag-team of two COs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CoMode {
+    #[default]
+    Single,
+
+    /// Players can switch COs at the end of their turn and use a Dual Strike.
+    Tag,
+}
+
+
+/// Options which are chosen before the match starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub co_mode: CoMode,
+
+    /// Units which cannot be built in this match.
+    pub banned_units: Vec<UnitClass>,
+
+    /// Whether units such as the Neotank can only be built by players who own a Lab.
+    pub lab_units: bool,
+
+    /// Whether Fog of War is enabled, so players only see the tiles near their units and properties.
+    pub fog: bool,
+
+    /// Whether the weather is randomly chosen at the start of every day.
+    pub random_weather: bool,
+
+    /// Ways that players can win or lose, by default only capturing an HQ.
+    pub victory: Vec<Victory>,
+
+    /// Whether every command can be undone, even after hidden information is revealed.
+    pub sandbox: bool,
+
+    /// Seed for every random roll in the match, such as the weather and the luck rolled by [`History`](crate::History).
+    pub seed: u64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            co_mode: CoMode::default(),
+            banned_units: vec![],
+            lab_units: false,
+            fog: false,
+            random_weather: false,
+            victory: vec![Victory::Hq],
+            sandbox: false,
+            seed: 0,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::map::{Coord};
+use crate::building::{BuildingClass};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+/// HP of damage dealt by a missile, it can never destroy a unit.
+pub const MISSILE_DAMAGE: u32 = 30;
+
+/// Units within this distance of the target tile are hit by the missile.
+pub const MISSILE_RADIUS: u32 = 2;
+
+
+impl GameState {
+    /// The unit launches the missile from the silo that it is standing on.
+    ///
+    /// Every unit within 2 tiles of the target takes 30 HP of damage, but it
+    /// cannot go below 1 HP. Units inside of transports are not affected.
+    ///
+    /// The silo becomes empty and it cannot be used again.
+    pub fn launch_missile(&mut self, id: UnitId, target: Coord) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        let silo = unit.coord;
+
+        let can_launch =
+            unit.class.can_capture() &&
+            !unit.is_loaded() &&
+            self.map.contains(&target) &&
+            self.map.get(&silo).and_then(|tile| tile.building) == Some(BuildingClass::MissileSilo);
+
+        if !can_launch {
+            return Err(RuleError::CannotLaunch(id));
+        }
+
+        self.units.get_mut(&id).unwrap().waited = true;
+        self.map.get_mut(&silo).unwrap().building = Some(BuildingClass::MissileSiloEmpty);
+
+        let mut events = vec![Event::MissileLaunched { unit: id, silo, target }];
+
+        for (id, unit) in self.units.iter_mut() {
+            if !unit.is_loaded() && unit.coord.distance(&target) <= MISSILE_RADIUS {
+                let damage = MISSILE_DAMAGE.min(unit.hp - 1);
+
+                if damage > 0 {
+                    unit.hp -= damage;
+                    events.push(Event::MissileHit { unit: *id, damage });
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
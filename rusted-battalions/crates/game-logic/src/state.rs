@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap};
+
+use crate::nation::{Nation};
+use crate::map::{Coord, Map};
+use crate::unit::{Unit};
+use crate::capture::{Capture};
+use crate::error::{RuleError};
+use crate::event::{Event};
+use crate::co::{PlayerCo};
+use crate::rules::{Rules};
+use crate::weather::{Weather};
+
+
+/// Unique identifier for a unit, it is never reused within a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnitId(pub u32);
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub nation: Nation,
+    pub funds: u32,
+
+    /// Defeated players are skipped when cycling turns.
+    pub defeated: bool,
+
+    /// Players in the same team win together, `None` is a team with only this player.
+    pub team: Option<u32>,
+
+    /// Players without a CO do not get any CO bonuses or powers.
+    pub co: Option<PlayerCo>,
+}
+
+impl Player {
+    pub fn new(nation: Nation) -> Self {
+        Self {
+            nation,
+            funds: 0,
+            defeated: false,
+            team: None,
+            co: None,
+        }
+    }
+}
+
+
+/// The full rules state of a game in progress.
+#[derive(Debug, Clone)]
+pub struct GameState {
+    pub map: Map,
+
+    pub rules: Rules,
+
+    /// Weather for today, CO powers can temporarily change it.
+    pub weather: Weather,
+
+    /// Weather caused by a CO power, it lasts until the nation's next turn.
+    pub(crate) power_weather: Option<(Nation, Weather)>,
+
+    /// Sorted by [`Nation`], which is also the turn order.
+    pub(crate) players: Vec<Player>,
+
+    pub(crate) units: BTreeMap<UnitId, Unit>,
+
+    next_unit_id: u32,
+
+    /// Properties which are partially captured.
+    pub(crate) captures: BTreeMap<Coord, Capture>,
+
+    /// HP of pipeseams which were damaged but not destroyed.
+    pub(crate) structures: BTreeMap<Coord, u32>,
+
+    /// Starts at day 1.
+    pub(crate) day: u32,
+
+    /// Index into `players` for whose turn it is.
+    pub(crate) current: usize,
+
+    /// Defeated players, in the order that they were defeated.
+    pub(crate) defeat_order: Vec<Nation>,
+
+    pub(crate) game_over: bool,
+}
+
+impl GameState {
+    /// Creates a game on day 1, call [`GameState::start_turn`] to begin the first turn.
+    pub fn new(map: Map, nations: &[Nation]) -> Self {
+        assert!(!nations.is_empty(), "Game must have at least 1 player");
+
+        let mut nations = nations.to_vec();
+
+        nations.sort();
+        nations.dedup();
+
+        Self {
+            map,
+            rules: Rules::default(),
+            weather: Weather::Clear,
+            power_weather: None,
+            players: nations.into_iter().map(Player::new).collect(),
+            units: BTreeMap::new(),
+            next_unit_id: 0,
+            captures: BTreeMap::new(),
+            structures: BTreeMap::new(),
+            day: 1,
+            current: 0,
+            defeat_order: vec![],
+            game_over: false,
+        }
+    }
+
+    #[inline]
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    #[inline]
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn player(&self, nation: Nation) -> Option<&Player> {
+        self.players.iter().find(|player| player.nation == nation)
+    }
+
+    pub fn player_mut(&mut self, nation: Nation) -> Option<&mut Player> {
+        self.players.iter_mut().find(|player| player.nation == nation)
+    }
+
+    /// The player whose turn it is.
+    #[inline]
+    pub fn current_player(&self) -> &Player {
+        &self.players[self.current]
+    }
+
+    pub fn add_unit(&mut self, unit: Unit) -> UnitId {
+        let id = UnitId(self.next_unit_id);
+
+        self.next_unit_id += 1;
+
+        self.units.insert(id, unit);
+
+        id
+    }
+
+    /// Removes the unit and resets any property it was capturing.
+    ///
+    /// If the unit is a transport then its cargo is also removed.
+    pub fn remove_unit(&mut self, id: UnitId) -> Option<Unit> {
+        self.reset_capture(id);
+
+        let unit = self.units.remove(&id)?;
+
+        if let Some(transport) = unit.transport {
+            if let Some(transport) = self.units.get_mut(&transport) {
+                transport.cargo.retain(|cargo| *cargo != id);
+            }
+        }
+
+        for cargo in unit.cargo.iter() {
+            self.remove_unit(*cargo);
+        }
+
+        Some(unit)
+    }
+
+    /// Changes the position of the unit, this does not check the movement rules.
+    ///
+    /// If the unit leaves the tile then it stops capturing, and it is marked as moved.
+    pub fn move_unit(&mut self, id: UnitId, coord: Coord) -> Result<(), RuleError> {
+        let unit = self.units.get(&id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.coord != coord {
+            self.set_coord(id, coord);
+            self.reset_capture(id);
+            self.units.get_mut(&id).unwrap().moved = true;
+        }
+
+        Ok(())
+    }
+
+    /// Changes the position of the unit and its cargo.
+    pub(crate) fn set_coord(&mut self, id: UnitId, coord: Coord) {
+        let unit = self.units.get_mut(&id).unwrap();
+
+        unit.coord = coord;
+
+        for cargo in unit.cargo.clone() {
+            self.set_coord(cargo, coord);
+        }
+    }
+
+    pub fn unit(&self, id: UnitId) -> Option<&Unit> {
+        self.units.get(&id)
+    }
+
+    pub fn unit_mut(&mut self, id: UnitId) -> Option<&mut Unit> {
+        self.units.get_mut(&id)
+    }
+
+    /// Iterates over the units, sorted by their [`UnitId`].
+    pub fn units(&self) -> impl Iterator<Item = (UnitId, &Unit)> {
+        self.units.iter().map(|(id, unit)| (*id, unit))
+    }
+
+    /// Returns the unit standing on the tile, this ignores units inside of transports.
+    pub fn unit_at(&self, coord: &Coord) -> Option<(UnitId, &Unit)> {
+        self.units().find(|(_, unit)| unit.coord == *coord && !unit.is_loaded())
+    }
+
+    /// Destroys the unit and its cargo.
+    ///
+    /// The value of the cargo charges the power meter of its owner.
+    #[inline]
+    pub fn destroy_unit(&mut self, id: UnitId) -> Vec<Event> {
+        self.destroy_unit_by(id, None)
+    }
+
+    /// Destroys the unit and its cargo, the value of the cargo charges the power
+    /// meters of its owner and of the nation which destroyed it.
+    ///
+    /// The transport does not charge the meters, that is done when it is damaged.
+    pub(crate) fn destroy_unit_by(&mut self, id: UnitId, destroyer: Option<Nation>) -> Vec<Event> {
+        let mut events = vec![];
+
+        if let Some(unit) = self.units.get(&id) {
+            let mut cargo = unit.cargo.clone();
+
+            events.push(Event::Destroyed {
+                unit: id,
+                coord: unit.coord,
+                nation: unit.nation,
+                value: unit.value(),
+            });
+
+            while let Some(id) = cargo.pop() {
+                let unit = &self.units[&id];
+
+                cargo.extend(unit.cargo.iter().copied());
+
+                let (nation, value) = (unit.nation, unit.value());
+
+                events.push(Event::Destroyed {
+                    unit: id,
+                    coord: unit.coord,
+                    nation,
+                    value,
+                });
+
+                if let Some(co) = self.player_mut(nation).and_then(|player| player.co.as_mut()) {
+                    co.charge(0, value);
+                }
+
+                if let Some(co) = destroyer.and_then(|destroyer| self.player_mut(destroyer)).and_then(|player| player.co.as_mut()) {
+                    co.charge(value, 0);
+                }
+            }
+
+            self.remove_unit(id);
+        }
+
+        events
+    }
+}
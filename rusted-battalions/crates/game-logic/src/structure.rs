@@ -0,0 +1,327 @@
+use crate::nation::{Nation};
+use crate::map::{Coord};
+use crate::terrain::{TerrainClass};
+use crate::building::{BuildingClass, Direction};
+use crate::damage::{Defender, Target, calculate_damage};
+use crate::random::{Rng, SeededRng};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+/// HP of structures like pipeseams, Advance Wars uses 99 instead.
+pub const STRUCTURE_HP: u32 = 100;
+
+
+/// What a Black Hole structure does at the start of its owner's turn.
+///
+/// Structures only affect units of other nations, and they can never destroy a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureAction {
+    /// The structure does not do anything.
+    None,
+
+    /// Damages every unit in the same rows and columns as the structure.
+    Beam { damage: u32 },
+
+    /// Damages the most valuable unit in front of the cannon.
+    ///
+    /// The cannon can hit units up to `range` tiles in front of it, and it gets 1 tile
+    /// wider on each side for every tile further away.
+    Cannon { range: u32, damage: u32 },
+
+    /// Heals the owner's units within `range` tiles, or every unit if the range is `None`.
+    Heal { hp: u32, range: Option<u32> },
+
+    /// Fires a missile at the tile which deals the most total damage to units within `radius`.
+    Missile { radius: u32, damage: u32 },
+
+    /// Erupts on random tiles within `range` tiles, damaging the unit on each tile.
+    Eruption { shots: u32, range: u32, damage: u32 },
+}
+
+
+/// Number of tiles between the value and the span, or 0 if it is inside of the span.
+fn outside(value: u32, start: u32, length: u32) -> u32 {
+    let end = start + length - 1;
+    start.saturating_sub(value) + value.saturating_sub(end)
+}
+
+/// Number of tiles between the coordinate and the closest tile of the building.
+fn footprint_distance(origin: &Coord, building: BuildingClass, coord: &Coord) -> u32 {
+    let (width, height) = building.size();
+    outside(coord.x, origin.x, width) + outside(coord.y, origin.y, height)
+}
+
+/// Whether the coordinate is within the cone in front of the cannon.
+fn in_cone(origin: &Coord, building: BuildingClass, direction: Direction, range: u32, coord: &Coord) -> bool {
+    let (width, height) = building.size();
+
+    let x = coord.x as i64;
+    let y = coord.y as i64;
+    let left = origin.x as i64;
+    let top = origin.y as i64;
+    let right = left + width as i64 - 1;
+    let bottom = top + height as i64 - 1;
+
+    let (forward, side) = match direction {
+        Direction::Up => (top - y, outside(coord.x, origin.x, width)),
+        Direction::Down => (y - bottom, outside(coord.x, origin.x, width)),
+        Direction::Left => (left - x, outside(coord.y, origin.y, height)),
+        Direction::Right => (x - right, outside(coord.y, origin.y, height)),
+    };
+
+    forward >= 1 && forward <= range as i64 && (side as i64) < forward
+}
+
+
+impl GameState {
+    /// Returns the HP of the pipeseam or structure, or `None` if it cannot be attacked.
+    ///
+    /// Structures which cover multiple tiles return the same HP for every tile.
+    pub fn structure_hp(&self, coord: &Coord) -> Option<u32> {
+        let tile = self.map.get(coord)?;
+
+        let max_hp = match tile.building {
+            Some(building) => building.max_hp()?,
+            None => match tile.class {
+                TerrainClass::Pipeseam { destroyed: false } => STRUCTURE_HP,
+                _ => return None,
+            },
+        };
+
+        Some(self.structures.get(&tile.origin).copied().unwrap_or(max_hp))
+    }
+
+    /// The unit attacks the pipeseam without moving, pipeseams do not counter-attack.
+    ///
+    /// When the pipeseam reaches 0 HP it is destroyed, and it becomes a plain tile
+    /// which can be moved through.
+    pub fn attack_pipeseam<R>(&mut self, id: UnitId, coord: Coord, rng: R) -> Result<Vec<Event>, RuleError> where R: Rng {
+        let is_pipeseam = self.map.get(&coord).map(|tile| tile.building.is_none()).unwrap_or(false);
+
+        let hp = self.hit_structure(id, &coord, is_pipeseam, rng)?;
+
+        let mut events = vec![Event::PipeseamDamaged { unit: id, coord, hp }];
+
+        if hp == 0 {
+            self.structures.remove(&coord);
+            self.map.get_mut(&coord).unwrap().class = TerrainClass::Pipeseam { destroyed: true };
+            events.push(Event::PipeseamDestroyed { coord });
+
+        } else {
+            self.structures.insert(coord, hp);
+        }
+
+        Ok(events)
+    }
+
+    /// The unit attacks any tile of the Black Hole structure without moving, structures do not counter-attack.
+    ///
+    /// When the structure reaches 0 HP it is destroyed, and it is removed from every tile that it covers.
+    pub fn attack_structure<R>(&mut self, id: UnitId, coord: Coord, rng: R) -> Result<Vec<Event>, RuleError> where R: Rng {
+        let tile = self.map.get(&coord).ok_or(RuleError::CannotAttack(id))?;
+
+        let origin = tile.origin;
+        let is_structure = tile.building.map(|building| building.is_structure()).unwrap_or(false);
+
+        let own_structure = match (self.unit(id), tile.owner) {
+            (Some(unit), Some(owner)) => self.same_team(unit.nation, owner),
+            _ => false,
+        };
+
+        if own_structure {
+            return Err(RuleError::CannotAttack(id));
+        }
+
+        let hp = self.hit_structure(id, &coord, is_structure, rng)?;
+
+        let mut events = vec![Event::StructureDamaged { unit: id, coord: origin, hp }];
+
+        if hp == 0 {
+            self.structures.remove(&origin);
+            self.map.remove_building(&origin);
+            events.push(Event::StructureDestroyed { coord: origin });
+
+        } else {
+            self.structures.insert(origin, hp);
+        }
+
+        Ok(events)
+    }
+
+    /// Validates the attack and damages the pipeseam or structure, returning its new HP.
+    fn hit_structure<R>(&mut self, id: UnitId, coord: &Coord, valid: bool, rng: R) -> Result<u32, RuleError> where R: Rng {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        let hp = self.structure_hp(coord).filter(|_| valid).ok_or(RuleError::CannotAttack(id))?;
+
+        if !self.vision(unit.nation).is_visible(coord) {
+            return Err(RuleError::TargetNotVisible(id));
+        }
+
+        let (min_range, max_range) = self.attack_range(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        let distance = unit.coord.distance(coord);
+
+        if unit.is_loaded() || (unit.moved && unit.class.is_indirect()) || distance < min_range || distance > max_range {
+            return Err(RuleError::CannotAttack(id));
+        }
+
+        let attacker = self.attacker(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        // Structures use the same damage values as pipeseams
+        let defender = Defender {
+            hp,
+            ..Defender::new(Target::Pipeseam)
+        };
+
+        let damage = calculate_damage(&attacker, &defender, rng).ok_or(RuleError::CannotAttack(id))?;
+
+        let unit = self.units.get_mut(&id).unwrap();
+
+        unit.use_ammo(damage.weapon);
+        unit.waited = true;
+
+        Ok(hp.saturating_sub(damage.damage))
+    }
+
+    /// Every structure owned by the current player does its [`StructureAction`].
+    pub(crate) fn structure_phase(&mut self, events: &mut Vec<Event>) {
+        let nation = self.current_player().nation;
+
+        let structures: Vec<(Coord, BuildingClass)> = self.map.tiles()
+            .filter(|tile| tile.owner == Some(nation) && tile.origin == tile.coord)
+            .filter_map(|tile| tile.building.map(|building| (tile.coord, building)))
+            .filter(|(_, building)| building.is_structure())
+            .collect();
+
+        for (origin, building) in structures {
+            match building.action() {
+                StructureAction::None => {},
+
+                StructureAction::Beam { damage } => {
+                    let (width, height) = building.size();
+
+                    let targets = self.structure_targets(nation, |coord| {
+                        outside(coord.x, origin.x, width) == 0 || outside(coord.y, origin.y, height) == 0
+                    });
+
+                    events.push(Event::StructureFired { coord: origin, target: origin });
+                    self.damage_units(&targets, damage, events);
+                },
+
+                StructureAction::Cannon { range, damage } => {
+                    let direction = building.direction().unwrap();
+
+                    let targets = self.structure_targets(nation, |coord| {
+                        in_cone(&origin, building, direction, range, coord)
+                    });
+
+                    // Ties are won by the lowest UnitId
+                    let target = targets.into_iter()
+                        .rev()
+                        .max_by_key(|id| self.units[id].value());
+
+                    if let Some(target) = target {
+                        events.push(Event::StructureFired { coord: origin, target: self.units[&target].coord });
+                        self.damage_units(&[target], damage, events);
+                    }
+                },
+
+                StructureAction::Heal { hp, range } => {
+                    for (id, unit) in self.units.iter_mut() {
+                        let in_range = range
+                            .map(|range| footprint_distance(&origin, building, &unit.coord) <= range)
+                            .unwrap_or(true);
+
+                        if unit.nation == nation && !unit.is_loaded() && in_range {
+                            let hp = hp.min(100 - unit.hp);
+
+                            if hp > 0 {
+                                unit.hp += hp;
+                                events.push(Event::Healed { unit: *id, hp });
+                            }
+                        }
+                    }
+                },
+
+                StructureAction::Missile { radius, damage } => {
+                    let mut best: Option<(Coord, u32)> = None;
+
+                    for tile in self.map.tiles() {
+                        let total: u32 = self.structure_targets(nation, |coord| coord.distance(&tile.coord) <= radius)
+                            .into_iter()
+                            .map(|id| damage.min(self.units[&id].hp - 1))
+                            .sum();
+
+                        if total > best.map(|(_, total)| total).unwrap_or(0) {
+                            best = Some((tile.coord, total));
+                        }
+                    }
+
+                    if let Some((target, _)) = best {
+                        let targets = self.structure_targets(nation, |coord| coord.distance(&target) <= radius);
+
+                        events.push(Event::StructureFired { coord: origin, target });
+                        self.damage_units(&targets, damage, events);
+                    }
+                },
+
+                StructureAction::Eruption { shots, range, damage } => {
+                    let (width, height) = building.size();
+
+                    // The eruptions are different every day, but they are always the same for the same match seed
+                    let mut rng = SeededRng::with_key(self.rules.seed, ((self.day as u64) << 32) ^ ((origin.y as u64) << 16) ^ (origin.x as u64));
+
+                    let min_x = origin.x.saturating_sub(range) as i32;
+                    let min_y = origin.y.saturating_sub(range) as i32;
+                    let max_x = (origin.x + width - 1 + range).min(self.map.width - 1) as i32;
+                    let max_y = (origin.y + height - 1 + range).min(self.map.height - 1) as i32;
+
+                    for _ in 0..shots {
+                        let target = Coord::new(
+                            rng.range(min_x, max_x) as u32,
+                            rng.range(min_y, max_y) as u32,
+                        );
+
+                        let targets = self.structure_targets(nation, |coord| *coord == target);
+
+                        events.push(Event::StructureFired { coord: origin, target });
+                        self.damage_units(&targets, damage, events);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Units which can be hit by the nation's structures, sorted by [`UnitId`].
+    fn structure_targets<F>(&self, nation: Nation, mut f: F) -> Vec<UnitId> where F: FnMut(&Coord) -> bool {
+        self.units.iter()
+            .filter(|(_, unit)| !self.same_team(unit.nation, nation) && !unit.is_loaded() && f(&unit.coord))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Structures can never reduce a unit below 1 HP.
+    fn damage_units(&mut self, targets: &[UnitId], damage: u32, events: &mut Vec<Event>) {
+        for id in targets {
+            let unit = self.units.get_mut(id).unwrap();
+
+            let damage = damage.min(unit.hp - 1);
+
+            if damage > 0 {
+                unit.hp -= damage;
+                events.push(Event::StructureHit { unit: *id, damage });
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+impl GameState {
+    /// The APC refills the fuel and ammo of every adjacent unit in its team.
+    ///
+    /// APCs also resupply adjacent units at the start of the turn, this lets them do it immediately.
+    pub fn supply_units(&mut self, id: UnitId) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        let nation = unit.nation;
+        let coord = unit.coord;
+
+        let targets: Vec<UnitId> = self.units()
+            .filter(|(_, other)| self.same_team(other.nation, nation) && !other.is_loaded() && other.coord.distance(&coord) == 1)
+            .map(|(id, _)| id)
+            .collect();
+
+        if !unit.class.can_supply() || unit.is_loaded() || targets.is_empty() {
+            return Err(RuleError::CannotSupply(id));
+        }
+
+        self.units.get_mut(&id).unwrap().waited = true;
+
+        let mut events = vec![];
+
+        for target in targets {
+            if self.units.get_mut(&target).unwrap().resupply() {
+                events.push(Event::Resupplied { unit: target });
+            }
+        }
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::state::{GameState};
+use crate::event::{Event};
+use crate::error::{RuleError};
+use crate::co::{PowerKind};
+use crate::rules::{CoMode};
+
+
+impl GameState {
+    /// Swaps the current player's active CO with their partner, and then ends the turn.
+    pub fn switch_co(&mut self) -> Result<Vec<Event>, RuleError> {
+        let nation = self.current_player().nation;
+
+        if self.rules.co_mode != CoMode::Tag {
+            return Err(RuleError::CannotSwitchCo(nation));
+        }
+
+        let co = match &mut self.players[self.current].co {
+            Some(co) if co.power != Some(PowerKind::Tag) => co,
+            _ => return Err(RuleError::CannotSwitchCo(nation)),
+        };
+
+        let partner = co.partner.ok_or(RuleError::CannotSwitchCo(nation))?;
+
+        co.partner = Some(co.co);
+        co.co = partner;
+
+        let mut events = vec![Event::CoSwitched { nation, co: partner }];
+
+        events.extend(self.end_turn());
+
+        Ok(events)
+    }
+
+    /// After the first turn of a Dual Strike the partner becomes active and
+    /// the same player gets a second turn with the partner's Super CO Power.
+    ///
+    /// The second turn happens on the same day, and it does not have any upkeep.
+    pub(crate) fn dual_strike_turn(&mut self) -> Option<Vec<Event>> {
+        let nation = self.current_player().nation;
+
+        let co = self.players[self.current].co.as_mut()?;
+
+        if co.power != Some(PowerKind::Tag) || co.tag_turn {
+            return None;
+        }
+
+        let partner = co.partner?;
+
+        co.partner = Some(co.co);
+        co.co = partner;
+        co.tag_turn = true;
+
+        let mut events = vec![
+            Event::CoSwitched { nation, co: partner },
+            Event::TurnStarted { nation, day: self.day },
+        ];
+
+        for unit in self.units.values_mut() {
+            if unit.nation == nation {
+                unit.waited = false;
+                unit.moved = false;
+            }
+        }
+
+        self.power_instants(partner, PowerKind::Tag, &mut events);
+
+        Some(events)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::movement::{MovementType};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TerrainClass {
+    Empty,
+    Grass,
+    Road {
+        ruins: bool,
+    },
+    Bridge {
+        orientation: Orientation,
+    },
+    Forest,
+    Mountain {
+        variant: u32,
+    },
+    Pipeline,
+    Pipeseam {
+        destroyed: bool,
+    },
+    Ocean,
+    River,
+    Shoal,
+    Reef,
+}
+
+impl TerrainClass {
+    pub const ALL: &[Self] = &[
+        Self::Empty,
+        Self::Grass,
+        Self::Road { ruins: false },
+        Self::Bridge { orientation: Orientation::Horizontal },
+        Self::Forest,
+        Self::Mountain { variant: 0 },
+        Self::Pipeline,
+        Self::Pipeseam { destroyed: false, },
+        Self::Ocean,
+        Self::River,
+        Self::Shoal,
+        Self::Reef,
+    ];
+
+    /// Each star gives +10% defense, scaled by the non-rounded HP of the defender.
+    pub fn defense_stars(&self) -> u32 {
+        match self {
+            Self::Mountain { .. } => 4,
+            Self::Forest => 2,
+            Self::Grass | Self::Road { ruins: true } | Self::Pipeseam { destroyed: true } | Self::Reef => 1,
+
+            Self::Empty |
+            Self::Road { ruins: false } |
+            Self::Bridge { .. } |
+            Self::Pipeline |
+            Self::Pipeseam { destroyed: false } |
+            Self::Ocean |
+            Self::River |
+            Self::Shoal => 0,
+        }
+    }
+
+    /// Units inside of this terrain can only be seen by adjacent units.
+    pub fn conceals(&self) -> bool {
+        matches!(self, Self::Forest | Self::Reef)
+    }
+
+    /// Returns the number of movement points needed to enter this terrain.
+    ///
+    /// Returns `None` if the terrain cannot be entered.
+    pub fn movement_cost(&self, movement: MovementType) -> Option<u32> {
+        use MovementType::*;
+
+        match self {
+            Self::Empty => None,
+
+            // Destroyed pipeseams are the same as plains
+            Self::Grass | Self::Road { ruins: true } | Self::Pipeseam { destroyed: true } => match movement {
+                Foot | Boots | Treads | Air => Some(1),
+                Tires => Some(2),
+                Sea | Lander | Pipe => None,
+            },
+
+            Self::Road { ruins: false } | Self::Bridge { .. } => match movement {
+                Foot | Boots | Treads | Tires | Air => Some(1),
+                Sea | Lander | Pipe => None,
+            },
+
+            Self::Forest => match movement {
+                Foot | Boots | Air => Some(1),
+                Treads => Some(2),
+                Tires => Some(3),
+                Sea | Lander | Pipe => None,
+            },
+
+            Self::Mountain { .. } => match movement {
+                Boots | Air => Some(1),
+                Foot => Some(2),
+                Treads | Tires | Sea | Lander | Pipe => None,
+            },
+
+            Self::River => match movement {
+                Boots | Air => Some(1),
+                Foot => Some(2),
+                Treads | Tires | Sea | Lander | Pipe => None,
+            },
+
+            Self::Pipeline | Self::Pipeseam { destroyed: false } => match movement {
+                Pipe => Some(1),
+                Foot | Boots | Treads | Tires | Air | Sea | Lander => None,
+            },
+
+            Self::Ocean => match movement {
+                Air | Sea | Lander => Some(1),
+                Foot | Boots | Treads | Tires | Pipe => None,
+            },
+
+            Self::Shoal => match movement {
+                Foot | Boots | Treads | Tires | Air | Lander => Some(1),
+                Sea | Pipe => None,
+            },
+
+            Self::Reef => match movement {
+                Air => Some(1),
+                Sea | Lander => Some(2),
+                Foot | Boots | Treads | Tires | Pipe => None,
+            },
+        }
+    }
+}
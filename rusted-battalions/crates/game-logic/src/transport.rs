@@ -0,0 +1,107 @@
+use crate::map::{Coord, Tile};
+use crate::terrain::{TerrainClass};
+use crate::building::{BuildingClass};
+use crate::unit::{Unit, UnitClass};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+use crate::error::{RuleError};
+
+
+/// Whether the transport is able to unload its cargo while standing on the tile.
+///
+/// Landers and Black Boats can only unload from shoals and ports.
+pub fn can_unload_from(transport: UnitClass, tile: &Tile) -> bool {
+    match transport {
+        UnitClass::Lander |
+        UnitClass::BlackBoat => {
+            tile.class == TerrainClass::Shoal || tile.building == Some(BuildingClass::Port)
+        },
+        _ => true,
+    }
+}
+
+
+/// Whether the unit can be loaded into the transport, ignoring where they are standing.
+pub fn can_load_into(unit: &Unit, transport: &Unit) -> bool {
+    !unit.is_loaded() &&
+    !transport.is_loaded() &&
+    transport.nation == unit.nation &&
+    transport.class.can_carry(unit.class) &&
+    (transport.cargo.len() as u32) < transport.class.cargo_capacity()
+}
+
+
+impl GameState {
+    /// The unit moves into the transport, the unit must be on the same tile as the transport.
+    ///
+    /// Units get onto the transport's tile with [`Command::Move`](crate::Command::Move), which loads them.
+    /// Transports can load and unload an unlimited number of times per turn.
+    pub fn load_unit(&mut self, id: UnitId, transport_id: UnitId) -> Result<Vec<Event>, RuleError> {
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+        let transport = self.unit(transport_id).ok_or(RuleError::UnitNotFound(transport_id))?;
+
+        if unit.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(id));
+        }
+
+        if unit.waited {
+            return Err(RuleError::UnitWaited(id));
+        }
+
+        let can_load =
+            id != transport_id &&
+            transport.coord == unit.coord &&
+            can_load_into(unit, transport);
+
+        if !can_load {
+            return Err(RuleError::CannotLoad(id));
+        }
+
+        let coord = transport.coord;
+
+        self.reset_capture(id);
+        self.set_coord(id, coord);
+
+        let unit = self.units.get_mut(&id).unwrap();
+        unit.transport = Some(transport_id);
+        unit.waited = true;
+
+        self.units.get_mut(&transport_id).unwrap().cargo.push(id);
+
+        Ok(vec![Event::Loaded { unit: id, transport: transport_id }])
+    }
+
+    /// The unit leaves the transport and moves onto an adjacent tile.
+    ///
+    /// The unloaded unit cannot act until the next turn.
+    pub fn unload_unit(&mut self, transport_id: UnitId, id: UnitId, coord: Coord) -> Result<Vec<Event>, RuleError> {
+        let transport = self.unit(transport_id).ok_or(RuleError::UnitNotFound(transport_id))?;
+        let unit = self.unit(id).ok_or(RuleError::UnitNotFound(id))?;
+
+        if transport.nation != self.current_player().nation {
+            return Err(RuleError::NotYourUnit(transport_id));
+        }
+
+        let can_unload =
+            !transport.is_loaded() &&
+            unit.transport == Some(transport_id) &&
+            transport.coord.distance(&coord) == 1 &&
+            self.unit_at(&coord).is_none() &&
+            self.map.get(&transport.coord).map(|tile| can_unload_from(transport.class, tile)).unwrap_or(false) &&
+            self.map.get(&coord).and_then(|tile| unit.class.movement_type().cost(tile)).is_some();
+
+        if !can_unload {
+            return Err(RuleError::CannotUnload(id));
+        }
+
+        self.units.get_mut(&transport_id).unwrap().cargo.retain(|cargo| *cargo != id);
+
+        self.set_coord(id, coord);
+
+        let unit = self.units.get_mut(&id).unwrap();
+        unit.transport = None;
+        unit.waited = true;
+
+        Ok(vec![Event::Unloaded { unit: id, transport: transport_id, coord }])
+    }
+}
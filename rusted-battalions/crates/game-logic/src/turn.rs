@@ -0,0 +1,163 @@
+use crate::map::{Coord};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+
+
+impl GameState {
+    /// Ends the current player's turn and starts the next player's turn.
+    ///
+    /// After the last player it goes back to the first player and starts a new day.
+    ///
+    /// During a Dual Strike the current player gets a second turn instead,
+    /// unless they were defeated during the first turn.
+    ///
+    /// The victory conditions are checked at the end of every day.
+    pub fn end_turn(&mut self) -> Vec<Event> {
+        if !self.current_player().defeated {
+            if let Some(events) = self.dual_strike_turn() {
+                return events;
+            }
+        }
+
+        let len = self.players.len();
+        let day = self.day;
+
+        let mut next = self.current;
+
+        loop {
+            next += 1;
+
+            if next >= len {
+                next = 0;
+                self.day += 1;
+            }
+
+            if next == self.current || !self.players[next].defeated {
+                break;
+            }
+        }
+
+        self.current = next;
+
+        let mut events = vec![];
+
+        if self.day != day {
+            self.check_victory(&mut events);
+
+            if self.game_over {
+                return events;
+            }
+
+            self.daily_weather(&mut events);
+
+            // The first player of the day might have been defeated
+            if self.current_player().defeated {
+                events.extend(self.end_turn());
+                return events;
+            }
+        }
+
+        events.extend(self.start_turn());
+        events
+    }
+
+    /// Starts the current player's turn.
+    ///
+    /// The player's active CO power and its weather end, and then the upkeep is done in this order:
+    ///
+    /// 1. Funds are given for every owned property.
+    /// 2. Units on owned properties are repaired as much as the funds allow, and resupplied.
+    /// 3. Units next to an APC are resupplied.
+    /// 4. Daily fuel is used up, and air / sea units with no fuel crash.
+    ///    Hidden Stealths and dived Submarines use up more fuel.
+    ///
+    /// Because resupply happens before the daily fuel, an air / sea unit with 0 fuel
+    /// which is next to an APC will not crash.
+    pub fn start_turn(&mut self) -> Vec<Event> {
+        let nation = self.current_player().nation;
+
+        let mut events = vec![Event::TurnStarted { nation, day: self.day }];
+
+        self.end_power();
+        self.end_power_weather(&mut events);
+
+        for unit in self.units.values_mut() {
+            if unit.nation == nation {
+                unit.waited = false;
+                unit.moved = false;
+            }
+        }
+
+        self.pay_income(&mut events);
+        self.repair_phase(&mut events);
+        self.resupply_units(&mut events);
+        self.use_daily_fuel(&mut events);
+        self.structure_phase(&mut events);
+
+        events
+    }
+
+    /// Ids of the current player's units, sorted by [`UnitId`].
+    ///
+    /// Units inside of transports do not get any upkeep.
+    pub(crate) fn current_units(&self) -> Vec<UnitId> {
+        let nation = self.current_player().nation;
+
+        self.units()
+            .filter(|(_, unit)| unit.nation == nation && !unit.is_loaded())
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn pay_income(&mut self, events: &mut Vec<Event>) {
+        let nation = self.current_player().nation;
+
+        let funds = self.map.tiles()
+            .filter(|tile| tile.owner == Some(nation))
+            .filter_map(|tile| tile.building)
+            .map(|building| building.income())
+            .sum();
+
+        if funds > 0 {
+            self.players[self.current].funds += funds;
+            events.push(Event::Income { nation, funds });
+        }
+    }
+
+    fn resupply_units(&mut self, events: &mut Vec<Event>) {
+        let nation = self.current_player().nation;
+
+        let suppliers: Vec<Coord> = self.units.values()
+            .filter(|unit| unit.nation == nation && unit.class.can_supply() && !unit.is_loaded())
+            .map(|unit| unit.coord)
+            .collect();
+
+        for id in self.current_units() {
+            let unit = self.units.get_mut(&id).unwrap();
+
+            let adjacent = suppliers.iter().any(|coord| coord.distance(&unit.coord) == 1);
+
+            if adjacent && unit.resupply() {
+                events.push(Event::Resupplied { unit: id });
+            }
+        }
+    }
+
+    fn use_daily_fuel(&mut self, events: &mut Vec<Event>) {
+        for id in self.current_units() {
+            let unit = self.units.get_mut(&id).unwrap();
+
+            let fuel = unit.daily_fuel().min(unit.fuel);
+
+            if fuel > 0 {
+                unit.fuel -= fuel;
+                events.push(Event::FuelUsed { unit: id, fuel });
+            }
+
+            if unit.fuel == 0 && unit.class.crashes() {
+                events.push(Event::Crashed { unit: id, coord: unit.coord });
+                events.extend(self.destroy_unit(id));
+            }
+        }
+    }
+}
@@ -0,0 +1,513 @@
+use crate::nation::{Nation};
+use crate::map::{Coord};
+use crate::movement::{MovementType};
+use crate::state::{UnitId};
+use crate::damage::{Weapon, Target, select_weapon};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitClass {
+    Infantry,
+    Mech,
+    Recon,
+    APC,
+    Artillery,
+    Tank,
+    AntiAir,
+    Missile,
+    Rocket,
+    MediumTank,
+    Piperunner,
+    Neotank,
+    MegaTank,
+    BCopter,
+    TCopter,
+    Fighter,
+    Bomber,
+    Stealth,
+    Battleship,
+    Cruiser,
+    Submarine,
+    Lander,
+    Carrier,
+    BlackBoat,
+    BlackBomb,
+    Oozium,
+}
+
+impl UnitClass {
+    pub const ALL: &[Self] = &[
+        Self::Infantry,
+        Self::Mech,
+        Self::Recon,
+        Self::APC,
+        Self::Artillery,
+        Self::Tank,
+        Self::AntiAir,
+        Self::Missile,
+        Self::Rocket,
+        Self::MediumTank,
+        Self::Piperunner,
+        Self::Neotank,
+        Self::MegaTank,
+        Self::BCopter,
+        Self::TCopter,
+        Self::Fighter,
+        Self::Bomber,
+        Self::Stealth,
+        Self::Battleship,
+        Self::Cruiser,
+        Self::Submarine,
+        Self::Lander,
+        Self::Carrier,
+        Self::BlackBoat,
+        Self::BlackBomb,
+        Self::Oozium,
+    ];
+
+    pub fn movement_type(&self) -> MovementType {
+        match self {
+            Self::Infantry => MovementType::Foot,
+
+            Self::Mech |
+            Self::Oozium => MovementType::Boots,
+
+            Self::APC |
+            Self::Artillery |
+            Self::Tank |
+            Self::AntiAir |
+            Self::MediumTank |
+            Self::Neotank |
+            Self::MegaTank => MovementType::Treads,
+
+            Self::Recon |
+            Self::Missile |
+            Self::Rocket => MovementType::Tires,
+
+            Self::BCopter |
+            Self::TCopter |
+            Self::Fighter |
+            Self::Bomber |
+            Self::Stealth |
+            Self::BlackBomb => MovementType::Air,
+
+            Self::Battleship |
+            Self::Cruiser |
+            Self::Submarine |
+            Self::Carrier => MovementType::Sea,
+
+            Self::Lander |
+            Self::BlackBoat => MovementType::Lander,
+
+            Self::Piperunner => MovementType::Pipe,
+        }
+    }
+
+    /// Number of movement points the unit has every turn.
+    pub fn movement(&self) -> u32 {
+        match self {
+            Self::Infantry => 3,
+            Self::Mech => 2,
+            Self::Recon => 8,
+            Self::APC => 6,
+            Self::Artillery => 5,
+            Self::Tank => 6,
+            Self::AntiAir => 6,
+            Self::Missile => 4,
+            Self::Rocket => 5,
+            Self::MediumTank => 5,
+            Self::Piperunner => 9,
+            Self::Neotank => 6,
+            Self::MegaTank => 4,
+            Self::BCopter => 6,
+            Self::TCopter => 6,
+            Self::Fighter => 9,
+            Self::Bomber => 7,
+            Self::Stealth => 6,
+            Self::Battleship => 5,
+            Self::Cruiser => 6,
+            Self::Submarine => 5,
+            Self::Lander => 6,
+            Self::Carrier => 5,
+            Self::BlackBoat => 7,
+            Self::BlackBomb => 9,
+            Self::Oozium => 1,
+        }
+    }
+
+    /// Minimum and maximum attack range, in tiles.
+    pub fn attack_range(&self) -> (u32, u32) {
+        match self {
+            Self::Artillery => (2, 3),
+            Self::Rocket => (3, 5),
+            Self::Missile => (3, 5),
+            Self::Piperunner => (2, 5),
+            Self::Battleship => (2, 6),
+            Self::Carrier => (3, 8),
+            _ => (1, 1),
+        }
+    }
+
+    /// Indirect units cannot move and attack on the same turn, and they never counter-attack.
+    pub fn is_indirect(&self) -> bool {
+        self.attack_range().1 > 1
+    }
+
+    /// Whether the unit can capture properties.
+    pub fn can_capture(&self) -> bool {
+        matches!(self, Self::Infantry | Self::Mech)
+    }
+
+    /// Whether the unit can engulf adjacent ground units, instead of attacking them.
+    pub fn can_engulf(&self) -> bool {
+        matches!(self, Self::Oozium)
+    }
+
+    /// Whether the unit can self-destruct, damaging every nearby unit.
+    pub fn can_explode(&self) -> bool {
+        matches!(self, Self::BlackBomb)
+    }
+
+    /// Whether the unit refills the fuel and ammo of adjacent allied units.
+    pub fn can_supply(&self) -> bool {
+        matches!(self, Self::APC)
+    }
+
+    /// Whether the player must own a Lab to build the unit, when `Rules::lab_units` is enabled.
+    pub fn requires_lab(&self) -> bool {
+        matches!(self, Self::Piperunner | Self::Neotank | Self::MegaTank | Self::Stealth | Self::BlackBomb | Self::Carrier | Self::BlackBoat)
+    }
+
+    /// Funds needed to build the unit, before any CO modifiers.
+    pub fn cost(&self) -> u32 {
+        match self {
+            Self::Infantry => 1000,
+            Self::Mech => 3000,
+            Self::Recon => 4000,
+            Self::APC => 5000,
+            Self::Artillery => 6000,
+            Self::Tank => 7000,
+            Self::AntiAir => 8000,
+            Self::Missile => 12000,
+            Self::Rocket => 15000,
+            Self::MediumTank => 16000,
+            Self::Piperunner => 20000,
+            Self::Neotank => 22000,
+            Self::MegaTank => 28000,
+            Self::BCopter => 9000,
+            Self::TCopter => 5000,
+            Self::Fighter => 20000,
+            Self::Bomber => 22000,
+            Self::Stealth => 24000,
+            Self::Battleship => 28000,
+            Self::Cruiser => 18000,
+            Self::Submarine => 20000,
+            Self::Lander => 12000,
+            Self::Carrier => 30000,
+            Self::BlackBoat => 7500,
+            Self::BlackBomb => 25000,
+            // Cannot be built, it is only placed by the map
+            Self::Oozium => 0,
+        }
+    }
+
+    pub fn max_fuel(&self) -> u32 {
+        match self {
+            Self::Infantry => 99,
+            Self::Mech => 70,
+            Self::Recon => 80,
+            Self::APC => 70,
+            Self::Artillery => 50,
+            Self::Tank => 70,
+            Self::AntiAir => 60,
+            Self::Missile => 50,
+            Self::Rocket => 50,
+            Self::MediumTank => 50,
+            Self::Piperunner => 99,
+            Self::Neotank => 99,
+            Self::MegaTank => 50,
+            Self::BCopter => 99,
+            Self::TCopter => 99,
+            Self::Fighter => 99,
+            Self::Bomber => 99,
+            Self::Stealth => 60,
+            Self::Battleship => 99,
+            Self::Cruiser => 99,
+            Self::Submarine => 60,
+            Self::Lander => 99,
+            Self::Carrier => 99,
+            Self::BlackBoat => 60,
+            Self::BlackBomb => 45,
+            Self::Oozium => 99,
+        }
+    }
+
+    /// Fuel which is used up at the start of every turn.
+    pub fn daily_fuel(&self) -> u32 {
+        match self {
+            Self::BCopter |
+            Self::TCopter => 2,
+
+            Self::Fighter |
+            Self::Bomber |
+            Self::Stealth |
+            Self::BlackBomb => 5,
+
+            Self::Battleship |
+            Self::Cruiser |
+            Self::Submarine |
+            Self::Lander |
+            Self::Carrier |
+            Self::BlackBoat => 1,
+
+            _ => 0,
+        }
+    }
+
+    /// Stealths can hide and Submarines can dive.
+    pub fn can_hide(&self) -> bool {
+        matches!(self, Self::Stealth | Self::Submarine)
+    }
+
+    /// Fuel which is used up at the start of every turn while a Stealth is hidden or a Submarine is dived.
+    pub fn hidden_daily_fuel(&self) -> u32 {
+        match self {
+            Self::Stealth => 8,
+            Self::Submarine => 5,
+            _ => self.daily_fuel(),
+        }
+    }
+
+    /// Whether the unit moves on land.
+    pub fn is_ground(&self) -> bool {
+        matches!(self.movement_type(), MovementType::Foot | MovementType::Boots | MovementType::Treads | MovementType::Tires | MovementType::Pipe)
+    }
+
+    /// Whether the unit is destroyed when it runs out of fuel.
+    pub fn crashes(&self) -> bool {
+        matches!(self.movement_type(), MovementType::Air | MovementType::Sea | MovementType::Lander)
+    }
+
+    /// Ammo for the primary weapon, the secondary weapon has unlimited ammo.
+    pub fn max_ammo(&self) -> u32 {
+        match self {
+            Self::Mech => 3,
+            Self::Artillery => 9,
+            Self::Tank => 9,
+            Self::AntiAir => 9,
+            Self::Missile => 6,
+            Self::Rocket => 6,
+            Self::MediumTank => 8,
+            Self::Piperunner => 9,
+            Self::Neotank => 9,
+            Self::MegaTank => 3,
+            Self::BCopter => 6,
+            Self::Fighter => 9,
+            Self::Bomber => 9,
+            Self::Stealth => 6,
+            Self::Battleship => 9,
+            Self::Cruiser => 9,
+            Self::Submarine => 6,
+            Self::Carrier => 9,
+
+            Self::Infantry |
+            Self::Recon |
+            Self::APC |
+            Self::TCopter |
+            Self::Lander |
+            Self::BlackBoat |
+            Self::BlackBomb |
+            Self::Oozium => 0,
+        }
+    }
+
+    /// Number of units that the transport can hold.
+    pub fn cargo_capacity(&self) -> u32 {
+        match self {
+            Self::APC |
+            Self::TCopter => 1,
+
+            Self::Lander |
+            Self::BlackBoat |
+            Self::Cruiser |
+            Self::Carrier => 2,
+
+            _ => 0,
+        }
+    }
+
+    /// Whether the transport can hold the unit.
+    pub fn can_carry(&self, cargo: UnitClass) -> bool {
+        use MovementType::*;
+
+        match self {
+            Self::APC |
+            Self::TCopter |
+            Self::BlackBoat => matches!(cargo, Self::Infantry | Self::Mech),
+
+            Self::Lander => cargo != Self::Oozium && matches!(cargo.movement_type(), Foot | Boots | Treads | Tires),
+
+            Self::Cruiser => matches!(cargo, Self::BCopter | Self::TCopter),
+
+            Self::Carrier => cargo.movement_type() == Air,
+
+            _ => false,
+        }
+    }
+
+    /// Number of tiles the unit can see in Fog of War.
+    pub fn vision(&self) -> u32 {
+        match self {
+            Self::Infantry => 2,
+            Self::Mech => 2,
+            Self::Recon => 5,
+            Self::APC => 1,
+            Self::Artillery => 1,
+            Self::Tank => 3,
+            Self::AntiAir => 2,
+            Self::Missile => 5,
+            Self::Rocket => 1,
+            Self::MediumTank => 1,
+            Self::Piperunner => 4,
+            Self::Neotank => 1,
+            Self::MegaTank => 1,
+            Self::BCopter => 3,
+            Self::TCopter => 2,
+            Self::Fighter => 2,
+            Self::Bomber => 2,
+            Self::Stealth => 4,
+            Self::Battleship => 2,
+            Self::Cruiser => 3,
+            Self::Submarine => 5,
+            Self::Lander => 1,
+            Self::Carrier => 4,
+            Self::BlackBoat => 1,
+            Self::BlackBomb => 1,
+            Self::Oozium => 1,
+        }
+    }
+}
+
+
+/// The rules state of a single unit.
+#[derive(Debug, Clone)]
+pub struct Unit {
+    pub coord: Coord,
+    pub class: UnitClass,
+    pub nation: Nation,
+
+    /// Non-rounded HP, from 1 to 100.
+    pub hp: u32,
+
+    pub fuel: u32,
+
+    /// Ammo for the primary weapon.
+    pub ammo: u32,
+
+    /// Whether the unit has already acted this turn.
+    pub waited: bool,
+
+    /// Whether the unit has moved this turn, indirect units cannot attack after moving.
+    pub moved: bool,
+
+    /// Whether a Stealth is hidden or a Submarine is dived.
+    pub hidden: bool,
+
+    /// Funds that were paid when the unit was built.
+    ///
+    /// This is used for repairs, joining, and the power meter, so CO powers
+    /// which change unit costs do not affect units which were already built.
+    pub build_cost: u32,
+
+    /// Units held by this transport.
+    pub cargo: Vec<UnitId>,
+
+    /// The transport which is holding this unit.
+    pub transport: Option<UnitId>,
+}
+
+impl Unit {
+    pub fn new(coord: Coord, class: UnitClass, nation: Nation) -> Self {
+        Self {
+            coord,
+            class,
+            nation,
+            hp: 100,
+            fuel: class.max_fuel(),
+            ammo: class.max_ammo(),
+            waited: false,
+            moved: false,
+            hidden: false,
+            build_cost: class.cost(),
+            cargo: vec![],
+            transport: None,
+        }
+    }
+
+    /// Whether the unit is inside of a transport.
+    #[inline]
+    pub fn is_loaded(&self) -> bool {
+        self.transport.is_some()
+    }
+
+    /// Funds value of the unit, based on its non-rounded HP and build cost.
+    pub fn value(&self) -> u32 {
+        self.build_cost * self.hp / 100
+    }
+
+    /// Hidden Stealths can only be attacked by Fighters and Stealths,
+    /// and dived Submarines can only be attacked by Cruisers and Submarines.
+    pub fn can_be_attacked_by(&self, attacker: UnitClass) -> bool {
+        if self.hidden {
+            match self.class {
+                UnitClass::Stealth => matches!(attacker, UnitClass::Fighter | UnitClass::Stealth),
+                UnitClass::Submarine => matches!(attacker, UnitClass::Cruiser | UnitClass::Submarine),
+                _ => true,
+            }
+
+        } else {
+            true
+        }
+    }
+
+    /// Fuel which is used up at the start of every turn.
+    pub fn daily_fuel(&self) -> u32 {
+        if self.hidden {
+            self.class.hidden_daily_fuel()
+
+        } else {
+            self.class.daily_fuel()
+        }
+    }
+
+    /// Chooses the weapon against the target based on the remaining ammo.
+    pub fn weapon(&self, target: Target) -> Option<(Weapon, u32)> {
+        select_weapon(self.class, self.ammo, target)
+    }
+
+    /// Uses up 1 ammo if the primary weapon was fired.
+    ///
+    /// When the ammo reaches 0 the unit falls back to its secondary weapon.
+    pub fn use_ammo(&mut self, weapon: Weapon) {
+        if let Weapon::Primary = weapon {
+            self.ammo = self.ammo.saturating_sub(1);
+        }
+    }
+
+    /// Refills the fuel and ammo.
+    ///
+    /// Returns `true` if anything changed.
+    pub fn resupply(&mut self) -> bool {
+        let fuel = self.class.max_fuel();
+        let ammo = self.class.max_ammo();
+
+        if self.fuel != fuel || self.ammo != ammo {
+            self.fuel = fuel;
+            self.ammo = ammo;
+            true
+
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::nation::{Nation};
+use crate::state::{GameState, UnitId};
+use crate::event::{Event};
+
+
+/// A way to win or lose the match, these are chosen in [`Rules::victory`](crate::Rules::victory).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Victory {
+    /// Capturing an HQ defeats its owner.
+    Hq,
+
+    /// A player who has no units after day 1 is defeated.
+    Rout,
+
+    /// The first team to own this many properties wins.
+    Properties(u32),
+
+    /// Capturing a Lab defeats its owner.
+    Lab,
+
+    /// The nation's team wins if the nation is still playing at the end of the day.
+    Survive {
+        nation: Nation,
+        day: u32,
+    },
+
+    /// The nation's team wins if the unit is destroyed by the end of the day, otherwise the team is defeated.
+    Destroy {
+        nation: Nation,
+        unit: UnitId,
+        day: u32,
+    },
+}
+
+
+impl GameState {
+    /// Whether the match has ended, commands are no longer allowed.
+    #[inline]
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    /// Whether the two nations are in the same team.
+    pub fn same_team(&self, a: Nation, b: Nation) -> bool {
+        if a == b {
+            return true;
+        }
+
+        let team = |nation| self.player(nation).and_then(|player| player.team);
+
+        match (team(a), team(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Every nation in the nation's team, including the nation, in turn order.
+    pub fn team(&self, nation: Nation) -> Vec<Nation> {
+        self.players.iter()
+            .map(|player| player.nation)
+            .filter(|other| self.same_team(*other, nation))
+            .collect()
+    }
+
+    /// The player loses, and all of their units are removed.
+    pub(crate) fn defeat_player(&mut self, nation: Nation, events: &mut Vec<Event>) {
+        let Some(player) = self.player_mut(nation) else {
+            return;
+        };
+
+        if player.defeated {
+            return;
+        }
+
+        player.defeated = true;
+
+        let units: Vec<UnitId> = self.units()
+            .filter(|(_, unit)| unit.nation == nation)
+            .map(|(id, _)| id)
+            .collect();
+
+        for id in units {
+            self.remove_unit(id);
+        }
+
+        self.defeat_order.push(nation);
+
+        events.push(Event::Defeated { nation });
+    }
+
+    /// Every player who is not in the nation's team is defeated.
+    fn win(&mut self, nation: Nation, events: &mut Vec<Event>) {
+        for loser in self.playing() {
+            if !self.same_team(loser, nation) {
+                self.defeat_player(loser, events);
+            }
+        }
+    }
+
+    /// Players who have not been defeated, in turn order.
+    fn playing(&self) -> Vec<Nation> {
+        self.players.iter()
+            .filter(|player| !player.defeated)
+            .map(|player| player.nation)
+            .collect()
+    }
+
+    fn properties(&self, nation: Nation) -> u32 {
+        self.map.tiles()
+            .filter(|tile| tile.building.map(|building| building.can_capture()).unwrap_or(false))
+            .filter(|tile| tile.owner.map(|owner| self.same_team(owner, nation)).unwrap_or(false))
+            .count() as u32
+    }
+
+    /// The current player gives up and is defeated, then the next player's turn starts.
+    pub fn resign(&mut self) -> Vec<Event> {
+        let mut events = vec![];
+
+        self.defeat_player(self.current_player().nation, &mut events);
+        self.check_victory(&mut events);
+
+        if !self.game_over {
+            events.extend(self.end_turn());
+        }
+
+        events
+    }
+
+    /// Checks the [`Victory`] conditions, this is done after every command and at the end of every day.
+    ///
+    /// When only 1 team is left the match ends. The ranking has the winners first,
+    /// followed by the defeated players, starting with the most recently defeated.
+    pub(crate) fn check_victory(&mut self, events: &mut Vec<Event>) {
+        if self.game_over {
+            return;
+        }
+
+        for victory in self.rules.victory.clone() {
+            match victory {
+                // These are checked when the property is captured
+                Victory::Hq | Victory::Lab => {},
+
+                Victory::Rout => {
+                    if self.day > 1 {
+                        for nation in self.playing() {
+                            if !self.units().any(|(_, unit)| unit.nation == nation) {
+                                self.defeat_player(nation, events);
+                            }
+                        }
+                    }
+                },
+
+                Victory::Properties(count) => {
+                    let winner = self.playing().into_iter().find(|nation| self.properties(*nation) >= count);
+
+                    if let Some(winner) = winner {
+                        self.win(winner, events);
+                    }
+                },
+
+                Victory::Survive { nation, day } => {
+                    if self.day > day && self.playing().contains(&nation) {
+                        self.win(nation, events);
+                    }
+                },
+
+                Victory::Destroy { nation, unit, day } => {
+                    if self.playing().contains(&nation) {
+                        if self.unit(unit).is_none() {
+                            self.win(nation, events);
+
+                        } else if self.day > day {
+                            for loser in self.playing() {
+                                if self.same_team(loser, nation) {
+                                    self.defeat_player(loser, events);
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        // A match without any defeats never ends, so that a single player can practice
+        if self.defeat_order.is_empty() {
+            return;
+        }
+
+        let mut ranking = self.playing();
+
+        let teams_left = ranking.iter().filter(|nation| !self.same_team(**nation, ranking[0])).count();
+
+        if teams_left == 0 {
+            ranking.extend(self.defeat_order.iter().rev());
+
+            self.game_over = true;
+
+            events.push(Event::GameOver { ranking });
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::nation::{Nation};
+use crate::map::{Coord, Map, Tile};
+use crate::terrain::{TerrainClass};
+use crate::unit::{Unit, UnitClass};
+use crate::weather::{Weather};
+
+
+/// Returns the number of tiles the unit can see while standing on the tile.
+///
+/// Infantry and Mechs get +3 vision while standing on a mountain.
+pub fn vision_range(class: UnitClass, tile: &Tile) -> u32 {
+    terrain_vision(class, class.vision(), tile)
+}
+
+/// Same as [`vision_range`], except the vision already includes the CO modifiers.
+pub fn terrain_vision(class: UnitClass, vision: u32, tile: &Tile) -> u32 {
+    match (class, tile.class) {
+        (UnitClass::Infantry | UnitClass::Mech, TerrainClass::Mountain { .. }) => vision + 3,
+        _ => vision,
+    }
+}
+
+
+/// Which tiles a player can see in Fog of War.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vision {
+    pub width: u32,
+    pub height: u32,
+
+    /// The nation which owns the vision, it can always see its own units.
+    nation: Option<Nation>,
+
+    /// Teammates who share their vision, their hidden units can always be seen.
+    allies: Vec<Nation>,
+
+    visible: Vec<bool>,
+
+    /// Tiles next to the nation's units, hidden enemy units can be seen on these tiles.
+    detected: Vec<bool>,
+}
+
+impl Vision {
+    /// Calculates the vision for the nation.
+    ///
+    /// Every unit and building owned by the nation can see its own tile,
+    /// units inside of transports cannot see anything.
+    ///
+    /// Units can see every tile within their vision range, except for forests and
+    /// reefs, which can only be seen by adjacent units.
+    ///
+    /// Hidden Stealths and dived Submarines can only be seen by adjacent units.
+    #[inline]
+    pub fn new<'a, I>(map: &Map, units: I, nation: Nation) -> Self where I: IntoIterator<Item = &'a Unit> {
+        Self::with_weather(map, units, nation, Weather::Clear)
+    }
+
+    /// Calculates the vision for the nation, the weather changes the vision range of units.
+    #[inline]
+    pub fn with_weather<'a, I>(map: &Map, units: I, nation: Nation, weather: Weather) -> Self where I: IntoIterator<Item = &'a Unit> {
+        Self::with_range(map, units, nation, |unit, tile| weather.vision(vision_range(unit.class, tile)))
+    }
+
+    /// Calculates the vision for the nation, the `range` function returns the vision range
+    /// of the unit while it is standing on the tile.
+    pub fn with_range<'a, I, F>(map: &Map, units: I, nation: Nation, mut range: F) -> Self
+        where I: IntoIterator<Item = &'a Unit>,
+              F: FnMut(&Unit, &Tile) -> u32 {
+
+        let size = map.width as usize * map.height as usize;
+
+        let mut vision = Self {
+            width: map.width,
+            height: map.height,
+            nation: Some(nation),
+            allies: vec![],
+            visible: vec![false; size],
+            detected: vec![false; size],
+        };
+
+        for tile in map.tiles() {
+            if tile.owner == Some(nation) {
+                vision.reveal(&tile.coord);
+            }
+        }
+
+        for unit in units {
+            if unit.nation != nation || unit.is_loaded() {
+                continue;
+            }
+
+            let range = match map.get(&unit.coord) {
+                Some(tile) => range(unit, tile),
+                None => continue,
+            };
+
+            vision.reveal(&unit.coord);
+
+            for coord in std::iter::once(unit.coord).chain(unit.coord.adjacent()) {
+                if let Some(index) = vision.get_index(&coord) {
+                    vision.detected[index] = true;
+                }
+            }
+
+            let min_x = unit.coord.x.saturating_sub(range);
+            let min_y = unit.coord.y.saturating_sub(range);
+            let max_x = unit.coord.x.saturating_add(range).min(map.width.saturating_sub(1));
+            let max_y = unit.coord.y.saturating_add(range).min(map.height.saturating_sub(1));
+
+            for y in min_y..=max_y {
+                for x in min_x..=max_x {
+                    let coord = Coord { x, y };
+
+                    let distance = coord.distance(&unit.coord);
+
+                    if distance <= range {
+                        let tile = map.get(&coord).unwrap();
+
+                        if distance <= 1 || !tile.class.conceals() {
+                            vision.reveal(&coord);
+                        }
+                    }
+                }
+            }
+        }
+
+        vision
+    }
+
+    /// Vision when Fog of War is disabled, every tile is visible.
+    ///
+    /// Hidden units are never visible, use [`Vision::without_fog`] to see them.
+    pub fn all(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            nation: None,
+            allies: vec![],
+            visible: vec![true; width as usize * height as usize],
+            detected: vec![false; width as usize * height as usize],
+        }
+    }
+
+    /// Vision for the nation when Fog of War is disabled.
+    ///
+    /// Every tile is visible, but hidden units can still only be seen by adjacent units.
+    pub fn without_fog<'a, I>(map: &Map, units: I, nation: Nation) -> Self where I: IntoIterator<Item = &'a Unit> {
+        let vision = Self::new(map, units, nation);
+
+        Self {
+            visible: vec![true; vision.visible.len()],
+            ..vision
+        }
+    }
+
+    /// Adds the vision of a teammate, so the team can see everything that either of them can see.
+    pub fn share(&mut self, other: &Vision) {
+        assert_eq!((self.width, self.height), (other.width, other.height), "Vision sizes do not match");
+
+        for (visible, other) in self.visible.iter_mut().zip(other.visible.iter()) {
+            *visible |= *other;
+        }
+
+        for (detected, other) in self.detected.iter_mut().zip(other.detected.iter()) {
+            *detected |= *other;
+        }
+
+        self.allies.extend(other.nation);
+        self.allies.extend(other.allies.iter().copied());
+    }
+
+    /// Makes every tile visible, hidden units can still only be seen by adjacent units.
+    pub(crate) fn reveal_all(&mut self) {
+        self.visible.fill(true);
+    }
+
+    fn get_index(&self, coord: &Coord) -> Option<usize> {
+        if coord.x < self.width && coord.y < self.height {
+            Some(((coord.y * self.width) + coord.x) as usize)
+
+        } else {
+            None
+        }
+    }
+
+    fn reveal(&mut self, coord: &Coord) {
+        if let Some(index) = self.get_index(coord) {
+            self.visible[index] = true;
+        }
+    }
+
+    /// Returns `false` if the tile is covered by fog, or if it is outside of the map.
+    pub fn is_visible(&self, coord: &Coord) -> bool {
+        self.get_index(coord).map(|index| self.visible[index]).unwrap_or(false)
+    }
+
+    /// Returns `true` if hidden enemy units on the tile can be seen.
+    pub fn is_detected(&self, coord: &Coord) -> bool {
+        self.get_index(coord).map(|index| self.detected[index]).unwrap_or(false)
+    }
+
+    /// Returns `true` if the unit can be seen by the player.
+    pub fn can_see(&self, unit: &Unit) -> bool {
+        if unit.hidden && self.nation != Some(unit.nation) && !self.allies.contains(&unit.nation) {
+            self.is_detected(&unit.coord)
+
+        } else {
+            self.is_visible(&unit.coord)
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::nation::{Nation};
+use crate::map::{Tile};
+use crate::terrain::{TerrainClass};
+use crate::movement::{MovementType};
+use crate::unit::{Unit};
+use crate::vision::{Vision, terrain_vision};
+use crate::random::{Rng, SeededRng};
+use crate::state::{GameState};
+use crate::event::{Event};
+
+
+/// The weather affects every unit on the map, using the Dual Strike rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Weather {
+    #[default]
+    Clear,
+
+    /// Vision is reduced, and vehicles move slower on plains and forests.
+    Rain,
+
+    /// Most units move slower, and air units use double movement everywhere.
+    Snow,
+
+    /// The maximum range of indirect units is reduced.
+    Sandstorm,
+}
+
+impl Weather {
+    pub const ALL: &[Self] = &[
+        Self::Clear,
+        Self::Rain,
+        Self::Snow,
+        Self::Sandstorm,
+    ];
+
+    /// Returns the number of movement points needed to enter the tile in this weather.
+    ///
+    /// Returns `None` if the tile cannot be entered.
+    pub fn movement_cost(&self, movement: MovementType, tile: &Tile) -> Option<u32> {
+        use MovementType::*;
+
+        let cost = movement.cost(tile)?;
+
+        // Buildings replace the terrain, so only air units are slowed down on them
+        let terrain = match tile.building {
+            Some(_) => None,
+            None => Some(tile.class),
+        };
+
+        let plains_or_forest = matches!(terrain, Some(TerrainClass::Grass | TerrainClass::Road { ruins: true } | TerrainClass::Pipeseam { destroyed: true } | TerrainClass::Forest));
+        let mountain = matches!(terrain, Some(TerrainClass::Mountain { .. }));
+        let sea = matches!(terrain, Some(TerrainClass::Ocean | TerrainClass::Reef));
+
+        let extra = match self {
+            Self::Clear | Self::Sandstorm => 0,
+
+            Self::Rain => match movement {
+                Treads | Tires if plains_or_forest => 1,
+                _ => 0,
+            },
+
+            Self::Snow => match movement {
+                Air => 1,
+                Foot if mountain => 2,
+                Foot | Treads | Tires if plains_or_forest => 1,
+                Boots if mountain => 1,
+                Sea | Lander if sea => 1,
+                _ => 0,
+            },
+        };
+
+        Some(cost + extra)
+    }
+
+    /// Changes the vision range, units can always see at least 1 tile.
+    pub fn vision(&self, vision: u32) -> u32 {
+        match self {
+            Self::Rain => vision.saturating_sub(1).max(1),
+            _ => vision,
+        }
+    }
+
+    /// Changes the attack range, direct units are not affected.
+    pub fn attack_range(&self, (min, max): (u32, u32)) -> (u32, u32) {
+        match self {
+            Self::Sandstorm if max > 1 => (min, (max - 1).max(min)),
+            _ => (min, max),
+        }
+    }
+
+    /// Picks the weather for a day, clear weather is the most likely.
+    pub fn random<R>(mut rng: R) -> Self where R: Rng {
+        match rng.range(0, 9) {
+            0 => Self::Rain,
+            1 => Self::Snow,
+            2 => Self::Sandstorm,
+            _ => Self::Clear,
+        }
+    }
+}
+
+
+impl GameState {
+    /// The weather for today, or the weather caused by a CO power.
+    pub fn current_weather(&self) -> Weather {
+        match self.power_weather {
+            Some((_, weather)) => weather,
+            None => self.weather,
+        }
+    }
+
+    /// The weather which changes the unit's movement costs, some COs are not affected by the weather.
+    pub(crate) fn unit_weather(&self, unit: &Unit) -> Weather {
+        let weather = self.current_weather();
+
+        let ignores = self.player(unit.nation)
+            .and_then(|player| player.co.as_ref())
+            .map(|co| co.ignores_weather(unit.class, weather))
+            .unwrap_or(false);
+
+        if ignores {
+            Weather::Clear
+
+        } else {
+            weather
+        }
+    }
+
+    /// Calculates the vision for the nation in the current weather, teammates share their vision.
+    ///
+    /// If [`Rules::fog`](crate::Rules::fog) is disabled then every tile is visible, but hidden units can still only be seen by adjacent units.
+    pub fn vision(&self, nation: Nation) -> Vision {
+        let mut vision = self.nation_vision(nation);
+
+        for ally in self.team(nation) {
+            if ally != nation {
+                vision.share(&self.nation_vision(ally));
+            }
+        }
+
+        if !self.rules.fog {
+            vision.reveal_all();
+        }
+
+        vision
+    }
+
+    /// The vision of the nation's own units and buildings, including its CO's vision modifiers.
+    fn nation_vision(&self, nation: Nation) -> Vision {
+        let weather = self.current_weather();
+        let co = self.player(nation).and_then(|player| player.co.as_ref());
+
+        Vision::with_range(&self.map, self.units.values(), nation, |unit, tile| {
+            let vision = co.map(|co| co.vision(unit.class)).unwrap_or(unit.class.vision());
+
+            weather.vision(terrain_vision(unit.class, vision, tile))
+        })
+    }
+
+    /// A CO power changes the weather until the player's next turn.
+    pub(crate) fn set_power_weather(&mut self, nation: Nation, weather: Weather, events: &mut Vec<Event>) {
+        let old = self.current_weather();
+
+        self.power_weather = Some((nation, weather));
+
+        if weather != old {
+            events.push(Event::WeatherChanged { weather });
+        }
+    }
+
+    /// The weather from the current player's CO power ends when their turn starts.
+    pub(crate) fn end_power_weather(&mut self, events: &mut Vec<Event>) {
+        let nation = self.current_player().nation;
+
+        if let Some((owner, _)) = self.power_weather {
+            if owner == nation {
+                let old = self.current_weather();
+
+                self.power_weather = None;
+
+                if self.weather != old {
+                    events.push(Event::WeatherChanged { weather: self.weather });
+                }
+            }
+        }
+    }
+
+    /// Picks the weather for the new day, if random weather is enabled.
+    pub(crate) fn daily_weather(&mut self, events: &mut Vec<Event>) {
+        if !self.rules.random_weather {
+            return;
+        }
+
+        let old = self.current_weather();
+
+        self.weather = Weather::random(SeededRng::with_key(self.rules.seed, self.day as u64));
+
+        let weather = self.current_weather();
+
+        if weather != old {
+            events.push(Event::WeatherChanged { weather });
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, Event, RuleError,
+};
+
+mod common;
+
+use common::{grass_map};
+
+
+#[test]
+fn test_explode() {
+    let mut state = GameState::new(grass_map(9, 9), &[Nation::OrangeStar, Nation::BlackHole]);
+
+    let bomb = state.add_unit(Unit::new(Coord::new(4, 4), UnitClass::BlackBomb, Nation::OrangeStar));
+
+    let edge = state.add_unit(Unit::new(Coord::new(4, 7), UnitClass::Tank, Nation::BlackHole));
+    let weak = state.add_unit(Unit { hp: 30, ..Unit::new(Coord::new(5, 5), UnitClass::Tank, Nation::BlackHole) });
+    let outside = state.add_unit(Unit::new(Coord::new(6, 6), UnitClass::Tank, Nation::BlackHole));
+    let own = state.add_unit(Unit::new(Coord::new(3, 4), UnitClass::Fighter, Nation::OrangeStar));
+
+    state.start_turn();
+
+    let events = state.explode_unit(bomb).unwrap();
+
+    assert_eq!(events, vec![
+        Event::Exploded { unit: bomb, coord: Coord::new(4, 4) },
+        Event::BombHit { unit: edge, damage: 50 },
+        Event::BombHit { unit: weak, damage: 29 },
+        Event::BombHit { unit: own, damage: 50 },
+    ]);
+
+    assert!(state.unit(bomb).is_none());
+    assert_eq!(state.unit(weak).unwrap().hp, 1);
+    assert_eq!(state.unit(outside).unwrap().hp, 100);
+}
+
+#[test]
+fn test_cannot_explode() {
+    let mut state = GameState::new(grass_map(2, 1), &[Nation::OrangeStar, Nation::BlackHole]);
+
+    let bomber = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Bomber, Nation::OrangeStar));
+    let bomb = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::BlackBomb, Nation::BlackHole));
+
+    state.start_turn();
+
+    assert_eq!(state.explode_unit(bomber), Err(RuleError::CannotExplode(bomber)));
+    assert_eq!(state.explode_unit(bomb), Err(RuleError::NotYourUnit(bomb)));
+}
@@ -0,0 +1,200 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, BuildingClass, Event, RuleError,
+    CAPTURE_POINTS,
+};
+
+mod common;
+
+use common::{grass_map, set_building};
+
+
+fn owner(state: &GameState, x: u32, y: u32) -> Option<Nation> {
+    state.map.get(&Coord::new(x, y)).unwrap().owner
+}
+
+/// Ends the turn for every player, so that it is the same player's turn again.
+fn next_day(state: &mut GameState) {
+    for _ in 0..state.players().iter().filter(|player| !player.defeated).count() {
+        state.end_turn();
+    }
+}
+
+
+#[test]
+fn test_capture() {
+    let mut map = grass_map(3, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::City, None);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let unit = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.start_turn();
+
+    let events = state.capture_property(unit).unwrap();
+
+    assert_eq!(events, vec![Event::CaptureProgress { unit, coord: Coord::new(0, 0), points: 100 }]);
+    assert_eq!(state.capture_at(&Coord::new(0, 0)).unwrap().points, 100);
+    assert_eq!(owner(&state, 0, 0), None);
+
+    // The unit can only capture once per turn
+    assert_eq!(state.capture_property(unit), Err(RuleError::UnitWaited(unit)));
+
+    next_day(&mut state);
+
+    let events = state.capture_property(unit).unwrap();
+
+    assert_eq!(events, vec![
+        Event::CaptureProgress { unit, coord: Coord::new(0, 0), points: 0 },
+        Event::Captured { unit, coord: Coord::new(0, 0), nation: Nation::OrangeStar },
+    ]);
+
+    assert_eq!(owner(&state, 0, 0), Some(Nation::OrangeStar));
+    assert!(state.capture_at(&Coord::new(0, 0)).is_none());
+
+    next_day(&mut state);
+
+    // It cannot capture its own property
+    assert_eq!(state.capture_property(unit), Err(RuleError::CannotCapture(unit)));
+}
+
+#[test]
+fn test_non_rounded_hp() {
+    let mut map = grass_map(1, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::City, Some(Nation::BlueMoon));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let unit = state.add_unit(Unit { hp: 55, ..Unit::new(Coord::new(0, 0), UnitClass::Mech, Nation::OrangeStar) });
+
+    state.start_turn();
+
+    // Advance Wars would round 55 HP up to 6
+    state.capture_property(unit).unwrap();
+    assert_eq!(state.capture_at(&Coord::new(0, 0)).unwrap().points, CAPTURE_POINTS - 55);
+
+    next_day(&mut state);
+    state.capture_property(unit).unwrap();
+    assert_eq!(state.capture_at(&Coord::new(0, 0)).unwrap().points, 90);
+
+    next_day(&mut state);
+    state.capture_property(unit).unwrap();
+    assert_eq!(state.capture_at(&Coord::new(0, 0)).unwrap().points, 35);
+
+    next_day(&mut state);
+    state.capture_property(unit).unwrap();
+    assert_eq!(owner(&state, 0, 0), Some(Nation::OrangeStar));
+}
+
+#[test]
+fn test_capture_reset() {
+    let mut map = grass_map(3, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::Base, None);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    let unit = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.start_turn();
+    state.capture_property(unit).unwrap();
+
+    // Moving to the same tile does not reset it
+    state.move_unit(unit, Coord::new(0, 0)).unwrap();
+    assert!(state.capture_at(&Coord::new(0, 0)).is_some());
+
+    // Leaving the tile resets it
+    state.move_unit(unit, Coord::new(1, 0)).unwrap();
+    assert!(state.capture_at(&Coord::new(0, 0)).is_none());
+
+    state.move_unit(unit, Coord::new(0, 0)).unwrap();
+    state.end_turn();
+
+    state.capture_property(unit).unwrap();
+    assert_eq!(state.capture_at(&Coord::new(0, 0)).unwrap().points, 100);
+
+    // Dying resets it
+    state.remove_unit(unit);
+    assert!(state.capture_at(&Coord::new(0, 0)).is_none());
+
+    let unit = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.capture_property(unit).unwrap();
+    assert_eq!(state.capture_at(&Coord::new(0, 0)).unwrap().points, 100);
+}
+
+#[test]
+fn test_cannot_capture() {
+    let mut map = grass_map(5, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::MissileSilo, None);
+    set_building(&mut map, 1, 0, BuildingClass::City, None);
+    set_building(&mut map, 4, 0, BuildingClass::City, None);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let silo = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::OrangeStar));
+    let grass = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(3, 0), UnitClass::Infantry, Nation::BlueMoon));
+    let apc = state.add_unit(Unit::new(Coord::new(4, 0), UnitClass::APC, Nation::OrangeStar));
+    let loaded = state.add_unit(Unit::new(Coord::new(4, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.start_turn();
+    state.load_unit(loaded, apc).unwrap();
+
+    next_day(&mut state);
+
+    // Units inside of transports cannot capture
+    assert_eq!(state.capture_property(loaded), Err(RuleError::CannotCapture(loaded)));
+
+    assert_eq!(state.capture_property(silo), Err(RuleError::CannotCapture(silo)));
+    assert_eq!(state.capture_property(tank), Err(RuleError::CannotCapture(tank)));
+    assert_eq!(state.capture_property(grass), Err(RuleError::CannotCapture(grass)));
+    assert_eq!(state.capture_property(enemy), Err(RuleError::NotYourUnit(enemy)));
+
+    state.remove_unit(enemy);
+    assert_eq!(state.capture_property(enemy), Err(RuleError::UnitNotFound(enemy)));
+}
+
+#[test]
+fn test_hq_capture() {
+    let mut map = grass_map(4, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::HQ2, Some(Nation::BlueMoon));
+    set_building(&mut map, 1, 0, BuildingClass::City, Some(Nation::BlueMoon));
+    set_building(&mut map, 2, 0, BuildingClass::Port, Some(Nation::BlueMoon));
+    set_building(&mut map, 3, 0, BuildingClass::City, Some(Nation::GreenEarth));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon, Nation::GreenEarth]);
+
+    let unit = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+    state.capture_property(unit).unwrap();
+    next_day(&mut state);
+
+    let events = state.capture_property(unit).unwrap();
+
+    assert_eq!(events, vec![
+        Event::CaptureProgress { unit, coord: Coord::new(0, 0), points: 0 },
+        Event::Captured { unit, coord: Coord::new(0, 0), nation: Nation::OrangeStar },
+        Event::TurnedOver { coord: Coord::new(1, 0), nation: Nation::OrangeStar },
+        Event::TurnedOver { coord: Coord::new(2, 0), nation: Nation::OrangeStar },
+        Event::Defeated { nation: Nation::BlueMoon },
+    ]);
+
+    assert_eq!(owner(&state, 0, 0), Some(Nation::OrangeStar));
+    assert_eq!(owner(&state, 1, 0), Some(Nation::OrangeStar));
+    assert_eq!(owner(&state, 2, 0), Some(Nation::OrangeStar));
+    assert_eq!(owner(&state, 3, 0), Some(Nation::GreenEarth));
+
+    assert!(state.unit(enemy).is_none());
+    assert!(state.player(Nation::BlueMoon).unwrap().defeated);
+
+    state.end_turn();
+    assert_eq!(state.current_player().nation, Nation::GreenEarth);
+}
@@ -0,0 +1,327 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, TerrainClass, BuildingClass, Orientation, Event, RuleError,
+    Co, PlayerCo, PowerKind, Rules, STAR_VALUE, calculate_damage,
+};
+
+mod common;
+
+use common::{NoLuck, grass_map, set, set_building};
+
+
+fn set_co(state: &mut GameState, nation: Nation, co: Co) {
+    state.player_mut(nation).unwrap().co = Some(PlayerCo::new(co));
+}
+
+/// Returns the damage without luck of the attacker against the defender.
+fn damage(state: &GameState, attacker: Coord, defender: Coord) -> u32 {
+    let (attacker, attacker_unit) = state.unit_at(&attacker).unwrap();
+    let (defender, _) = state.unit_at(&defender).unwrap();
+
+    let attacker = state.attacker(attacker).unwrap();
+    let defender = state.defender(defender, attacker_unit.class).unwrap();
+
+    calculate_damage(&attacker, &defender, NoLuck).unwrap().damage
+}
+
+
+/// The "Tank" rows of "Dual Strike CO Bonus.md", with the defender on 0 to 3 stars.
+#[test]
+fn test_co_bonus() {
+    let terrain = [
+        TerrainClass::Road { ruins: false },
+        TerrainClass::Grass,
+        TerrainClass::Forest,
+    ];
+
+    let check = |co: Co, power: Option<PowerKind>, comtowers: u32, expected: [u32; 4]| {
+        for (stars, expected) in expected.into_iter().enumerate() {
+            let mut map = grass_map(5, 2);
+
+            set(&mut map, 0, 0, TerrainClass::Road { ruins: false });
+
+            match terrain.get(stars) {
+                Some(class) => set(&mut map, 1, 0, *class),
+                None => set_building(&mut map, 1, 0, BuildingClass::City, None),
+            }
+
+            for x in 0..comtowers {
+                set_building(&mut map, x, 1, BuildingClass::ComTower, Some(Nation::OrangeStar));
+            }
+
+            let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+            set_co(&mut state, Nation::OrangeStar, co);
+            set_co(&mut state, Nation::BlueMoon, Co::Kanbei);
+
+            state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap().power = power;
+
+            state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+            state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+            assert_eq!(damage(&state, Coord::new(0, 0), Coord::new(1, 0)), expected, "{:?} {:?} {} stars", co, power, stars);
+        }
+    };
+
+    check(Co::Andy, None, 0, [44, 38, 33, 27]);
+    check(Co::Max, None, 0, [52, 46, 39, 33]);
+
+    // The docs only show the generic +10% for Andy, so it uses Hyper Repair
+    check(Co::Andy, Some(PowerKind::Power), 0, [48, 42, 36, 30]);
+
+    // Max's documented +30% is Max Blast
+    check(Co::Max, Some(PowerKind::SuperPower), 0, [70, 61, 52, 44]);
+    check(Co::Max, Some(PowerKind::SuperPower), 3, [83, 72, 62, 52]);
+}
+
+#[test]
+fn test_koal() {
+    let mut map = grass_map(4, 1);
+
+    set(&mut map, 0, 0, TerrainClass::Road { ruins: false });
+    set(&mut map, 1, 0, TerrainClass::Bridge { orientation: Orientation::Vertical });
+    set(&mut map, 2, 0, TerrainClass::Road { ruins: true });
+
+    let koal = PlayerCo::new(Co::Koal);
+
+    let attack = |x: u32| koal.attack(UnitClass::Tank, map.get(&Coord::new(x, 0)), 0);
+
+    // Bridges count as roads, but ruined roads do not
+    assert_eq!(attack(0), 10);
+    assert_eq!(attack(1), 10);
+    assert_eq!(attack(2), 0);
+    assert_eq!(attack(3), 0);
+
+    let koal = PlayerCo { power: Some(PowerKind::SuperPower), ..koal };
+
+    assert_eq!(koal.attack(UnitClass::Tank, map.get(&Coord::new(1, 0)), 0), 30);
+    assert_eq!(koal.movement(UnitClass::Infantry), 5);
+}
+
+#[test]
+fn test_modifiers() {
+    let max = PlayerCo::new(Co::Max);
+
+    assert_eq!(max.attack(UnitClass::Tank, None, 0), 20);
+    assert_eq!(max.attack(UnitClass::Infantry, None, 0), 0);
+    assert_eq!(max.attack(UnitClass::Artillery, None, 0), -10);
+    assert_eq!(max.attack_range(UnitClass::Artillery), (2, 2));
+    assert_eq!(max.attack_range(UnitClass::Tank), (1, 1));
+    assert_eq!(max.movement(UnitClass::Tank), 6);
+
+    let max = PlayerCo { power: Some(PowerKind::Power), ..max };
+
+    assert_eq!(max.movement(UnitClass::Tank), 7);
+    assert_eq!(max.movement(UnitClass::Infantry), 3);
+
+    assert_eq!(PlayerCo::new(Co::Andy).cost(UnitClass::Tank), 7000);
+    assert_eq!(PlayerCo::new(Co::Kanbei).cost(UnitClass::Tank), 8400);
+    assert_eq!(PlayerCo::new(Co::Colin).cost(UnitClass::Tank), 5600);
+    assert_eq!(PlayerCo::new(Co::Hachi).cost(UnitClass::Tank), 6300);
+    assert_eq!(PlayerCo { power: Some(PowerKind::Power), ..PlayerCo::new(Co::Hachi) }.cost(UnitClass::Tank), 3500);
+
+    assert_eq!(PlayerCo::new(Co::Sonja).vision(UnitClass::Tank), 4);
+    assert_eq!(PlayerCo::new(Co::Andy).luck(UnitClass::Tank), (9, 0));
+    assert_eq!(PlayerCo::new(Co::Sonja).luck(UnitClass::Tank), (9, 9));
+    assert_eq!(PlayerCo::new(Co::Flak).luck(UnitClass::Tank), (15, 10));
+    assert_eq!(PlayerCo { power: Some(PowerKind::SuperPower), ..PlayerCo::new(Co::Jugger) }.luck(UnitClass::Tank), (95, 45));
+
+    let javier = PlayerCo::new(Co::Javier);
+
+    assert_eq!(javier.defense(UnitClass::Tank, None, UnitClass::Artillery), 20);
+    assert_eq!(javier.defense(UnitClass::Tank, None, UnitClass::Tank), 0);
+    assert!(javier.comtower_defense());
+    assert!(!max.comtower_defense());
+
+    let mut map = grass_map(2, 1);
+
+    set(&mut map, 1, 0, TerrainClass::Mountain { variant: 0 });
+
+    let lash = PlayerCo::new(Co::Lash);
+
+    assert_eq!(lash.attack(UnitClass::Infantry, map.get(&Coord::new(0, 0)), 0), 10);
+    assert_eq!(lash.attack(UnitClass::Infantry, map.get(&Coord::new(1, 0)), 0), 40);
+
+    let colin = PlayerCo { power: Some(PowerKind::SuperPower), ..PlayerCo::new(Co::Colin) };
+
+    assert_eq!(colin.attack(UnitClass::Tank, None, 50000), 140);
+}
+
+#[test]
+fn test_defender() {
+    let mut map = grass_map(4, 1);
+
+    set_building(&mut map, 3, 0, BuildingClass::ComTower, Some(Nation::BlueMoon));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    set_co(&mut state, Nation::BlueMoon, Co::Javier);
+
+    state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let defender = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    let direct = state.defender(defender, UnitClass::Tank).unwrap();
+
+    assert_eq!(direct.co_bonus, 0);
+    assert_eq!(direct.comtowers, 1);
+    assert_eq!(direct.terrain_stars, 1);
+
+    assert_eq!(state.defender(defender, UnitClass::Rocket).unwrap().co_bonus, 20);
+
+    set_co(&mut state, Nation::BlueMoon, Co::Grimm);
+
+    let grimm = state.defender(defender, UnitClass::Tank).unwrap();
+
+    assert_eq!(grimm.co_bonus, 0);
+    assert_eq!(grimm.co_penalty, 20);
+    assert_eq!(grimm.comtowers, 0);
+}
+
+#[test]
+fn test_lash() {
+    let mut map = grass_map(2, 1);
+
+    set(&mut map, 1, 0, TerrainClass::Forest);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    set_co(&mut state, Nation::BlueMoon, Co::Lash);
+
+    state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let defender = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    assert_eq!(state.defender(defender, UnitClass::Tank).unwrap().terrain_stars, 2);
+
+    let normal = damage(&state, Coord::new(0, 0), Coord::new(1, 0));
+
+    // Terrain Tactics doubles the terrain stars
+    state.player_mut(Nation::BlueMoon).unwrap().co.as_mut().unwrap().power = Some(PowerKind::Power);
+
+    assert_eq!(state.defender(defender, UnitClass::Tank).unwrap().terrain_stars, 4);
+    assert!(damage(&state, Coord::new(0, 0), Coord::new(1, 0)) < normal);
+
+    state.player_mut(Nation::BlueMoon).unwrap().co.as_mut().unwrap().power = Some(PowerKind::SuperPower);
+
+    assert_eq!(state.defender(defender, UnitClass::Tank).unwrap().terrain_stars, 4);
+}
+
+#[test]
+fn test_sonja_vision() {
+    let mut state = GameState::new(grass_map(6, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.rules = Rules { fog: true, ..Rules::default() };
+
+    state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    assert!(state.vision(Nation::OrangeStar).is_visible(&Coord::new(2, 0)));
+    assert!(!state.vision(Nation::OrangeStar).is_visible(&Coord::new(3, 0)));
+
+    // Sonja's units see 1 tile further, and 1 more during her powers
+    set_co(&mut state, Nation::OrangeStar, Co::Sonja);
+
+    assert!(state.vision(Nation::OrangeStar).is_visible(&Coord::new(3, 0)));
+    assert!(!state.vision(Nation::OrangeStar).is_visible(&Coord::new(4, 0)));
+
+    state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap().power = Some(PowerKind::Power);
+
+    assert!(state.vision(Nation::OrangeStar).is_visible(&Coord::new(4, 0)));
+}
+
+#[test]
+fn test_power_meter() {
+    let mut state = GameState::new(grass_map(2, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    set_co(&mut state, Nation::OrangeStar, Co::Max);
+
+    state.start_turn();
+
+    assert_eq!(state.activate_power(PowerKind::Power), Err(RuleError::CannotActivatePower(Nation::OrangeStar)));
+
+    let co = state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap();
+
+    assert_eq!(co.power_cost(PowerKind::Power), 3 * STAR_VALUE);
+    assert_eq!(co.max_meter(), 6 * STAR_VALUE);
+
+    // Damage taken charges twice as much as damage dealt
+    co.charge(14000, 0);
+    assert_eq!(co.meter, 7000);
+
+    co.charge(0, 20000);
+    assert_eq!(co.meter, 27000);
+    assert!(co.can_activate(PowerKind::Power));
+    assert!(!co.can_activate(PowerKind::SuperPower));
+
+    co.charge(100000, 100000);
+    assert_eq!(co.meter, 54000);
+
+    let events = state.activate_power(PowerKind::Power).unwrap();
+
+    assert_eq!(events, vec![Event::PowerActivated { nation: Nation::OrangeStar, co: Co::Max, power: PowerKind::Power }]);
+
+    let co = state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap();
+
+    assert_eq!(co.meter, 27000);
+    assert_eq!(co.power, Some(PowerKind::Power));
+
+    // Stars are 20% more expensive after every use
+    assert_eq!(co.star_value(), 10800);
+
+    // The meter does not charge while a power is active
+    co.charge(0, 10000);
+    assert_eq!(co.meter, 27000);
+
+    assert_eq!(state.activate_power(PowerKind::Power), Err(RuleError::CannotActivatePower(Nation::OrangeStar)));
+
+    // The power lasts through the other player's turn
+    state.end_turn();
+    assert_eq!(state.player(Nation::OrangeStar).unwrap().co.as_ref().unwrap().power, Some(PowerKind::Power));
+
+    state.end_turn();
+    assert_eq!(state.player(Nation::OrangeStar).unwrap().co.as_ref().unwrap().power, None);
+
+    // Players without a CO cannot use powers
+    state.end_turn();
+    assert_eq!(state.activate_power(PowerKind::Power), Err(RuleError::CannotActivatePower(Nation::BlueMoon)));
+}
+
+#[test]
+fn test_instant_effects() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    set_co(&mut state, Nation::OrangeStar, Co::Andy);
+    set_co(&mut state, Nation::BlueMoon, Co::Colin);
+
+    let hurt = state.add_unit(Unit { hp: 45, ..Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar) });
+    let almost = state.add_unit(Unit { hp: 91, ..Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::OrangeStar) });
+    state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Tank, Nation::OrangeStar));
+
+    state.start_turn();
+
+    state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap().meter = 3 * STAR_VALUE;
+
+    let events = state.activate_power(PowerKind::Power).unwrap();
+
+    assert_eq!(events, vec![
+        Event::PowerActivated { nation: Nation::OrangeStar, co: Co::Andy, power: PowerKind::Power },
+        Event::Healed { unit: hurt, hp: 20 },
+        Event::Healed { unit: almost, hp: 9 },
+    ]);
+
+    assert_eq!(state.unit(hurt).unwrap().hp, 65);
+    assert_eq!(state.unit(almost).unwrap().hp, 100);
+
+    state.end_turn();
+
+    let player = state.player_mut(Nation::BlueMoon).unwrap();
+
+    player.funds = 9000;
+    player.co.as_mut().unwrap().meter = 2 * STAR_VALUE;
+
+    let events = state.activate_power(PowerKind::Power).unwrap();
+
+    assert_eq!(events, vec![
+        Event::PowerActivated { nation: Nation::BlueMoon, co: Co::Colin, power: PowerKind::Power },
+        Event::FundsGained { nation: Nation::BlueMoon, funds: 4500 },
+    ]);
+
+    assert_eq!(state.current_player().funds, 13500);
+}
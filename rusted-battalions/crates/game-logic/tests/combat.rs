@@ -0,0 +1,181 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, Event, RuleError, Co, PlayerCo, Rules,
+};
+
+mod common;
+
+use common::{NoLuck, grass_map};
+
+
+#[test]
+fn test_counter_attack() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::new(Co::Andy));
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    let result = state.resolve_attack(tank, enemy, NoLuck).unwrap();
+
+    // The counter-attack uses the defender's reduced HP
+    assert_eq!(result.damage, 49);
+    assert_eq!(result.counter, Some(25));
+    assert!(!result.attacker_destroyed);
+    assert!(!result.defender_destroyed);
+
+    assert_eq!(result.events, vec![
+        Event::Attacked { unit: tank, target: enemy, damage: 49, hp: 51 },
+        Event::Attacked { unit: enemy, target: tank, damage: 25, hp: 75 },
+    ]);
+
+    let unit = state.unit(tank).unwrap();
+
+    assert!(unit.waited);
+    assert_eq!(unit.ammo, 8);
+    assert_eq!(state.unit(enemy).unwrap().ammo, 8);
+
+    // Half of the damage dealt, plus all of the damage taken
+    assert_eq!(state.player(Nation::OrangeStar).unwrap().co.as_ref().unwrap().meter, (3430 / 2) + 1750);
+
+    assert_eq!(state.resolve_attack(tank, enemy, NoLuck), Err(RuleError::UnitWaited(tank)));
+}
+
+#[test]
+fn test_forecast() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    let forecast = state.forecast_attack(tank, enemy).unwrap();
+
+    assert_eq!((forecast.min_damage, forecast.max_damage), (49, 57));
+    assert_eq!(forecast.counter, Some((21, 29)));
+
+    // The attack always stays within the forecast
+    let result = state.resolve_attack(tank, enemy, NoLuck).unwrap();
+
+    let (min_counter, max_counter) = forecast.counter.unwrap();
+
+    assert_eq!(result.damage, forecast.min_damage);
+    assert!((min_counter..=max_counter).contains(&result.counter.unwrap()));
+
+    assert_eq!(state.forecast_attack(tank, enemy), Err(RuleError::UnitWaited(tank)));
+}
+
+#[test]
+fn test_destroyed() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit { hp: 20, ..Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon) });
+    let infantry = state.add_unit(Unit { hp: 10, ..Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::OrangeStar) });
+
+    state.start_turn();
+
+    // Destroyed units cannot counter-attack
+    let result = state.resolve_attack(tank, enemy, NoLuck).unwrap();
+
+    assert_eq!(result.counter, None);
+    assert!(result.defender_destroyed);
+
+    assert_eq!(result.events, vec![
+        Event::Attacked { unit: tank, target: enemy, damage: 20, hp: 0 },
+        Event::Destroyed { unit: enemy, coord: Coord::new(1, 0), nation: Nation::BlueMoon, value: 0 },
+    ]);
+
+    assert!(state.unit(enemy).is_none());
+
+    // The attacker can be destroyed by the counter-attack
+    let enemy = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    let result = state.resolve_attack(infantry, enemy, NoLuck).unwrap();
+
+    assert_eq!(result.damage, 0);
+    assert_eq!(result.counter, Some(10));
+    assert!(result.attacker_destroyed);
+    assert!(state.unit(infantry).is_none());
+}
+
+#[test]
+fn test_fog_target() {
+    let mut state = GameState::new(grass_map(8, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.rules = Rules { fog: true, ..Rules::default() };
+
+    let rocket = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Rocket, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(4, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    // The enemy is in range, but it is hidden in the fog
+    assert_eq!(state.resolve_attack(rocket, enemy, NoLuck), Err(RuleError::TargetNotVisible(rocket)));
+    assert_eq!(state.unit(enemy).unwrap().hp, 100);
+
+    // Another unit of the team can spot it
+    state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Recon, Nation::OrangeStar));
+
+    assert!(state.resolve_attack(rocket, enemy, NoLuck).is_ok());
+}
+
+#[test]
+fn test_indirect() {
+    let mut state = GameState::new(grass_map(5, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let artillery = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Artillery, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    // Minimum range
+    assert_eq!(state.resolve_attack(artillery, enemy, NoLuck), Err(RuleError::CannotAttack(artillery)));
+
+    // Indirect units cannot move and attack on the same turn
+    state.move_unit(artillery, Coord::new(3, 0)).unwrap();
+
+    assert_eq!(state.resolve_attack(artillery, enemy, NoLuck), Err(RuleError::CannotAttack(artillery)));
+
+    state.end_turn();
+    state.end_turn();
+
+    // Indirect attacks are never countered
+    let result = state.resolve_attack(artillery, enemy, NoLuck).unwrap();
+
+    assert_eq!(result.damage, 63);
+    assert_eq!(result.counter, None);
+    assert_eq!(state.unit(artillery).unwrap().hp, 100);
+
+    state.end_turn();
+
+    // Too far away for the tank to attack
+    assert_eq!(state.resolve_attack(enemy, artillery, NoLuck), Err(RuleError::CannotAttack(enemy)));
+
+    state.move_unit(enemy, Coord::new(2, 0)).unwrap();
+
+    // Indirect units do not counter-attack
+    let result = state.resolve_attack(enemy, artillery, NoLuck).unwrap();
+
+    assert_eq!(result.counter, None);
+}
+
+#[test]
+fn test_cannot_attack() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let own = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::OrangeStar));
+    let fighter = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Fighter, Nation::BlueMoon));
+
+    state.start_turn();
+
+    assert_eq!(state.resolve_attack(tank, own, NoLuck), Err(RuleError::CannotAttack(tank)));
+
+    // Tanks cannot attack air units
+    assert_eq!(state.resolve_attack(tank, fighter, NoLuck), Err(RuleError::CannotAttack(tank)));
+    assert_eq!(state.resolve_attack(fighter, tank, NoLuck), Err(RuleError::NotYourUnit(fighter)));
+}
@@ -0,0 +1,159 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, Event, RuleError, Command, Rules,
+};
+
+mod common;
+
+use common::{NoLuck, grass_map, path};
+
+
+#[test]
+fn test_move() {
+    let mut state = GameState::new(grass_map(5, 2), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let ally = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(3, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.start_turn();
+
+    // Not connected, stops on an ally, and moves through an enemy
+    assert_eq!(state.apply(Command::Move { unit: tank, path: path(&[(0, 0), (2, 0)]) }, NoLuck), Err(RuleError::CannotMove(tank)));
+    assert_eq!(state.apply(Command::Move { unit: tank, path: path(&[(0, 0), (1, 0)]) }, NoLuck), Err(RuleError::CannotMove(tank)));
+    assert_eq!(state.apply(Command::Move { unit: tank, path: path(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]) }, NoLuck), Err(RuleError::CannotMove(tank)));
+    assert_eq!(state.apply(Command::Move { unit: enemy, path: path(&[(3, 0), (4, 0)]) }, NoLuck), Err(RuleError::NotYourUnit(enemy)));
+
+    // Allies can be moved through
+    let route = path(&[(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)]);
+
+    let events = state.apply(Command::Move { unit: tank, path: route.clone() }, NoLuck).unwrap();
+
+    assert_eq!(events, vec![Event::Moved { unit: tank, path: route }]);
+
+    let unit = state.unit(tank).unwrap();
+
+    assert_eq!(unit.coord, Coord::new(4, 1));
+    assert_eq!(unit.fuel, 65);
+    assert!(unit.moved);
+    assert!(!unit.waited);
+
+    // Units can only move once per turn
+    assert_eq!(state.apply(Command::Move { unit: tank, path: path(&[(4, 1), (4, 0)]) }, NoLuck), Err(RuleError::CannotMove(tank)));
+
+    assert_eq!(state.apply(Command::Wait { unit: tank }, NoLuck), Ok(vec![Event::Waited { unit: tank }]));
+    assert_eq!(state.apply(Command::Wait { unit: tank }, NoLuck), Err(RuleError::UnitWaited(tank)));
+
+    // Too expensive for the movement points or the fuel
+    assert_eq!(state.apply(Command::Move { unit: ally, path: path(&[(1, 0), (1, 1), (2, 1), (3, 1), (3, 0)]) }, NoLuck), Err(RuleError::CannotMove(ally)));
+
+    state.unit_mut(ally).unwrap().fuel = 1;
+
+    assert_eq!(state.apply(Command::Move { unit: ally, path: path(&[(1, 0), (1, 1), (2, 1)]) }, NoLuck), Err(RuleError::CannotMove(ally)));
+    assert!(state.apply(Command::Move { unit: ally, path: path(&[(1, 0), (1, 1)]) }, NoLuck).is_ok());
+}
+
+#[test]
+fn test_fog_trap() {
+    let mut state = GameState::new(grass_map(8, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.rules = Rules { fog: true, ..Rules::default() };
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(6, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.start_turn();
+
+    // The enemy is in the fog, so it can be moved onto
+    assert!(!state.vision(Nation::OrangeStar).can_see(state.unit(enemy).unwrap()));
+    assert!(state.reachable(tank).unwrap().contains(&Coord::new(6, 0)));
+
+    // The tank stops before the enemy
+    let events = state.apply(Command::Move { unit: tank, path: path(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0)]) }, NoLuck).unwrap();
+
+    assert_eq!(events, vec![
+        Event::Moved { unit: tank, path: path(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0)]) },
+        Event::Trapped { unit: tank, coord: Coord::new(6, 0) },
+    ]);
+
+    let unit = state.unit(tank).unwrap();
+
+    assert_eq!(unit.coord, Coord::new(5, 0));
+    assert_eq!(unit.fuel, 65);
+    assert!(unit.waited);
+
+    // Without fog every enemy blocks the path
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.start_turn();
+
+    assert_eq!(state.apply(Command::Move { unit: tank, path: path(&[(0, 0), (1, 0), (2, 0)]) }, NoLuck), Err(RuleError::CannotMove(tank)));
+}
+
+#[test]
+fn test_move_and_attack() {
+    let mut state = GameState::new(grass_map(5, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let artillery = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Artillery, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(4, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.start_turn();
+
+    state.apply(Command::Move { unit: tank, path: path(&[(0, 0), (1, 0), (2, 0), (3, 0)]) }, NoLuck).unwrap();
+
+    let events = state.apply(Command::Attack { unit: tank, target: enemy }, NoLuck).unwrap();
+
+    assert!(matches!(events[0], Event::Attacked { unit, target, .. } if unit == tank && target == enemy));
+    assert!(state.unit(tank).unwrap().waited);
+
+    // Indirect units cannot move and attack on the same turn
+    state.apply(Command::Move { unit: artillery, path: path(&[(1, 0), (2, 0)]) }, NoLuck).unwrap();
+
+    assert_eq!(state.apply(Command::Attack { unit: artillery, target: enemy }, NoLuck), Err(RuleError::CannotAttack(artillery)));
+
+    let events = state.apply(Command::EndTurn, NoLuck).unwrap();
+
+    assert_eq!(events[0], Event::TurnStarted { nation: Nation::BlueMoon, day: 1 });
+}
+
+#[test]
+fn test_supply() {
+    let mut state = GameState::new(grass_map(3, 2), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let apc = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::APC, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let full = state.add_unit(Unit::new(Coord::new(1, 1), UnitClass::Infantry, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit { fuel: 10, ..Unit::new(Coord::new(2, 0), UnitClass::Recon, Nation::BlueMoon) });
+
+    state.start_turn();
+
+    // Used up after the turn started
+    let unit = state.unit_mut(tank).unwrap();
+
+    unit.fuel = 10;
+    unit.ammo = 0;
+
+    assert_eq!(state.apply(Command::Supply { unit: full }, NoLuck), Err(RuleError::CannotSupply(full)));
+
+    // Units which are already full are skipped, and enemies are never supplied
+    assert_eq!(state.apply(Command::Supply { unit: apc }, NoLuck), Ok(vec![Event::Resupplied { unit: tank }]));
+
+    let unit = state.unit(tank).unwrap();
+
+    assert_eq!(unit.fuel, UnitClass::Tank.max_fuel());
+    assert_eq!(unit.ammo, UnitClass::Tank.max_ammo());
+    assert_eq!(state.unit(enemy).unwrap().fuel, 10);
+    assert!(state.unit(apc).unwrap().waited);
+
+    // There must be an adjacent ally
+    state.end_turn();
+    state.end_turn();
+
+    state.remove_unit(tank);
+    state.remove_unit(full);
+
+    assert_eq!(state.apply(Command::Supply { unit: apc }, NoLuck), Err(RuleError::CannotSupply(apc)));
+}
@@ -0,0 +1,41 @@
+//! Fixtures which are shared by the integration tests.
+#![allow(dead_code)]
+
+use rusted_battalions_game_logic::{
+    Map, Coord, Nation, BuildingClass, TerrainClass, Rng,
+};
+
+
+/// Always rolls the lowest number, so there is no luck.
+pub struct NoLuck;
+
+impl Rng for NoLuck {
+    fn range(&mut self, min: i32, _max: i32) -> i32 {
+        min
+    }
+}
+
+
+pub fn grass_map(width: u32, height: u32) -> Map {
+    let mut map = Map::new(width, height);
+
+    for tile in map.tiles_mut() {
+        tile.class = TerrainClass::Grass;
+    }
+
+    map
+}
+
+pub fn set(map: &mut Map, x: u32, y: u32, class: TerrainClass) {
+    map.get_mut(&Coord::new(x, y)).unwrap().class = class;
+}
+
+pub fn set_building(map: &mut Map, x: u32, y: u32, building: BuildingClass, owner: Option<Nation>) {
+    let tile = map.get_mut(&Coord::new(x, y)).unwrap();
+    tile.building = Some(building);
+    tile.owner = owner;
+}
+
+pub fn path(coords: &[(u32, u32)]) -> Vec<Coord> {
+    coords.iter().map(|(x, y)| Coord::new(*x, *y)).collect()
+}
@@ -0,0 +1,427 @@
+use rusted_battalions_game_logic::{
+    Unit, UnitClass, Coord, Nation, Weapon, Target, Attacker, Defender, Rng, LuckMode, calculate_damage, select_weapon,
+    CounterAttack, forecast,
+};
+
+mod common;
+
+use common::{NoLuck};
+
+
+const DAMAGE_VALUES: &str = include_str!("../../../docs/Dual Strike Damage Values.md");
+const TERRAIN_STARS: &str = include_str!("../../../docs/Dual Strike Terrain Stars.md");
+const CO_BONUS: &str = include_str!("../../../docs/Dual Strike CO Bonus.md");
+
+
+struct Section {
+    heading: String,
+    subheading: String,
+    rows: Vec<(String, u32)>,
+}
+
+/// Parses the `# heading`, `## subheading`, and `Name = 50%` rows of the docs.
+fn parse(doc: &str) -> Vec<Section> {
+    let mut sections = vec![];
+
+    let mut heading = "";
+    let mut subheading = "";
+    let mut in_block = false;
+
+    for line in doc.lines() {
+        let line = line.trim_end();
+
+        if line == "```" {
+            in_block = !in_block;
+
+            if in_block {
+                sections.push(Section {
+                    heading: heading.to_string(),
+                    subheading: subheading.to_string(),
+                    rows: vec![],
+                });
+            }
+
+        } else if in_block {
+            let (name, value) = line.split_once(" = ").unwrap();
+            let value = value.strip_suffix('%').unwrap().parse().unwrap();
+
+            sections.last_mut().unwrap().rows.push((name.to_string(), value));
+
+        } else if let Some(line) = line.strip_prefix("## ") {
+            subheading = line;
+
+        } else if let Some(line) = line.strip_prefix("# ") {
+            heading = line;
+        }
+    }
+
+    assert!(!sections.is_empty());
+
+    sections
+}
+
+fn target(name: &str) -> Target {
+    match name {
+        "Infantry" => Target::Unit(UnitClass::Infantry),
+        "Mech" => Target::Unit(UnitClass::Mech),
+        "Recon" => Target::Unit(UnitClass::Recon),
+        "Tank" => Target::Unit(UnitClass::Tank),
+        "Md Tank" => Target::Unit(UnitClass::MediumTank),
+        "Neotank" => Target::Unit(UnitClass::Neotank),
+        "Megatank" => Target::Unit(UnitClass::MegaTank),
+        "APC" => Target::Unit(UnitClass::APC),
+        "Artillery" => Target::Unit(UnitClass::Artillery),
+        "Rockets" => Target::Unit(UnitClass::Rocket),
+        "Anti-Air" => Target::Unit(UnitClass::AntiAir),
+        "Missiles" => Target::Unit(UnitClass::Missile),
+        "Piperunner" => Target::Unit(UnitClass::Piperunner),
+        "Fighter" => Target::Unit(UnitClass::Fighter),
+        "Bomber" => Target::Unit(UnitClass::Bomber),
+        "B Copter" => Target::Unit(UnitClass::BCopter),
+        "T Copter" => Target::Unit(UnitClass::TCopter),
+        "Stealth" => Target::Unit(UnitClass::Stealth),
+        "Black Bomb" => Target::Unit(UnitClass::BlackBomb),
+        "Battleship" => Target::Unit(UnitClass::Battleship),
+        "Cruiser" => Target::Unit(UnitClass::Cruiser),
+        "Lander" => Target::Unit(UnitClass::Lander),
+        "Sub" => Target::Unit(UnitClass::Submarine),
+        "Black Boat" => Target::Unit(UnitClass::BlackBoat),
+        "Carrier" => Target::Unit(UnitClass::Carrier),
+        "Pipeseam" => Target::Pipeseam,
+        _ => panic!("Unknown target {}", name),
+    }
+}
+
+fn unit_class(name: &str) -> UnitClass {
+    match target(name) {
+        Target::Unit(class) => class,
+        Target::Pipeseam => panic!("Pipeseam cannot attack"),
+    }
+}
+
+fn stars(subheading: &str) -> u32 {
+    subheading.strip_suffix(" Star").unwrap().parse().unwrap()
+}
+
+fn check(section: &Section, attacker: &Attacker, defender: &Defender) {
+    for (name, expected) in section.rows.iter() {
+        let defender = Defender {
+            target: target(name),
+            ..defender.clone()
+        };
+
+        let damage = calculate_damage(attacker, &defender, NoLuck).map(|damage| damage.damage);
+
+        let expected = if *expected == 0 { None } else { Some(*expected) };
+
+        assert_eq!(damage, expected, "{} / {} / {}", section.heading, section.subheading, name);
+    }
+}
+
+
+#[test]
+fn test_damage_values() {
+    let sections = parse(DAMAGE_VALUES);
+
+    assert_eq!(sections.len(), 22);
+
+    for section in sections.iter() {
+        let (name, ammo) = match section.subheading.strip_suffix(" (out of ammo)") {
+            Some(name) => (name, 0),
+            None => (section.subheading.as_str(), 9),
+        };
+
+        let attacker = Attacker {
+            ammo,
+            ..Attacker::new(unit_class(name))
+        };
+
+        check(section, &attacker, &Defender::new(Target::Pipeseam));
+    }
+}
+
+#[test]
+fn test_forecast_values() {
+    let sections = parse(DAMAGE_VALUES);
+
+    for section in sections.iter() {
+        let (name, ammo) = match section.subheading.strip_suffix(" (out of ammo)") {
+            Some(name) => (name, 0),
+            None => (section.subheading.as_str(), 9),
+        };
+
+        let attacker = Attacker {
+            ammo,
+            ..Attacker::new(unit_class(name))
+        };
+
+        for (name, expected) in section.rows.iter() {
+            let defender = Defender::new(target(name));
+
+            let forecast = forecast(&attacker, &defender, None);
+
+            if *expected == 0 {
+                assert_eq!(forecast, None, "{} / {}", section.subheading, name);
+
+            } else {
+                let forecast = forecast.unwrap();
+
+                // The lowest damage has no luck, and the highest damage has +9% luck
+                assert_eq!(forecast.min_damage, (*expected).min(100), "{} / {}", section.subheading, name);
+                assert_eq!(forecast.max_damage, (*expected + 9).min(100), "{} / {}", section.subheading, name);
+                assert_eq!(forecast.counter, None);
+            }
+        }
+    }
+
+    // Every unit counter-attacks with its own base damage against the attacker, 0 if it cannot counter-attack
+    let base_damage = |attacker: &str, target: &str| -> u32 {
+        sections.iter()
+            .find(|section| section.subheading == attacker)
+            .and_then(|section| section.rows.iter().find(|(name, _)| name == target))
+            .map(|(_, damage)| *damage)
+            .unwrap_or(0)
+    };
+
+    for section in sections.iter().filter(|section| !section.subheading.ends_with(" (out of ammo)")) {
+        let attacker = Attacker::new(unit_class(&section.subheading));
+
+        for (name, expected) in section.rows.iter().filter(|(name, expected)| *expected > 0 && name != "Pipeseam") {
+            let counter = CounterAttack {
+                attacker: Attacker::new(unit_class(name)),
+                defender: Defender::new(target(&section.subheading)),
+            };
+
+            let result = forecast(&attacker, &Defender::new(target(name)), Some(&counter)).unwrap();
+
+            let counter_damage = base_damage(name, &section.subheading);
+
+            let min_damage = (*expected).min(100);
+            let max_damage = (*expected + 9).min(100);
+
+            let expected_counter = if counter_damage == 0 {
+                None
+
+            } else if min_damage == 100 {
+                Some((0, 0))
+
+            } else {
+                // The counter-attack is scaled by the defender's HP after the attack, with 0% to 9% luck
+                let max = (((counter_damage + 9) * (100 - min_damage)) / 100).min(100);
+                let min = ((counter_damage * (100 - max_damage)) / 100).min(100);
+
+                Some((min, max))
+            };
+
+            assert_eq!(result.counter, expected_counter, "{} / {}", section.subheading, name);
+        }
+    }
+}
+
+#[test]
+fn test_forecast_counter() {
+    let tank = Target::Unit(UnitClass::Tank);
+
+    let counter = CounterAttack {
+        attacker: Attacker::new(UnitClass::Tank),
+        defender: Defender::new(tank),
+    };
+
+    // 55% to 64% damage, so the counter-attack is from 36 HP to 45 HP
+    let result = forecast(&Attacker::new(UnitClass::Tank), &Defender::new(tank), Some(&counter)).unwrap();
+
+    assert_eq!(result.weapon, Weapon::Primary);
+    assert_eq!((result.min_damage, result.max_damage), (55, 64));
+    assert_eq!(result.counter, Some((19, 28)));
+    assert!(!result.can_destroy(100));
+
+    // The defender might be destroyed before it can counter-attack
+    let defender = Defender { hp: 60, ..Defender::new(tank) };
+
+    let result = forecast(&Attacker::new(UnitClass::Tank), &defender, Some(&counter)).unwrap();
+
+    assert_eq!((result.min_damage, result.max_damage), (55, 60));
+    assert_eq!(result.counter, Some((0, 3)));
+    assert!(result.can_destroy(60));
+
+    // The defender is always destroyed
+    let defender = Defender { hp: 50, ..Defender::new(tank) };
+
+    let result = forecast(&Attacker::new(UnitClass::Tank), &defender, Some(&counter)).unwrap();
+
+    assert_eq!(result.counter, Some((0, 0)));
+
+    // The defender does not have a weapon which can hit the attacker
+    let counter = CounterAttack {
+        attacker: Attacker::new(UnitClass::Tank),
+        defender: Defender::new(Target::Unit(UnitClass::Fighter)),
+    };
+
+    let result = forecast(&Attacker::new(UnitClass::Fighter), &Defender::new(tank), Some(&counter));
+
+    assert_eq!(result, None);
+}
+
+#[test]
+fn test_terrain_stars() {
+    let sections = parse(TERRAIN_STARS);
+
+    assert_eq!(sections.len(), 5);
+
+    for section in sections.iter() {
+        assert_eq!(section.heading, "Tank attacking with Andy");
+
+        let defender = Defender {
+            terrain_stars: stars(&section.subheading),
+            ..Defender::new(Target::Pipeseam)
+        };
+
+        check(section, &Attacker::new(UnitClass::Tank), &defender);
+    }
+}
+
+#[test]
+fn test_co_bonus() {
+    let sections = parse(CO_BONUS);
+
+    assert_eq!(sections.len(), 36);
+
+    for section in sections.iter() {
+        let (attacker_name, defender_name) = section.heading.split_once(" attacking ").unwrap();
+
+        let is_max = attacker_name.starts_with("Max ");
+        let co_power = attacker_name.contains("CO power");
+
+        let comtowers = match attacker_name.split_once(" comtower") {
+            Some((left, _)) => left.rsplit('(').next().unwrap().parse().unwrap(),
+            None => 0,
+        };
+
+        let co_bonus = match (is_max, co_power) {
+            // Max has +20% for directs, and Max Force gives another +30%
+            (true, true) => 50,
+            (true, false) => 20,
+            (false, _) => 0,
+        };
+
+        let attacker = Attacker {
+            co_bonus,
+            co_power,
+            comtowers,
+            ..Attacker::new(UnitClass::Tank)
+        };
+
+        let defender = Defender {
+            // Kanbei has +20% defense
+            co_bonus: if defender_name == "Kanbei" { 20 } else { 0 },
+            terrain_stars: stars(&section.subheading),
+            ..Defender::new(Target::Pipeseam)
+        };
+
+        check(section, &attacker, &defender);
+    }
+}
+
+#[test]
+fn test_weapons() {
+    let infantry = Target::Unit(UnitClass::Infantry);
+    let tank = Target::Unit(UnitClass::Tank);
+
+    assert_eq!(select_weapon(UnitClass::Tank, 9, tank), Some((Weapon::Primary, 55)));
+    assert_eq!(select_weapon(UnitClass::Tank, 0, tank), Some((Weapon::Secondary, 6)));
+    assert_eq!(select_weapon(UnitClass::Tank, 9, infantry), Some((Weapon::Secondary, 75)));
+
+    assert_eq!(select_weapon(UnitClass::Artillery, 9, tank), Some((Weapon::Primary, 70)));
+    assert_eq!(select_weapon(UnitClass::Artillery, 0, tank), None);
+
+    assert_eq!(select_weapon(UnitClass::APC, 0, tank), None);
+    assert_eq!(select_weapon(UnitClass::Infantry, 0, Target::Unit(UnitClass::Fighter)), None);
+}
+
+#[test]
+fn test_ammo() {
+    let tank = Target::Unit(UnitClass::Tank);
+
+    let mut unit = Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar);
+
+    unit.ammo = 1;
+
+    let (weapon, _) = unit.weapon(tank).unwrap();
+
+    assert_eq!(weapon, Weapon::Primary);
+
+    unit.use_ammo(weapon);
+
+    assert_eq!(unit.ammo, 0);
+
+    // Out of ammo, so it uses the machine gun
+    assert_eq!(unit.weapon(tank), Some((Weapon::Secondary, 6)));
+
+    unit.use_ammo(Weapon::Secondary);
+
+    assert_eq!(unit.ammo, 0);
+
+    // Artillery does not have a secondary weapon
+    let artillery = Unit { ammo: 0, ..Unit::new(Coord::new(0, 0), UnitClass::Artillery, Nation::OrangeStar) };
+
+    assert_eq!(artillery.weapon(tank), None);
+}
+
+#[test]
+fn test_non_rounded_hp() {
+    let defender = Defender::new(Target::Unit(UnitClass::Infantry));
+
+    let attacker = Attacker {
+        hp: 55,
+        ..Attacker::new(UnitClass::Tank)
+    };
+
+    // 75% * 55 HP
+    assert_eq!(calculate_damage(&attacker, &defender, NoLuck).unwrap().damage, 41);
+
+    let defender = Defender {
+        hp: 55,
+        terrain_stars: 3,
+        ..defender
+    };
+
+    // 75% * (100% - 16.5%)
+    assert_eq!(calculate_damage(&Attacker::new(UnitClass::Tank), &defender, NoLuck).unwrap().damage, 62);
+}
+
+#[test]
+fn test_luck() {
+    struct MaxLuck;
+
+    impl Rng for MaxLuck {
+        fn range(&mut self, _min: i32, max: i32) -> i32 {
+            max
+        }
+    }
+
+    let attacker = Attacker {
+        good_luck: 9,
+        bad_luck: 0,
+        ..Attacker::new(UnitClass::Tank)
+    };
+
+    let defender = Defender::new(Target::Unit(UnitClass::Tank));
+
+    assert_eq!(calculate_damage(&attacker, &defender, MaxLuck).unwrap().damage, 64);
+
+    let attacker = Attacker {
+        good_luck: 0,
+        bad_luck: 90,
+        ..attacker
+    };
+
+    // Damage cannot go below 0
+    assert_eq!(calculate_damage(&attacker, &defender, NoLuck).unwrap().damage, 0);
+
+    let attacker = Attacker {
+        luck_mode: LuckMode::Classic,
+        ..attacker
+    };
+
+    assert_eq!(calculate_damage(&attacker, &defender, MaxLuck).unwrap().damage, 0);
+}
@@ -0,0 +1,95 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, Event, RuleError, Vision,
+};
+
+mod common;
+
+use common::{grass_map};
+
+
+#[test]
+fn test_hide() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let stealth = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Stealth, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Submarine, Nation::BlueMoon));
+
+    state.start_turn();
+
+    assert_eq!(state.hide_unit(tank), Err(RuleError::CannotHide(tank)));
+    assert_eq!(state.hide_unit(enemy), Err(RuleError::NotYourUnit(enemy)));
+    assert_eq!(state.appear_unit(stealth), Err(RuleError::CannotHide(stealth)));
+
+    assert_eq!(state.hide_unit(stealth), Ok(vec![Event::Hidden { unit: stealth }]));
+
+    let unit = state.unit(stealth).unwrap();
+
+    assert!(unit.hidden);
+    assert!(unit.waited);
+
+    assert_eq!(state.appear_unit(stealth), Err(RuleError::UnitWaited(stealth)));
+
+    state.end_turn();
+
+    assert_eq!(state.hide_unit(enemy), Ok(vec![Event::Hidden { unit: enemy }]));
+
+    state.end_turn();
+
+    assert_eq!(state.appear_unit(stealth), Ok(vec![Event::Appeared { unit: stealth }]));
+    assert!(!state.unit(stealth).unwrap().hidden);
+}
+
+#[test]
+fn test_hidden_vision() {
+    let map = grass_map(9, 1);
+
+    let sub = Unit { hidden: true, ..Unit::new(Coord::new(4, 0), UnitClass::Submarine, Nation::BlueMoon) };
+    let far = Unit::new(Coord::new(2, 0), UnitClass::Recon, Nation::OrangeStar);
+    let near = Unit::new(Coord::new(5, 0), UnitClass::Infantry, Nation::OrangeStar);
+
+    // The tile is visible, but the dived Submarine is not
+    let vision = Vision::new(&map, [&sub, &far], Nation::OrangeStar);
+
+    assert!(vision.is_visible(&sub.coord));
+    assert!(!vision.can_see(&sub));
+
+    let vision = Vision::new(&map, [&sub, &far, &near], Nation::OrangeStar);
+
+    assert!(vision.can_see(&sub));
+
+    // The owner can always see their own hidden units
+    let vision = Vision::new(&map, [&sub], Nation::BlueMoon);
+
+    assert!(vision.can_see(&sub));
+
+    // Hidden units are still hidden without Fog of War
+    let vision = Vision::without_fog(&map, [&sub, &far], Nation::OrangeStar);
+
+    assert!(vision.is_visible(&Coord::new(8, 0)));
+    assert!(!vision.can_see(&sub));
+
+    let vision = Vision::without_fog(&map, [&sub, &near], Nation::OrangeStar);
+
+    assert!(vision.can_see(&sub));
+}
+
+#[test]
+fn test_hidden_attackers() {
+    let stealth = Unit { hidden: true, ..Unit::new(Coord::new(0, 0), UnitClass::Stealth, Nation::BlueMoon) };
+    let sub = Unit { hidden: true, ..Unit::new(Coord::new(0, 0), UnitClass::Submarine, Nation::BlueMoon) };
+
+    assert!(stealth.can_be_attacked_by(UnitClass::Fighter));
+    assert!(stealth.can_be_attacked_by(UnitClass::Stealth));
+    assert!(!stealth.can_be_attacked_by(UnitClass::AntiAir));
+    assert!(!stealth.can_be_attacked_by(UnitClass::Missile));
+
+    assert!(sub.can_be_attacked_by(UnitClass::Cruiser));
+    assert!(sub.can_be_attacked_by(UnitClass::Submarine));
+    assert!(!sub.can_be_attacked_by(UnitClass::Battleship));
+    assert!(!sub.can_be_attacked_by(UnitClass::Bomber));
+
+    let sub = Unit { hidden: false, ..sub };
+
+    assert!(sub.can_be_attacked_by(UnitClass::Battleship));
+}
@@ -0,0 +1,193 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, RuleError, Rules, Command, History, UnitId, Event,
+};
+
+mod common;
+
+use common::{grass_map, path};
+
+
+/// Orange Star has an Infantry at (0, 0) and a Tank at (0, 1), Blue Moon has an Infantry at (5, 0).
+fn fog_state(rules: Rules) -> (GameState, [UnitId; 3]) {
+    let mut state = GameState::new(grass_map(8, 2), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.rules = rules;
+
+    let infantry = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(0, 1), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(5, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.start_turn();
+
+    (state, [infantry, tank, enemy])
+}
+
+
+#[test]
+fn test_replay() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    // The luck and the weather both come from the seed
+    state.rules = Rules { seed: 5, random_weather: true, ..Rules::default() };
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    let commands = vec![
+        Command::Move { unit: tank, path: path(&[(0, 0), (1, 0)]) },
+        Command::Attack { unit: tank, target: enemy },
+        Command::EndTurn,
+        Command::Attack { unit: enemy, target: tank },
+    ];
+
+    let first = History::replay(state.clone(), &commands).unwrap();
+    let second = History::replay(state.clone(), &commands).unwrap();
+
+    assert_eq!(first.commands(), &commands[..]);
+    assert_eq!(first.state().current_weather(), second.state().current_weather());
+
+    for id in [tank, enemy] {
+        assert_eq!(first.state().unit(id).map(|unit| unit.hp), second.state().unit(id).map(|unit| unit.hp));
+    }
+
+    // Commands which are not allowed stop the replay
+    assert_eq!(
+        History::replay(state, &[Command::Attack { unit: enemy, target: tank }]).map(|_| ()),
+        Err(RuleError::NotYourUnit(enemy)),
+    );
+}
+
+#[test]
+fn test_undo_redo() {
+    let (state, [infantry, tank, _]) = fog_state(Rules { fog: true, ..Rules::default() });
+
+    let mut history = History::new(state.clone());
+
+    assert!(!history.can_undo());
+    assert_eq!(history.undo(), Err(RuleError::CannotUndo));
+    assert_eq!(history.redo(), Err(RuleError::CannotRedo));
+
+    let move_tank = Command::Move { unit: tank, path: path(&[(0, 1), (1, 1)]) };
+
+    history.apply(move_tank.clone()).unwrap();
+    history.apply(Command::Wait { unit: infantry }).unwrap();
+
+    history.undo().unwrap();
+    history.undo().unwrap();
+
+    assert!(!history.can_undo());
+    assert!(history.commands().is_empty());
+    assert_eq!(history.state().unit(tank).unwrap().coord, Coord::new(0, 1));
+    assert_eq!(history.state().unit(tank).unwrap().fuel, state.unit(tank).unwrap().fuel);
+    assert!(!history.state().unit(infantry).unwrap().waited);
+
+    history.redo().unwrap();
+
+    assert_eq!(history.commands(), &[move_tank]);
+    assert_eq!(history.state().unit(tank).unwrap().coord, Coord::new(1, 1));
+    assert!(history.can_redo());
+
+    // A new command replaces the undone commands
+    history.apply(Command::Wait { unit: tank }).unwrap();
+
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn test_hidden_information() {
+    let (state, [infantry, tank, _]) = fog_state(Rules { fog: true, ..Rules::default() });
+
+    let mut history = History::new(state);
+
+    // The enemy is still in the fog
+    history.apply(Command::Move { unit: tank, path: path(&[(0, 1), (1, 1)]) }).unwrap();
+
+    assert!(history.can_undo());
+
+    // Moving within vision range of the enemy's infantry reveals it
+    history.apply(Command::Move { unit: infantry, path: path(&[(0, 0), (1, 0), (2, 0), (3, 0)]) }).unwrap();
+
+    assert!(!history.can_undo());
+    assert_eq!(history.undo(), Err(RuleError::CannotUndo));
+
+    history.apply(Command::Wait { unit: tank }).unwrap();
+
+    assert!(history.can_undo());
+
+    // Ending the turn cannot be undone
+    history.apply(Command::EndTurn).unwrap();
+
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn test_without_fog() {
+    let (state, [infantry, tank, _]) = fog_state(Rules::default());
+
+    let mut history = History::new(state);
+
+    // Every enemy can already be seen
+    history.apply(Command::Move { unit: infantry, path: path(&[(0, 0), (1, 0), (2, 0), (3, 0)]) }).unwrap();
+
+    assert!(history.can_undo());
+
+    history.apply(Command::Wait { unit: tank }).unwrap();
+
+    history.undo().unwrap();
+    history.undo().unwrap();
+
+    assert_eq!(history.state().unit(infantry).unwrap().coord, Coord::new(0, 0));
+}
+
+#[test]
+fn test_trapped() {
+    let (mut state, [_, tank, enemy]) = fog_state(Rules { fog: true, ..Rules::default() });
+
+    state.unit_mut(enemy).unwrap().coord = Coord::new(5, 1);
+
+    let mut history = History::new(state);
+
+    // Running into a hidden enemy cannot be undone
+    let events = history.apply(Command::Move { unit: tank, path: path(&[(0, 1), (1, 1), (2, 1), (3, 1), (4, 1), (5, 1)]) }).unwrap();
+
+    assert!(events.contains(&Event::Trapped { unit: tank, coord: Coord::new(5, 1) }));
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn test_luck_roll() {
+    let mut state = GameState::new(grass_map(2, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    let mut history = History::new(state.clone());
+
+    history.apply(Command::Attack { unit: tank, target: enemy }).unwrap();
+
+    assert!(!history.can_undo());
+
+    // Sandbox matches can undo everything, and redo rolls the same luck
+    state.rules = Rules { sandbox: true, ..Rules::default() };
+
+    let mut history = History::new(state);
+
+    history.apply(Command::Attack { unit: tank, target: enemy }).unwrap();
+
+    let hp = history.state().unit(enemy).unwrap().hp;
+
+    history.apply(Command::EndTurn).unwrap();
+
+    history.undo().unwrap();
+    history.undo().unwrap();
+
+    assert_eq!(history.state().unit(enemy).unwrap().hp, 100);
+
+    history.redo().unwrap();
+
+    assert_eq!(history.state().unit(enemy).unwrap().hp, hp);
+}
@@ -0,0 +1,137 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, Event, RuleError, join_refund,
+};
+
+mod common;
+
+use common::{grass_map, path};
+
+
+fn tank(x: u32, hp: u32, build_cost: u32) -> Unit {
+    Unit {
+        hp,
+        build_cost,
+        ..Unit::new(Coord::new(x, 0), UnitClass::Tank, Nation::OrangeStar)
+    }
+}
+
+
+#[test]
+fn test_join() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar]);
+
+    let unit = state.add_unit(Unit { fuel: 40, ammo: 2, ..tank(0, 30, 7000) });
+    let target = state.add_unit(Unit { fuel: 50, ammo: 3, ..tank(1, 45, 7000) });
+
+    state.start_turn();
+
+    let events = state.move_along(unit, &path(&[(0, 0), (1, 0)])).unwrap();
+
+    assert_eq!(events, vec![
+        Event::Moved { unit, path: path(&[(0, 0), (1, 0)]) },
+        Event::Joined { unit, target, hp: 75, refund: 0 },
+    ]);
+
+    assert!(state.unit(unit).is_none());
+
+    let target = state.unit(target).unwrap();
+
+    assert_eq!(target.hp, 75);
+    assert_eq!(target.fuel, 70);
+    assert_eq!(target.coord, Coord::new(1, 0));
+    assert_eq!(target.ammo, 5);
+    assert!(target.waited);
+
+    assert_eq!(state.current_player().funds, 0);
+}
+
+#[test]
+fn test_always_join() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar]);
+
+    // Advance Wars does not allow a damaged unit to join with a 100 HP unit
+    let unit = state.add_unit(tank(0, 55, 7000));
+    let target = state.add_unit(tank(1, 100, 7000));
+
+    state.start_turn();
+
+    let events = state.move_along(unit, &path(&[(0, 0), (1, 0)])).unwrap();
+
+    // Refund uses the non-rounded HP, Advance Wars would refund 6 HP
+    assert_eq!(events[1], Event::Joined { unit, target, hp: 100, refund: 3850 });
+    assert_eq!(state.current_player().funds, 3850);
+}
+
+#[test]
+fn test_build_cost() {
+    // Hachi's Merchant Union changes the cost after the units were built
+    let cheap = tank(0, 60, 3500);
+    let normal = tank(1, 70, 7000);
+
+    assert_eq!(join_refund(&cheap, &normal), 30 * 3500 / 100);
+    assert_eq!(join_refund(&normal, &cheap), 30 * 7000 / 100);
+
+    // Kanbei's units cost +20%
+    let kanbei = tank(0, 60, 8400);
+
+    assert_eq!(join_refund(&kanbei, &normal), 2520);
+    assert_eq!(kanbei.value(), 5040);
+
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar]);
+
+    let unit = state.add_unit(kanbei);
+    let target = state.add_unit(normal);
+
+    state.start_turn();
+    state.move_along(unit, &path(&[(0, 0), (1, 0)])).unwrap();
+
+    assert_eq!(state.current_player().funds, 2520);
+
+    // The target keeps its own build cost
+    assert_eq!(state.unit(target).unwrap().build_cost, 7000);
+}
+
+#[test]
+fn test_cannot_join() {
+    let mut state = GameState::new(grass_map(5, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let unit = state.add_unit(tank(0, 50, 7000));
+    let infantry = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let far = state.add_unit(tank(2, 50, 7000));
+    let enemy = state.add_unit(Unit::new(Coord::new(4, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    // The unit must be on the target's tile
+    assert_eq!(state.join_unit(unit, infantry), Err(RuleError::CannotJoin(unit)));
+    assert_eq!(state.join_unit(unit, far), Err(RuleError::CannotJoin(unit)));
+    assert_eq!(state.join_unit(unit, unit), Err(RuleError::CannotJoin(unit)));
+    assert_eq!(state.join_unit(enemy, far), Err(RuleError::NotYourUnit(enemy)));
+
+    // Only units of the same class can be joined
+    assert_eq!(state.move_along(unit, &path(&[(0, 0), (1, 0)])), Err(RuleError::CannotMove(unit)));
+    assert_eq!(state.move_along(far, &path(&[(2, 0), (3, 0), (4, 0)])), Err(RuleError::CannotMove(far)));
+}
+
+#[test]
+fn test_join_after_move() {
+    let mut state = GameState::new(grass_map(8, 2), &[Nation::OrangeStar]);
+
+    let unit = state.add_unit(tank(0, 50, 7000));
+    let target = state.add_unit(tank(7, 50, 7000));
+    let empty = state.add_unit(Unit { fuel: 0, coord: Coord::new(6, 1), ..tank(0, 50, 7000) });
+
+    state.start_turn();
+
+    // The tank moved its full range, so it cannot join the tank on the next tile
+    state.move_along(unit, &path(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0)])).unwrap();
+
+    assert_eq!(state.join_unit(unit, target), Err(RuleError::CannotJoin(unit)));
+    assert_eq!(state.move_along(unit, &path(&[(6, 0), (7, 0)])), Err(RuleError::CannotMove(unit)));
+
+    // Units without fuel cannot move onto the target
+    assert_eq!(state.join_unit(empty, target), Err(RuleError::CannotJoin(empty)));
+    assert_eq!(state.move_along(empty, &path(&[(6, 1), (7, 1), (7, 0)])), Err(RuleError::CannotMove(empty)));
+
+    assert_eq!(state.unit(target).unwrap().hp, 50);
+}
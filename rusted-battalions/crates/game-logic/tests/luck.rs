@@ -0,0 +1,126 @@
+use rusted_battalions_game_logic::{Rng, SeededRng, LuckMode};
+
+
+const ROLLS: usize = 100_000;
+
+
+/// Rolls the luck many times and returns how often each luck value was rolled.
+fn distribution(mode: LuckMode, good_luck: u32, bad_luck: u32) -> Vec<(i32, usize)> {
+    let (min, max) = mode.range(good_luck, bad_luck);
+
+    let mut counts = vec![0; (max - min + 1) as usize];
+
+    let mut rng = SeededRng::new(5);
+
+    for _ in 0..ROLLS {
+        let luck = mode.roll(good_luck, bad_luck, &mut rng);
+
+        assert!(luck >= min && luck <= max, "{} is not in {}..={}", luck, min, max);
+
+        counts[(luck - min) as usize] += 1;
+    }
+
+    (min..=max).zip(counts).collect()
+}
+
+fn average(counts: &[(i32, usize)]) -> f64 {
+    let sum: i64 = counts.iter().map(|(luck, count)| *luck as i64 * *count as i64).sum();
+    sum as f64 / ROLLS as f64
+}
+
+/// Every luck value should be equally likely.
+fn check_single_roll(good_luck: u32, bad_luck: u32) {
+    let counts = distribution(LuckMode::SingleRoll, good_luck, bad_luck);
+
+    assert_eq!(counts.first().unwrap().0, -(bad_luck as i32));
+    assert_eq!(counts.last().unwrap().0, good_luck as i32);
+
+    let expected = ROLLS as f64 / counts.len() as f64;
+
+    for (luck, count) in counts.iter() {
+        let error = (*count as f64 - expected).abs() / expected;
+        assert!(error < 0.1, "luck {} was rolled {} times, expected {}", luck, count, expected);
+    }
+
+    let expected = (good_luck as f64 - bad_luck as f64) / 2.0;
+    assert!((average(&counts) - expected).abs() < 0.25);
+}
+
+/// Subtracting two rolls makes the luck values near the middle more likely.
+fn check_classic(good_luck: u32, bad_luck: u32) {
+    let counts = distribution(LuckMode::Classic, good_luck, bad_luck);
+
+    let count = |luck: i32| counts.iter().find(|x| x.0 == luck).unwrap().1;
+
+    let possible = ((good_luck + 1) * (bad_luck + 1)) as f64;
+
+    // There is only one way to roll the max good luck or the max bad luck
+    let expected = ROLLS as f64 / possible;
+
+    for luck in [good_luck as i32, -(bad_luck as i32)] {
+        let error = (count(luck) as f64 - expected).abs() / expected;
+        assert!(error < 0.25, "luck {} was rolled {} times, expected {}", luck, count(luck), expected);
+    }
+
+    assert!(count(0) > count(good_luck as i32) * 5);
+    assert!(count(0) > count(-(bad_luck as i32)) * 5);
+
+    let expected = (good_luck as f64 - bad_luck as f64) / 2.0;
+    assert!((average(&counts) - expected).abs() < 0.25);
+}
+
+
+#[test]
+fn test_seeded_rng() {
+    let mut a = SeededRng::new(10);
+    let mut b = SeededRng::new(10);
+    let mut c = SeededRng::new(11);
+
+    let a: Vec<i32> = (0..100).map(|_| a.range(-50, 50)).collect();
+    let b: Vec<i32> = (0..100).map(|_| b.range(-50, 50)).collect();
+    let c: Vec<i32> = (0..100).map(|_| c.range(-50, 50)).collect();
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    let mut rng = SeededRng::new(1);
+
+    for _ in 0..100 {
+        assert_eq!(rng.range(7, 7), 7);
+        assert_eq!(rng.range(i32::MIN, i32::MIN), i32::MIN);
+    }
+}
+
+#[test]
+fn test_luck_range() {
+    assert_eq!(LuckMode::SingleRoll.range(9, 0), (0, 9));
+    assert_eq!(LuckMode::Classic.range(15, 10), (-10, 15));
+    assert_eq!(LuckMode::default(), LuckMode::SingleRoll);
+}
+
+#[test]
+fn test_andy() {
+    check_single_roll(9, 0);
+
+    // With no bad luck both modes never roll below 0
+    let counts = distribution(LuckMode::Classic, 9, 0);
+    assert!(counts.iter().take_while(|x| x.0 < 0).all(|x| x.1 == 0));
+}
+
+#[test]
+fn test_flak() {
+    check_single_roll(15, 10);
+    check_classic(15, 10);
+}
+
+#[test]
+fn test_jugger() {
+    check_single_roll(30, 15);
+    check_classic(30, 15);
+}
+
+#[test]
+fn test_sonja() {
+    check_single_roll(9, 9);
+    check_classic(9, 9);
+}
@@ -0,0 +1,211 @@
+use rusted_battalions_game_logic::{
+    Coord, Unit, UnitClass, Nation, TerrainClass, BuildingClass, MovementType,
+    Occupant, reachable_tiles,
+};
+
+mod common;
+
+use common::{grass_map, set};
+
+
+#[test]
+fn test_every_unit_has_movement() {
+    for class in UnitClass::ALL {
+        assert!(class.movement() > 0, "{:?}", class);
+    }
+
+    assert_eq!(UnitClass::Infantry.movement_type(), MovementType::Foot);
+    assert_eq!(UnitClass::Mech.movement_type(), MovementType::Boots);
+    assert_eq!(UnitClass::Tank.movement_type(), MovementType::Treads);
+    assert_eq!(UnitClass::Recon.movement_type(), MovementType::Tires);
+    assert_eq!(UnitClass::Fighter.movement_type(), MovementType::Air);
+    assert_eq!(UnitClass::Submarine.movement_type(), MovementType::Sea);
+    assert_eq!(UnitClass::Lander.movement_type(), MovementType::Lander);
+    assert_eq!(UnitClass::Piperunner.movement_type(), MovementType::Pipe);
+}
+
+#[test]
+fn test_empty_is_impassable() {
+    for class in UnitClass::ALL {
+        assert_eq!(TerrainClass::Empty.movement_cost(class.movement_type()), None);
+    }
+}
+
+#[test]
+fn test_infantry_diamond() {
+    let map = grass_map(9, 9);
+
+    let unit = Unit::new(Coord::new(4, 4), UnitClass::Infantry, Nation::OrangeStar);
+
+    let reachable = reachable_tiles(&unit, &map, |_| None);
+
+    for tile in map.tiles() {
+        let distance = tile.coord.distance(&unit.coord);
+
+        match reachable.get(&tile.coord) {
+            Some(reachable) => {
+                assert!(distance <= 3);
+                assert_eq!(reachable.cost, distance);
+            },
+            None => {
+                assert!(distance > 3);
+            },
+        }
+    }
+
+    assert_eq!(reachable.get(&unit.coord).unwrap().parent, None);
+}
+
+#[test]
+fn test_terrain_costs() {
+    let mut map = grass_map(6, 1);
+
+    set(&mut map, 1, 0, TerrainClass::Forest);
+    set(&mut map, 2, 0, TerrainClass::Mountain { variant: 0 });
+    set(&mut map, 3, 0, TerrainClass::River);
+
+    let infantry = Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar);
+    let reachable = reachable_tiles(&infantry, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(1, 0)).unwrap().cost, 1);
+    assert_eq!(reachable.get(&Coord::new(2, 0)).unwrap().cost, 3);
+    assert!(!reachable.contains(&Coord::new(3, 0)));
+
+    let mech = Unit::new(Coord::new(0, 0), UnitClass::Mech, Nation::OrangeStar);
+    let reachable = reachable_tiles(&mech, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(2, 0)).unwrap().cost, 2);
+    assert!(!reachable.contains(&Coord::new(3, 0)));
+
+    let tank = Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar);
+    let reachable = reachable_tiles(&tank, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(1, 0)).unwrap().cost, 2);
+    assert!(!reachable.contains(&Coord::new(2, 0)));
+
+    let recon = Unit::new(Coord::new(0, 0), UnitClass::Recon, Nation::OrangeStar);
+    let reachable = reachable_tiles(&recon, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(1, 0)).unwrap().cost, 3);
+
+    let fighter = Unit::new(Coord::new(0, 0), UnitClass::Fighter, Nation::OrangeStar);
+    let reachable = reachable_tiles(&fighter, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(5, 0)).unwrap().cost, 5);
+}
+
+#[test]
+fn test_sea_units() {
+    let mut map = grass_map(5, 1);
+
+    set(&mut map, 1, 0, TerrainClass::Shoal);
+    set(&mut map, 2, 0, TerrainClass::Ocean);
+    set(&mut map, 3, 0, TerrainClass::Reef);
+    set(&mut map, 4, 0, TerrainClass::Ocean);
+
+    let lander = Unit::new(Coord::new(2, 0), UnitClass::Lander, Nation::BlueMoon);
+    let reachable = reachable_tiles(&lander, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(1, 0)).unwrap().cost, 1);
+    assert_eq!(reachable.get(&Coord::new(3, 0)).unwrap().cost, 2);
+    assert_eq!(reachable.get(&Coord::new(4, 0)).unwrap().cost, 3);
+    assert!(!reachable.contains(&Coord::new(0, 0)));
+
+    let cruiser = Unit::new(Coord::new(2, 0), UnitClass::Cruiser, Nation::BlueMoon);
+    let reachable = reachable_tiles(&cruiser, &map, |_| None);
+
+    assert!(!reachable.contains(&Coord::new(1, 0)));
+    assert_eq!(reachable.get(&Coord::new(4, 0)).unwrap().cost, 3);
+}
+
+#[test]
+fn test_buildings() {
+    let mut map = grass_map(3, 1);
+
+    set(&mut map, 0, 0, TerrainClass::Ocean);
+    map.get_mut(&Coord::new(1, 0)).unwrap().building = Some(BuildingClass::Port);
+    map.get_mut(&Coord::new(2, 0)).unwrap().building = Some(BuildingClass::City);
+
+    let submarine = Unit::new(Coord::new(0, 0), UnitClass::Submarine, Nation::GreenEarth);
+    let reachable = reachable_tiles(&submarine, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(1, 0)).unwrap().cost, 1);
+    assert!(!reachable.contains(&Coord::new(2, 0)));
+
+    let recon = Unit::new(Coord::new(2, 0), UnitClass::Recon, Nation::GreenEarth);
+    let reachable = reachable_tiles(&recon, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(1, 0)).unwrap().cost, 1);
+}
+
+#[test]
+fn test_piperunner() {
+    let mut map = grass_map(4, 2);
+
+    set(&mut map, 0, 0, TerrainClass::Pipeline);
+    set(&mut map, 1, 0, TerrainClass::Pipeseam { destroyed: false });
+    set(&mut map, 2, 0, TerrainClass::Pipeline);
+    map.get_mut(&Coord::new(3, 0)).unwrap().building = Some(BuildingClass::Base);
+
+    let piperunner = Unit::new(Coord::new(0, 0), UnitClass::Piperunner, Nation::YellowComet);
+    let reachable = reachable_tiles(&piperunner, &map, |_| None);
+
+    assert_eq!(reachable.get(&Coord::new(3, 0)).unwrap().cost, 3);
+    assert!(!reachable.contains(&Coord::new(0, 1)));
+
+    let tank = Unit::new(Coord::new(0, 1), UnitClass::Tank, Nation::YellowComet);
+    let reachable = reachable_tiles(&tank, &map, |_| None);
+
+    assert!(!reachable.contains(&Coord::new(0, 0)));
+    assert!(!reachable.contains(&Coord::new(1, 0)));
+}
+
+#[test]
+fn test_occupied() {
+    let map = grass_map(5, 1);
+
+    let unit = Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar);
+
+    let reachable = reachable_tiles(&unit, &map, |coord| {
+        match coord.x {
+            1 => Some(Occupant::Ally),
+            3 => Some(Occupant::Enemy),
+            _ => None,
+        }
+    });
+
+    assert!(reachable.get(&Coord::new(1, 0)).unwrap().occupied);
+    assert_eq!(reachable.get(&Coord::new(2, 0)).unwrap().cost, 2);
+    assert!(!reachable.contains(&Coord::new(3, 0)));
+    assert!(!reachable.contains(&Coord::new(4, 0)));
+
+    let destinations = reachable.destinations().collect::<Vec<_>>();
+    assert_eq!(destinations, vec![Coord::new(0, 0), Coord::new(2, 0)]);
+}
+
+#[test]
+fn test_path() {
+    let mut map = grass_map(3, 3);
+
+    for tile in map.tiles_mut() {
+        tile.class = TerrainClass::Road { ruins: false };
+    }
+
+    set(&mut map, 1, 0, TerrainClass::Mountain { variant: 0 });
+    set(&mut map, 1, 1, TerrainClass::Mountain { variant: 0 });
+
+    let unit = Unit::new(Coord::new(0, 0), UnitClass::Recon, Nation::OrangeStar);
+    let reachable = reachable_tiles(&unit, &map, |_| None);
+
+    assert_eq!(reachable.path(&Coord::new(2, 0)), Some(vec![
+        Coord::new(0, 0),
+        Coord::new(0, 1),
+        Coord::new(0, 2),
+        Coord::new(1, 2),
+        Coord::new(2, 2),
+        Coord::new(2, 1),
+        Coord::new(2, 0),
+    ]));
+
+    assert_eq!(reachable.get(&Coord::new(2, 0)).unwrap().cost, 6);
+}
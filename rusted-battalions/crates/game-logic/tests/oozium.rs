@@ -0,0 +1,102 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, Target, Weapon, Event, RuleError,
+    Co, PlayerCo, base_damage,
+};
+
+mod common;
+
+use common::{grass_map};
+
+
+#[test]
+fn test_engulf() {
+    let mut state = GameState::new(grass_map(4, 1), &[Nation::OrangeStar, Nation::BlackHole]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::MegaTank, Nation::OrangeStar));
+    let oozium = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Oozium, Nation::BlackHole));
+
+    state.start_turn();
+    state.end_turn();
+
+    let events = state.engulf_unit(oozium, tank).unwrap();
+
+    assert_eq!(events, vec![
+        Event::Engulfed { unit: oozium, target: tank },
+        Event::Destroyed { unit: tank, coord: Coord::new(0, 0), nation: Nation::OrangeStar, value: 28000 },
+    ]);
+
+    assert!(state.unit(tank).is_none());
+
+    let unit = state.unit(oozium).unwrap();
+
+    assert_eq!(unit.coord, Coord::new(0, 0));
+    assert!(unit.waited);
+}
+
+#[test]
+fn test_engulf_meter() {
+    let mut state = GameState::new(grass_map(4, 1), &[Nation::OrangeStar, Nation::BlackHole]);
+
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::new(Co::Andy));
+    state.player_mut(Nation::BlackHole).unwrap().co = Some(PlayerCo::new(Co::Andy));
+
+    let apc = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::APC, Nation::OrangeStar));
+    let infantry = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let oozium = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Oozium, Nation::BlackHole));
+
+    state.start_turn();
+    state.move_along(infantry, &[Coord::new(1, 0), Coord::new(0, 0)]).unwrap();
+    state.end_turn();
+
+    state.move_along(oozium, &[Coord::new(2, 0), Coord::new(1, 0)]).unwrap();
+    state.engulf_unit(oozium, apc).unwrap();
+
+    // The APC and its cargo both charge the meters
+    let meter = |nation| state.player(nation).unwrap().co.as_ref().unwrap().meter;
+
+    assert_eq!(meter(Nation::OrangeStar), 5000 + 1000);
+    assert_eq!(meter(Nation::BlackHole), (5000 + 1000) / 2);
+}
+
+#[test]
+fn test_cannot_engulf() {
+    let mut state = GameState::new(grass_map(4, 1), &[Nation::OrangeStar, Nation::BlackHole]);
+
+    let oozium = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Oozium, Nation::OrangeStar));
+    let copter = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::BCopter, Nation::BlackHole));
+    let infantry = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::BlackHole));
+    let tank = state.add_unit(Unit::new(Coord::new(3, 0), UnitClass::Tank, Nation::OrangeStar));
+
+    state.start_turn();
+
+    // Only ground units can be engulfed
+    assert_eq!(state.engulf_unit(oozium, copter), Err(RuleError::CannotAttack(oozium)));
+
+    // Too far away
+    assert_eq!(state.engulf_unit(oozium, infantry), Err(RuleError::CannotAttack(oozium)));
+
+    // Only Oozium can engulf
+    assert_eq!(state.engulf_unit(tank, infantry), Err(RuleError::CannotAttack(tank)));
+
+    state.remove_unit(copter);
+    state.unit_mut(infantry).unwrap().coord = Coord::new(1, 0);
+
+    assert!(state.engulf_unit(oozium, infantry).is_ok());
+    assert_eq!(state.engulf_unit(oozium, infantry), Err(RuleError::UnitWaited(oozium)));
+}
+
+#[test]
+fn test_oozium_resistances() {
+    let oozium = Target::Unit(UnitClass::Oozium);
+
+    assert_eq!(UnitClass::Oozium.movement(), 1);
+
+    assert_eq!(base_damage(UnitClass::Infantry, Weapon::Secondary, oozium), Some(20));
+    assert_eq!(base_damage(UnitClass::Neotank, Weapon::Primary, oozium), Some(30));
+    assert_eq!(base_damage(UnitClass::Bomber, Weapon::Primary, oozium), Some(35));
+    assert_eq!(base_damage(UnitClass::Fighter, Weapon::Primary, oozium), None);
+    assert_eq!(base_damage(UnitClass::Missile, Weapon::Primary, oozium), None);
+
+    // Oozium only engulfs, it does not have any weapons
+    assert_eq!(base_damage(UnitClass::Oozium, Weapon::Primary, Target::Unit(UnitClass::Infantry)), None);
+}
@@ -0,0 +1,102 @@
+use rusted_battalions_game_logic::{
+    GameState, Map, Coord, Unit, UnitClass, Nation, TerrainClass, MovementType, Event, RuleError, STRUCTURE_HP,
+};
+
+mod common;
+
+use common::{NoLuck};
+
+
+fn pipe_map() -> Map {
+    let mut map = Map::new(5, 1);
+
+    for tile in map.tiles_mut() {
+        tile.class = TerrainClass::Grass;
+    }
+
+    map.get_mut(&Coord::new(2, 0)).unwrap().class = TerrainClass::Pipeseam { destroyed: false };
+    map.get_mut(&Coord::new(3, 0)).unwrap().class = TerrainClass::Pipeline;
+
+    map
+}
+
+
+#[test]
+fn test_destroy_pipeseam() {
+    let mut state = GameState::new(pipe_map(), &[Nation::OrangeStar]);
+
+    let seam = Coord::new(2, 0);
+
+    let tank = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::MediumTank, Nation::OrangeStar));
+
+    assert_eq!(state.structure_hp(&seam), Some(STRUCTURE_HP));
+    assert_eq!(state.structure_hp(&Coord::new(3, 0)), None);
+
+    state.start_turn();
+
+    let events = state.attack_pipeseam(tank, seam, NoLuck).unwrap();
+
+    assert_eq!(events, vec![Event::PipeseamDamaged { unit: tank, coord: seam, hp: 45 }]);
+    assert_eq!(state.structure_hp(&seam), Some(45));
+
+    let unit = state.unit(tank).unwrap();
+
+    assert!(unit.waited);
+    assert_eq!(unit.ammo, 7);
+
+    assert_eq!(state.attack_pipeseam(tank, seam, NoLuck), Err(RuleError::UnitWaited(tank)));
+
+    state.end_turn();
+
+    let events = state.attack_pipeseam(tank, seam, NoLuck).unwrap();
+
+    assert_eq!(events, vec![
+        Event::PipeseamDamaged { unit: tank, coord: seam, hp: 0 },
+        Event::PipeseamDestroyed { coord: seam },
+    ]);
+
+    let tile = state.map.get(&seam).unwrap();
+
+    assert_eq!(tile.class, TerrainClass::Pipeseam { destroyed: true });
+    assert_eq!(state.structure_hp(&seam), None);
+
+    // Destroyed pipeseams are plain tiles
+    assert_eq!(tile.class.movement_cost(MovementType::Treads), Some(1));
+    assert_eq!(tile.class.movement_cost(MovementType::Tires), Some(2));
+    assert_eq!(tile.class.movement_cost(MovementType::Pipe), None);
+    assert_eq!(tile.class.defense_stars(), 1);
+
+    state.end_turn();
+
+    assert_eq!(state.attack_pipeseam(tank, seam, NoLuck), Err(RuleError::CannotAttack(tank)));
+}
+
+#[test]
+fn test_pipeseam_range() {
+    let mut state = GameState::new(pipe_map(), &[Nation::OrangeStar]);
+
+    let seam = Coord::new(2, 0);
+
+    let artillery = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Artillery, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let fighter = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Fighter, Nation::OrangeStar));
+
+    state.start_turn();
+
+    // Too far away, and fighters cannot attack pipeseams
+    assert_eq!(state.attack_pipeseam(tank, seam, NoLuck), Err(RuleError::CannotAttack(tank)));
+    assert_eq!(state.attack_pipeseam(fighter, seam, NoLuck), Err(RuleError::CannotAttack(fighter)));
+
+    let events = state.attack_pipeseam(artillery, seam, NoLuck).unwrap();
+
+    assert_eq!(events, vec![Event::PipeseamDamaged { unit: artillery, coord: seam, hp: 55 }]);
+
+    // Non-rounded HP is used for the attacker
+    state.remove_unit(fighter);
+    state.unit_mut(tank).unwrap().coord = Coord::new(1, 0);
+    state.unit_mut(tank).unwrap().hp = 50;
+
+    let events = state.attack_pipeseam(tank, seam, NoLuck).unwrap();
+
+    assert_eq!(events, vec![Event::PipeseamDamaged { unit: tank, coord: seam, hp: 48 }]);
+}
@@ -0,0 +1,137 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, UnitClass, Nation, BuildingClass, Event, RuleError, Rules,
+    Co, PlayerCo,
+};
+
+mod common;
+
+use common::{grass_map};
+
+
+#[test]
+fn test_build_lists() {
+    assert!(BuildingClass::Base.build_list().contains(&UnitClass::Infantry));
+    assert!(BuildingClass::Airport.build_list().contains(&UnitClass::BlackBomb));
+    assert!(BuildingClass::Port.build_list().contains(&UnitClass::BlackBoat));
+    assert!(BuildingClass::City.build_list().is_empty());
+
+    // Every buildable unit is only built by 1 facility, Oozium cannot be built
+    for class in UnitClass::ALL {
+        let count = [BuildingClass::Base, BuildingClass::Airport, BuildingClass::Port].iter()
+            .filter(|building| building.build_list().contains(class))
+            .count();
+
+        assert_eq!(count, if *class == UnitClass::Oozium { 0 } else { 1 }, "{:?}", class);
+    }
+}
+
+#[test]
+fn test_build_unit() {
+    let mut map = grass_map(4, 1);
+
+    map.set_building(Coord::new(0, 0), BuildingClass::Base, Some(Nation::OrangeStar));
+    map.set_building(Coord::new(1, 0), BuildingClass::Airport, None);
+    map.set_building(Coord::new(2, 0), BuildingClass::Base, Some(Nation::BlueMoon));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.start_turn();
+
+    state.player_mut(Nation::OrangeStar).unwrap().funds = 8000;
+
+    let base = Coord::new(0, 0);
+
+    // Wrong facility, neutral, and enemy buildings
+    assert_eq!(state.build_unit(base, UnitClass::Fighter), Err(RuleError::CannotBuild(base)));
+    assert_eq!(state.build_unit(Coord::new(1, 0), UnitClass::Fighter), Err(RuleError::CannotBuild(Coord::new(1, 0))));
+    assert_eq!(state.build_unit(Coord::new(2, 0), UnitClass::Tank), Err(RuleError::CannotBuild(Coord::new(2, 0))));
+
+    assert_eq!(state.build_unit(base, UnitClass::MediumTank), Err(RuleError::NotEnoughFunds(Nation::OrangeStar)));
+
+    let events = state.build_unit(base, UnitClass::Tank).unwrap();
+
+    let (tank, unit) = state.unit_at(&base).unwrap();
+
+    assert_eq!(events, vec![Event::Built { unit: tank, coord: base, cost: 7000 }]);
+    assert_eq!(unit.class, UnitClass::Tank);
+    assert_eq!(unit.nation, Nation::OrangeStar);
+    assert!(unit.waited);
+    assert_eq!(state.player(Nation::OrangeStar).unwrap().funds, 1000);
+
+    // The base is occupied
+    assert_eq!(state.build_unit(base, UnitClass::Infantry), Err(RuleError::CannotBuild(base)));
+    assert!(state.build_options(&base).is_empty());
+}
+
+#[test]
+fn test_co_cost() {
+    let mut map = grass_map(2, 1);
+
+    map.set_building(Coord::new(0, 0), BuildingClass::Base, Some(Nation::OrangeStar));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::new(Co::Colin));
+
+    state.start_turn();
+
+    state.player_mut(Nation::OrangeStar).unwrap().funds = 7000;
+
+    let base = Coord::new(0, 0);
+
+    assert!(state.build_options(&base).contains(&(UnitClass::Tank, 5600)));
+
+    state.build_unit(base, UnitClass::Tank).unwrap();
+
+    // The cost is kept after the CO changes
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::new(Co::Kanbei));
+
+    let (_, unit) = state.unit_at(&base).unwrap();
+
+    assert_eq!(unit.build_cost, 5600);
+    assert_eq!(state.build_cost(Nation::OrangeStar, UnitClass::Tank), 8400);
+    assert_eq!(state.player(Nation::OrangeStar).unwrap().funds, 1400);
+}
+
+#[test]
+fn test_banned_and_lab_units() {
+    let mut map = grass_map(2, 1);
+
+    map.set_building(Coord::new(0, 0), BuildingClass::Base, Some(Nation::OrangeStar));
+    map.set_building(Coord::new(1, 0), BuildingClass::Lab, None);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    state.rules = Rules {
+        banned_units: vec![UnitClass::Infantry],
+        lab_units: true,
+        ..Rules::default()
+    };
+
+    state.player_mut(Nation::OrangeStar).unwrap().funds = 50000;
+
+    state.start_turn();
+
+    let base = Coord::new(0, 0);
+
+    let options: Vec<UnitClass> = state.build_options(&base).into_iter().map(|(class, _)| class).collect();
+
+    assert!(options.contains(&UnitClass::Mech));
+    assert!(!options.contains(&UnitClass::Infantry));
+    assert!(!options.contains(&UnitClass::Neotank));
+
+    assert_eq!(state.build_unit(base, UnitClass::Infantry), Err(RuleError::CannotBuild(base)));
+    assert_eq!(state.build_unit(base, UnitClass::Neotank), Err(RuleError::CannotBuild(base)));
+
+    // Owning a Lab unlocks the unit
+    state.map.get_mut(&Coord::new(1, 0)).unwrap().owner = Some(Nation::OrangeStar);
+
+    assert!(state.build_unit(base, UnitClass::Neotank).is_ok());
+
+    // Built units can act on the next turn
+    state.end_turn();
+
+    let (_, unit) = state.unit_at(&base).unwrap();
+
+    assert!(!unit.waited);
+}
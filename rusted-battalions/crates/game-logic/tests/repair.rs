@@ -0,0 +1,106 @@
+use rusted_battalions_game_logic::{
+    GameState, Map, Coord, Unit, UnitClass, Nation, TerrainClass, BuildingClass, Event,
+    repair_cost, affordable_repair,
+};
+
+
+fn city_map(width: u32) -> Map {
+    let mut map = Map::new(width, 1);
+
+    for tile in map.tiles_mut() {
+        tile.class = TerrainClass::Grass;
+        tile.building = Some(BuildingClass::City);
+        tile.owner = Some(Nation::OrangeStar);
+    }
+
+    map
+}
+
+fn repairs(events: &[Event]) -> Vec<&Event> {
+    events.iter().filter(|event| matches!(event, Event::Repaired { .. })).collect()
+}
+
+
+#[test]
+fn test_repair_cost() {
+    let tank = Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar);
+
+    assert_eq!(repair_cost(&tank, 20), 1400);
+    assert_eq!(repair_cost(&tank, 7), 490);
+    assert_eq!(repair_cost(&tank, 0), 0);
+
+    // Hachi's Merchant Union does not change the cost of existing units
+    let hachi = Unit { build_cost: 3500, ..tank.clone() };
+
+    assert_eq!(repair_cost(&hachi, 20), 700);
+
+    // Repairs are rounded up
+    let odd = Unit { build_cost: 1050, ..tank.clone() };
+
+    assert_eq!(repair_cost(&odd, 1), 11);
+}
+
+#[test]
+fn test_affordable_repair() {
+    let tank = Unit { hp: 50, ..Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar) };
+
+    assert_eq!(affordable_repair(&tank, 10000), 20);
+    assert_eq!(affordable_repair(&tank, 1400), 20);
+    assert_eq!(affordable_repair(&tank, 1399), 19);
+    assert_eq!(affordable_repair(&tank, 490), 7);
+    assert_eq!(affordable_repair(&tank, 69), 0);
+
+    // Uses the non-rounded HP
+    let tank = Unit { hp: 93, ..tank };
+
+    assert_eq!(affordable_repair(&tank, 10000), 7);
+}
+
+#[test]
+fn test_partial_repair() {
+    let mut state = GameState::new(city_map(1), &[Nation::OrangeStar]);
+
+    let tank = state.add_unit(Unit { hp: 50, ..Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar) });
+
+    // The city gives 1000 funds before the repairs
+    let events = state.start_turn();
+
+    assert_eq!(repairs(&events), vec![&Event::Repaired { unit: tank, hp: 14, cost: 980 }]);
+    assert_eq!(state.unit(tank).unwrap().hp, 64);
+    assert_eq!(state.current_player().funds, 20);
+}
+
+#[test]
+fn test_repair_order() {
+    let mut state = GameState::new(city_map(3), &[Nation::OrangeStar]);
+
+    let first = state.add_unit(Unit { hp: 10, ..Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::OrangeStar) });
+    let second = state.add_unit(Unit { hp: 10, ..Unit::new(Coord::new(0, 0), UnitClass::MegaTank, Nation::OrangeStar) });
+    let third = state.add_unit(Unit { hp: 10, ..Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::OrangeStar) });
+
+    // 3 cities gives 3000 funds
+    let events = state.start_turn();
+
+    // Older units are repaired first, so the last infantry is not repaired
+    assert_eq!(repairs(&events), vec![
+        &Event::Repaired { unit: first, hp: 20, cost: 200 },
+        &Event::Repaired { unit: second, hp: 10, cost: 2800 },
+    ]);
+
+    assert_eq!(state.current_player().funds, 0);
+    assert_eq!(state.unit(third).unwrap().hp, 10);
+
+    let events = state.end_turn();
+
+    assert_eq!(repairs(&events), vec![
+        &Event::Repaired { unit: first, hp: 20, cost: 200 },
+        &Event::Repaired { unit: second, hp: 10, cost: 2800 },
+    ]);
+
+    // Units are still resupplied even if they cannot be repaired
+    state.unit_mut(third).unwrap().fuel = 0;
+
+    let events = state.end_turn();
+
+    assert!(events.contains(&Event::Resupplied { unit: third }));
+}
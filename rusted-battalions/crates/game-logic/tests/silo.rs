@@ -0,0 +1,82 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, BuildingClass, Event, RuleError,
+};
+
+mod common;
+
+use common::{grass_map, set_building};
+
+
+#[test]
+fn test_launch_missile() {
+    let mut map = grass_map(9, 9);
+
+    set_building(&mut map, 0, 0, BuildingClass::MissileSilo, None);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let infantry = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    let center = state.add_unit(Unit::new(Coord::new(5, 5), UnitClass::Tank, Nation::BlueMoon));
+    let edge = state.add_unit(Unit::new(Coord::new(5, 7), UnitClass::Tank, Nation::BlueMoon));
+    let weak = state.add_unit(Unit { hp: 20, ..Unit::new(Coord::new(4, 4), UnitClass::Tank, Nation::BlueMoon) });
+    let dying = state.add_unit(Unit { hp: 1, ..Unit::new(Coord::new(6, 5), UnitClass::Tank, Nation::BlueMoon) });
+    let outside = state.add_unit(Unit::new(Coord::new(7, 7), UnitClass::Tank, Nation::BlueMoon));
+    let own = state.add_unit(Unit::new(Coord::new(3, 5), UnitClass::Tank, Nation::OrangeStar));
+
+    state.start_turn();
+
+    let events = state.launch_missile(infantry, Coord::new(5, 5)).unwrap();
+
+    assert_eq!(events, vec![
+        Event::MissileLaunched { unit: infantry, silo: Coord::new(0, 0), target: Coord::new(5, 5) },
+        Event::MissileHit { unit: center, damage: 30 },
+        Event::MissileHit { unit: edge, damage: 30 },
+        Event::MissileHit { unit: weak, damage: 19 },
+        Event::MissileHit { unit: own, damage: 30 },
+    ]);
+
+    // Missiles never destroy units
+    assert_eq!(state.unit(weak).unwrap().hp, 1);
+    assert_eq!(state.unit(dying).unwrap().hp, 1);
+    assert_eq!(state.unit(outside).unwrap().hp, 100);
+    assert_eq!(state.unit(center).unwrap().hp, 70);
+
+    assert!(state.unit(infantry).unwrap().waited);
+
+    assert_eq!(state.map.get(&Coord::new(0, 0)).unwrap().building, Some(BuildingClass::MissileSiloEmpty));
+
+    state.end_turn();
+    state.end_turn();
+
+    // The silo is empty
+    assert_eq!(state.launch_missile(infantry, Coord::new(5, 5)), Err(RuleError::CannotLaunch(infantry)));
+}
+
+#[test]
+fn test_cannot_launch() {
+    let mut map = grass_map(3, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::MissileSilo, None);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let mech = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Mech, Nation::OrangeStar));
+
+    state.start_turn();
+
+    // Only infantry and mechs can launch
+    assert_eq!(state.launch_missile(tank, Coord::new(2, 0)), Err(RuleError::CannotLaunch(tank)));
+
+    // Not on a silo
+    assert_eq!(state.launch_missile(mech, Coord::new(2, 0)), Err(RuleError::CannotLaunch(mech)));
+
+    state.remove_unit(tank);
+    state.unit_mut(mech).unwrap().coord = Coord::new(0, 0);
+
+    // Outside of the map
+    assert_eq!(state.launch_missile(mech, Coord::new(5, 0)), Err(RuleError::CannotLaunch(mech)));
+
+    assert!(state.launch_missile(mech, Coord::new(2, 0)).is_ok());
+}
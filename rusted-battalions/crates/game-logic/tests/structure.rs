@@ -0,0 +1,255 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, BuildingClass, Direction, MovementType,
+    Event, RuleError, Rules, STRUCTURE_HP,
+};
+
+mod common;
+
+use common::{NoLuck, grass_map};
+
+
+fn structure_state(width: u32, height: u32, origin: Coord, building: BuildingClass) -> GameState {
+    let mut map = grass_map(width, height);
+
+    map.set_building(origin, building, Some(Nation::BlackHole));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlackHole]);
+
+    state.start_turn();
+
+    state
+}
+
+/// Starts Black Hole's next turn, returning only the events from the structures.
+fn black_hole_turn(state: &mut GameState) -> Vec<Event> {
+    if state.current_player().nation == Nation::BlackHole {
+        state.end_turn();
+    }
+
+    state.end_turn().into_iter()
+        .filter(|event| matches!(event, Event::StructureFired { .. } | Event::StructureHit { .. } | Event::Healed { .. }))
+        .collect()
+}
+
+
+#[test]
+fn test_footprint() {
+    let mut map = grass_map(5, 5);
+
+    map.set_building(Coord::new(1, 1), BuildingClass::Fortress, Some(Nation::BlackHole));
+
+    for tile in map.tiles() {
+        let covered = tile.coord.x >= 1 && tile.coord.x <= 3 && tile.coord.y >= 1 && tile.coord.y <= 3;
+
+        assert_eq!(tile.building.is_some(), covered);
+
+        if covered {
+            assert_eq!(tile.origin, Coord::new(1, 1));
+            assert_eq!(tile.owner, Some(Nation::BlackHole));
+
+            // Structures block every unit
+            assert_eq!(MovementType::Treads.cost(tile), None);
+            assert_eq!(MovementType::Air.cost(tile), None);
+        }
+    }
+
+    assert!(!BuildingClass::Fortress.can_capture());
+    assert!(BuildingClass::Fortress.can_have_nation());
+
+    map.remove_building(&Coord::new(3, 2));
+
+    assert!(map.tiles().all(|tile| tile.building.is_none() && tile.owner.is_none() && tile.origin == tile.coord));
+}
+
+#[test]
+fn test_laser() {
+    let mut state = structure_state(5, 5, Coord::new(2, 2), BuildingClass::Laser);
+
+    let row = state.add_unit(Unit::new(Coord::new(0, 2), UnitClass::Tank, Nation::OrangeStar));
+    let column = state.add_unit(Unit { hp: 30, ..Unit::new(Coord::new(2, 4), UnitClass::Tank, Nation::OrangeStar) });
+    let _diagonal = state.add_unit(Unit::new(Coord::new(3, 3), UnitClass::Tank, Nation::OrangeStar));
+    let _own = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Tank, Nation::BlackHole));
+
+    assert_eq!(black_hole_turn(&mut state), vec![
+        Event::StructureFired { coord: Coord::new(2, 2), target: Coord::new(2, 2) },
+        Event::StructureHit { unit: row, damage: 50 },
+        Event::StructureHit { unit: column, damage: 29 },
+    ]);
+
+    assert_eq!(state.unit(column).unwrap().hp, 1);
+}
+
+#[test]
+fn test_cannon() {
+    let building = BuildingClass::Minicannon { direction: Direction::Right, grass: false };
+
+    let mut state = structure_state(6, 5, Coord::new(1, 2), building);
+
+    let _behind = state.add_unit(Unit::new(Coord::new(0, 2), UnitClass::MegaTank, Nation::OrangeStar));
+    let _outside = state.add_unit(Unit::new(Coord::new(2, 1), UnitClass::MegaTank, Nation::OrangeStar));
+    let _far = state.add_unit(Unit::new(Coord::new(5, 2), UnitClass::MegaTank, Nation::OrangeStar));
+    let infantry = state.add_unit(Unit::new(Coord::new(2, 2), UnitClass::Infantry, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(4, 0), UnitClass::Tank, Nation::OrangeStar));
+
+    // The most valuable unit in the cone is hit
+    assert_eq!(black_hole_turn(&mut state), vec![
+        Event::StructureFired { coord: Coord::new(1, 2), target: Coord::new(4, 0) },
+        Event::StructureHit { unit: tank, damage: 30 },
+    ]);
+
+    state.remove_unit(tank);
+
+    assert_eq!(black_hole_turn(&mut state), vec![
+        Event::StructureFired { coord: Coord::new(1, 2), target: Coord::new(2, 2) },
+        Event::StructureHit { unit: infantry, damage: 30 },
+    ]);
+}
+
+#[test]
+fn test_heal() {
+    let mut state = structure_state(9, 1, Coord::new(0, 0), BuildingClass::BlackCrystal);
+
+    let near = state.add_unit(Unit { hp: 50, ..Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlackHole) });
+    let far = state.add_unit(Unit { hp: 95, ..Unit::new(Coord::new(8, 0), UnitClass::Tank, Nation::BlackHole) });
+    let _enemy = state.add_unit(Unit { hp: 50, ..Unit::new(Coord::new(4, 0), UnitClass::Tank, Nation::OrangeStar) });
+
+    assert_eq!(black_hole_turn(&mut state), vec![
+        Event::Healed { unit: near, hp: 20 },
+        Event::Healed { unit: far, hp: 5 },
+    ]);
+
+    // Obelisks only heal nearby units
+    let mut state = structure_state(9, 2, Coord::new(0, 0), BuildingClass::BlackObelisk);
+
+    let near = state.add_unit(Unit { hp: 50, ..Unit::new(Coord::new(4, 1), UnitClass::Tank, Nation::BlackHole) });
+    let _far = state.add_unit(Unit { hp: 50, ..Unit::new(Coord::new(5, 1), UnitClass::Tank, Nation::BlackHole) });
+
+    assert_eq!(black_hole_turn(&mut state), vec![Event::Healed { unit: near, hp: 20 }]);
+}
+
+#[test]
+fn test_missile() {
+    let mut state = structure_state(12, 3, Coord::new(0, 0), BuildingClass::BlackOnyx);
+
+    let alone = state.add_unit(Unit::new(Coord::new(4, 1), UnitClass::Tank, Nation::OrangeStar));
+    let group1 = state.add_unit(Unit::new(Coord::new(9, 1), UnitClass::Tank, Nation::OrangeStar));
+    let group2 = state.add_unit(Unit::new(Coord::new(11, 1), UnitClass::Tank, Nation::OrangeStar));
+
+    assert_eq!(black_hole_turn(&mut state), vec![
+        Event::StructureFired { coord: Coord::new(0, 0), target: Coord::new(10, 0) },
+        Event::StructureHit { unit: group1, damage: 30 },
+        Event::StructureHit { unit: group2, damage: 30 },
+    ]);
+
+    assert_eq!(state.unit(alone).unwrap().hp, 100);
+}
+
+#[test]
+fn test_volcano() {
+    let mut state = structure_state(20, 20, Coord::new(8, 8), BuildingClass::Volcano);
+
+    let events = black_hole_turn(&mut state);
+
+    let targets: Vec<Coord> = events.iter().map(|event| match event {
+        Event::StructureFired { coord, target } => {
+            assert_eq!(*coord, Coord::new(8, 8));
+            *target
+        },
+        _ => panic!("Invalid event {:?}", event),
+    }).collect();
+
+    assert_eq!(targets.len(), 6);
+
+    for target in targets.iter() {
+        assert!(target.x <= 18 && target.y <= 18);
+    }
+
+    // The eruptions are deterministic
+    let mut other = structure_state(20, 20, Coord::new(8, 8), BuildingClass::Volcano);
+
+    assert_eq!(black_hole_turn(&mut other), events);
+
+    // Matches with a different seed have different eruptions
+    let mut other = structure_state(20, 20, Coord::new(8, 8), BuildingClass::Volcano);
+
+    other.rules.seed = 1;
+
+    assert_ne!(black_hole_turn(&mut other), events);
+
+    // Volcanoes cannot be destroyed
+    assert_eq!(state.structure_hp(&Coord::new(9, 9)), None);
+}
+
+#[test]
+fn test_attack_structure() {
+    let mut state = structure_state(5, 3, Coord::new(2, 0), BuildingClass::BlackCannon { direction: Direction::Right });
+
+    let tank = state.add_unit(Unit::new(Coord::new(1, 1), UnitClass::MediumTank, Nation::OrangeStar));
+    let own = state.add_unit(Unit::new(Coord::new(1, 2), UnitClass::MediumTank, Nation::BlackHole));
+
+    assert_eq!(state.structure_hp(&Coord::new(4, 2)), Some(STRUCTURE_HP));
+
+    assert_eq!(state.attack_pipeseam(tank, Coord::new(2, 1), NoLuck), Err(RuleError::CannotAttack(tank)));
+
+    let events = state.attack_structure(tank, Coord::new(2, 1), NoLuck).unwrap();
+
+    assert_eq!(events, vec![Event::StructureDamaged { unit: tank, coord: Coord::new(2, 0), hp: 45 }]);
+    assert_eq!(state.structure_hp(&Coord::new(4, 2)), Some(45));
+
+    state.end_turn();
+
+    // Structures cannot be attacked by their owner
+    assert_eq!(state.attack_structure(own, Coord::new(2, 2), NoLuck), Err(RuleError::CannotAttack(own)));
+
+    state.end_turn();
+
+    let events = state.attack_structure(tank, Coord::new(2, 1), NoLuck).unwrap();
+
+    assert_eq!(events, vec![
+        Event::StructureDamaged { unit: tank, coord: Coord::new(2, 0), hp: 0 },
+        Event::StructureDestroyed { coord: Coord::new(2, 0) },
+    ]);
+
+    assert!(state.map.tiles().all(|tile| tile.building.is_none()));
+    assert_eq!(state.structure_hp(&Coord::new(2, 0)), None);
+}
+
+#[test]
+fn test_fog_structure() {
+    let mut state = structure_state(8, 3, Coord::new(2, 0), BuildingClass::BlackCannon { direction: Direction::Right });
+
+    state.rules = Rules { fog: true, ..Rules::default() };
+
+    let rocket = state.add_unit(Unit::new(Coord::new(7, 1), UnitClass::Rocket, Nation::OrangeStar));
+
+    assert_eq!(state.attack_structure(rocket, Coord::new(4, 1), NoLuck), Err(RuleError::TargetNotVisible(rocket)));
+
+    state.rules.fog = false;
+
+    assert!(state.attack_structure(rocket, Coord::new(4, 1), NoLuck).is_ok());
+}
+
+#[test]
+fn test_team_structure() {
+    let mut map = grass_map(5, 3);
+
+    map.set_building(Coord::new(2, 0), BuildingClass::BlackCannon { direction: Direction::Right }, Some(Nation::BlackHole));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlackHole, Nation::BlueMoon]);
+
+    state.player_mut(Nation::OrangeStar).unwrap().team = Some(0);
+    state.player_mut(Nation::BlackHole).unwrap().team = Some(0);
+
+    let ally = state.add_unit(Unit::new(Coord::new(1, 1), UnitClass::MediumTank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(1, 2), UnitClass::MediumTank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    // Teammates cannot attack each other's structures
+    assert_eq!(state.attack_structure(ally, Coord::new(2, 1), NoLuck), Err(RuleError::CannotAttack(ally)));
+
+    state.end_turn();
+
+    assert_eq!(state.current_player().nation, Nation::BlueMoon);
+    assert!(state.attack_structure(enemy, Coord::new(2, 2), NoLuck).is_ok());
+}
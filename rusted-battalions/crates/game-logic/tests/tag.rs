@@ -0,0 +1,206 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, Event, RuleError,
+    Co, PlayerCo, PowerKind, Rules, CoMode, STAR_VALUE,
+};
+
+mod common;
+
+use common::{grass_map};
+
+
+fn tag_state(co: Co, partner: Co) -> GameState {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.rules = Rules { co_mode: CoMode::Tag, ..Rules::default() };
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::tag(co, partner));
+
+    state
+}
+
+fn co(state: &GameState, nation: Nation) -> &PlayerCo {
+    state.player(nation).unwrap().co.as_ref().unwrap()
+}
+
+
+#[test]
+fn test_switch_co() {
+    let mut state = tag_state(Co::Andy, Co::Max);
+
+    state.start_turn();
+
+    let events = state.switch_co().unwrap();
+
+    assert_eq!(events, vec![
+        Event::CoSwitched { nation: Nation::OrangeStar, co: Co::Max },
+        Event::TurnStarted { nation: Nation::BlueMoon, day: 1 },
+    ]);
+
+    let co = co(&state, Nation::OrangeStar);
+
+    assert_eq!(co.co, Co::Max);
+    assert_eq!(co.partner, Some(Co::Andy));
+
+    // Blue Moon does not have a partner
+    state.player_mut(Nation::BlueMoon).unwrap().co = Some(PlayerCo::new(Co::Kanbei));
+
+    assert_eq!(state.switch_co(), Err(RuleError::CannotSwitchCo(Nation::BlueMoon)));
+}
+
+#[test]
+fn test_single_mode() {
+    let mut state = tag_state(Co::Andy, Co::Max);
+
+    state.rules = Rules { co_mode: CoMode::Single, ..Rules::default() };
+    state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap().meter = 12 * STAR_VALUE;
+
+    state.start_turn();
+
+    assert_eq!(state.switch_co(), Err(RuleError::CannotSwitchCo(Nation::OrangeStar)));
+    assert_eq!(state.activate_power(PowerKind::Tag), Err(RuleError::CannotActivatePower(Nation::OrangeStar)));
+
+    // Tag powers need a partner
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar]);
+
+    state.rules = Rules { co_mode: CoMode::Tag, ..Rules::default() };
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo { meter: 12 * STAR_VALUE, ..PlayerCo::new(Co::Andy) });
+
+    state.start_turn();
+
+    assert_eq!(state.activate_power(PowerKind::Tag), Err(RuleError::CannotActivatePower(Nation::OrangeStar)));
+}
+
+#[test]
+fn test_shared_meter() {
+    let mut co = PlayerCo::tag(Co::Andy, Co::Kanbei);
+
+    // The meter holds the Super CO Powers of both COs
+    assert_eq!(co.power_cost(PowerKind::Tag), 13 * STAR_VALUE);
+    assert_eq!(co.max_meter(), 13 * STAR_VALUE);
+
+    co.charge(0, 6 * STAR_VALUE);
+
+    assert!(co.can_activate(PowerKind::SuperPower));
+    assert!(!co.can_activate(PowerKind::Tag));
+
+    // Switching keeps the meter
+    std::mem::swap(&mut co.co, co.partner.as_mut().unwrap());
+
+    assert_eq!(co.meter, 6 * STAR_VALUE);
+    assert!(!co.can_activate(PowerKind::SuperPower));
+
+    co.charge(0, 100 * STAR_VALUE);
+
+    assert_eq!(co.meter, 13 * STAR_VALUE);
+    assert!(co.can_activate(PowerKind::Tag));
+}
+
+#[test]
+fn test_dual_strike() {
+    let mut state = tag_state(Co::Andy, Co::Max);
+
+    let tank = state.add_unit(Unit { hp: 30, ..Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar) });
+
+    state.start_turn();
+
+    state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap().meter = 12 * STAR_VALUE;
+
+    let events = state.activate_power(PowerKind::Tag).unwrap();
+
+    assert_eq!(events, vec![
+        Event::PowerActivated { nation: Nation::OrangeStar, co: Co::Andy, power: PowerKind::Tag },
+        Event::Healed { unit: tank, hp: 50 },
+    ]);
+
+    assert_eq!(co(&state, Nation::OrangeStar).meter, 0);
+
+    // Hyper Upgrade combined with Max's day-to-day bonus
+    assert_eq!(state.attacker(tank).unwrap().co_bonus, 30);
+
+    state.unit_mut(tank).unwrap().waited = true;
+
+    let events = state.end_turn();
+
+    // Same player and same day, without any upkeep
+    assert_eq!(events, vec![
+        Event::CoSwitched { nation: Nation::OrangeStar, co: Co::Max },
+        Event::TurnStarted { nation: Nation::OrangeStar, day: 1 },
+    ]);
+
+    assert_eq!(state.current_player().nation, Nation::OrangeStar);
+    assert!(!state.unit(tank).unwrap().waited);
+
+    // Max Blast, Andy does not have any day-to-day modifiers
+    let attacker = state.attacker(tank).unwrap();
+
+    assert_eq!(attacker.co_bonus, 50);
+    assert!(attacker.co_power);
+    assert_eq!(co(&state, Nation::OrangeStar).movement(UnitClass::Tank), 8);
+
+    let events = state.end_turn();
+
+    assert_eq!(events, vec![Event::TurnStarted { nation: Nation::BlueMoon, day: 1 }]);
+
+    // The power lasts until the player's next turn
+    assert_eq!(co(&state, Nation::OrangeStar).power, Some(PowerKind::Tag));
+
+    state.end_turn();
+
+    let co = co(&state, Nation::OrangeStar);
+
+    assert_eq!(co.power, None);
+    assert!(!co.tag_turn);
+    assert_eq!(co.co, Co::Max);
+    assert_eq!(state.day(), 2);
+}
+
+#[test]
+fn test_resign_dual_strike() {
+    let mut state = GameState::new(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon, Nation::GreenEarth]);
+
+    state.rules = Rules { co_mode: CoMode::Tag, ..Rules::default() };
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::tag(Co::Andy, Co::Max));
+
+    state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::BlueMoon));
+    state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Tank, Nation::GreenEarth));
+
+    state.start_turn();
+
+    state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap().meter = 12 * STAR_VALUE;
+
+    state.activate_power(PowerKind::Tag).unwrap();
+
+    // A defeated player does not get the second turn
+    let events = state.resign();
+
+    assert!(events.contains(&Event::TurnStarted { nation: Nation::BlueMoon, day: 1 }));
+    assert!(!events.iter().any(|event| matches!(event, Event::CoSwitched { .. })));
+    assert_eq!(state.current_player().nation, Nation::BlueMoon);
+}
+
+#[test]
+fn test_combined_effects() {
+    let mut state = tag_state(Co::Grimm, Co::Kanbei);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+
+    state.start_turn();
+
+    // Only the active CO's day-to-day modifiers are used normally
+    assert_eq!(state.attacker(tank).unwrap().co_bonus, 30);
+    assert_eq!(state.defender(tank, UnitClass::Tank).unwrap().co_penalty, 20);
+
+    let co = state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap();
+
+    co.meter = co.max_meter();
+
+    state.activate_power(PowerKind::Tag).unwrap();
+
+    // Grimm's +30% and Kanbei's +20% are combined with Haymaker
+    assert_eq!(state.attacker(tank).unwrap().co_bonus, 100);
+
+    let defender = state.defender(tank, UnitClass::Tank).unwrap();
+
+    assert_eq!(defender.co_bonus, 0);
+    assert_eq!(defender.co_penalty, 0);
+}
@@ -0,0 +1,116 @@
+use rusted_battalions_game_logic::{
+    Map, Coord, UnitClass, TerrainClass, BuildingClass, Orientation, Target, Defender,
+};
+
+
+#[test]
+fn test_terrain_stars() {
+    assert_eq!(TerrainClass::Empty.defense_stars(), 0);
+    assert_eq!(TerrainClass::Grass.defense_stars(), 1);
+    assert_eq!(TerrainClass::Road { ruins: false }.defense_stars(), 0);
+    assert_eq!(TerrainClass::Road { ruins: true }.defense_stars(), 1);
+    assert_eq!(TerrainClass::Bridge { orientation: Orientation::Vertical }.defense_stars(), 0);
+    assert_eq!(TerrainClass::Forest.defense_stars(), 2);
+    assert_eq!(TerrainClass::Mountain { variant: 2 }.defense_stars(), 4);
+    assert_eq!(TerrainClass::River.defense_stars(), 0);
+    assert_eq!(TerrainClass::Ocean.defense_stars(), 0);
+    assert_eq!(TerrainClass::Shoal.defense_stars(), 0);
+    assert_eq!(TerrainClass::Reef.defense_stars(), 1);
+}
+
+#[test]
+fn test_building_stars() {
+    for class in BuildingClass::ALL {
+        let stars = class.defense_stars();
+
+        if class.is_hq() {
+            assert_eq!(stars, 4);
+
+        } else if class.is_structure() {
+            assert_eq!(stars, 0);
+
+        } else {
+            assert_eq!(stars, 3);
+        }
+    }
+}
+
+#[test]
+fn test_tile_stars() {
+    let mut map = Map::new(2, 1);
+
+    map.get_mut(&Coord::new(0, 0)).unwrap().class = TerrainClass::Grass;
+    map.get_mut(&Coord::new(0, 0)).unwrap().building = Some(BuildingClass::City);
+    map.get_mut(&Coord::new(1, 0)).unwrap().class = TerrainClass::Forest;
+
+    let city = map.get(&Coord::new(0, 0)).unwrap();
+    let forest = map.get(&Coord::new(1, 0)).unwrap();
+
+    assert_eq!(city.defense_stars(UnitClass::Infantry), 3);
+    assert_eq!(forest.defense_stars(UnitClass::Tank), 2);
+
+    // Air units never get terrain stars
+    assert_eq!(city.defense_stars(UnitClass::BCopter), 0);
+    assert_eq!(forest.defense_stars(UnitClass::Fighter), 0);
+}
+
+#[test]
+fn test_non_rounded_hp() {
+    let defender = Defender {
+        hp: 100,
+        terrain_stars: TerrainClass::Mountain { variant: 0 }.defense_stars(),
+        ..Defender::new(Target::Unit(UnitClass::Infantry))
+    };
+
+    assert_eq!(defender.terrain_defense(), 400);
+
+    // Advance Wars would round 55 HP up to 6, giving +24%
+    let defender = Defender {
+        hp: 55,
+        ..defender
+    };
+
+    assert_eq!(defender.terrain_defense(), 220);
+}
+
+#[test]
+fn test_income() {
+    assert_eq!(BuildingClass::HQ3.income(), 1000);
+    assert_eq!(BuildingClass::City.income(), 1000);
+    assert_eq!(BuildingClass::Base.income(), 1000);
+    assert_eq!(BuildingClass::Airport.income(), 1000);
+    assert_eq!(BuildingClass::Port.income(), 1000);
+    assert_eq!(BuildingClass::ComTower.income(), 0);
+    assert_eq!(BuildingClass::Lab.income(), 0);
+    assert_eq!(BuildingClass::MissileSilo.income(), 0);
+}
+
+#[test]
+fn test_capture() {
+    assert!(BuildingClass::HQ1.can_capture());
+    assert!(BuildingClass::City.can_capture());
+    assert!(BuildingClass::Lab.can_capture());
+    assert!(!BuildingClass::MissileSilo.can_capture());
+    assert!(!BuildingClass::MissileSiloEmpty.can_capture());
+}
+
+#[test]
+fn test_repairs() {
+    assert!(BuildingClass::City.repairs(UnitClass::Infantry));
+    assert!(BuildingClass::HQ2.repairs(UnitClass::MegaTank));
+    assert!(BuildingClass::Base.repairs(UnitClass::Recon));
+    assert!(!BuildingClass::City.repairs(UnitClass::Fighter));
+
+    assert!(BuildingClass::Airport.repairs(UnitClass::TCopter));
+    assert!(!BuildingClass::Airport.repairs(UnitClass::Tank));
+
+    assert!(BuildingClass::Port.repairs(UnitClass::Lander));
+    assert!(BuildingClass::Port.repairs(UnitClass::Submarine));
+    assert!(!BuildingClass::Port.repairs(UnitClass::Infantry));
+
+    for class in UnitClass::ALL {
+        assert!(!BuildingClass::ComTower.repairs(*class));
+        assert!(!BuildingClass::Lab.repairs(*class));
+        assert!(!BuildingClass::MissileSilo.repairs(*class));
+    }
+}
@@ -0,0 +1,260 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, TerrainClass, BuildingClass, Event, RuleError,
+    Vision, Co, PlayerCo, can_unload_from,
+};
+
+mod common;
+
+use common::{NoLuck, grass_map, set, path};
+
+
+#[test]
+fn test_capacity() {
+    assert_eq!(UnitClass::APC.cargo_capacity(), 1);
+    assert_eq!(UnitClass::TCopter.cargo_capacity(), 1);
+    assert_eq!(UnitClass::Lander.cargo_capacity(), 2);
+    assert_eq!(UnitClass::Cruiser.cargo_capacity(), 2);
+    assert_eq!(UnitClass::Carrier.cargo_capacity(), 2);
+    assert_eq!(UnitClass::BlackBoat.cargo_capacity(), 2);
+    assert_eq!(UnitClass::Tank.cargo_capacity(), 0);
+
+    for class in UnitClass::ALL {
+        if class.cargo_capacity() == 0 {
+            for cargo in UnitClass::ALL {
+                assert!(!class.can_carry(*cargo));
+            }
+        }
+    }
+
+    assert!(UnitClass::APC.can_carry(UnitClass::Mech));
+    assert!(!UnitClass::APC.can_carry(UnitClass::Tank));
+    assert!(UnitClass::Lander.can_carry(UnitClass::MegaTank));
+    assert!(UnitClass::Lander.can_carry(UnitClass::APC));
+    assert!(!UnitClass::Lander.can_carry(UnitClass::Piperunner));
+    assert!(!UnitClass::Lander.can_carry(UnitClass::BCopter));
+    assert!(UnitClass::Cruiser.can_carry(UnitClass::BCopter));
+    assert!(!UnitClass::Cruiser.can_carry(UnitClass::Fighter));
+    assert!(UnitClass::Carrier.can_carry(UnitClass::Fighter));
+    assert!(!UnitClass::Carrier.can_carry(UnitClass::Infantry));
+    assert!(UnitClass::BlackBoat.can_carry(UnitClass::Infantry));
+}
+
+#[test]
+fn test_unload_terrain() {
+    let mut map = grass_map(3, 1);
+
+    set(&mut map, 0, 0, TerrainClass::Ocean);
+    set(&mut map, 1, 0, TerrainClass::Shoal);
+    map.get_mut(&Coord::new(2, 0)).unwrap().building = Some(BuildingClass::Port);
+
+    let ocean = map.get(&Coord::new(0, 0)).unwrap();
+    let shoal = map.get(&Coord::new(1, 0)).unwrap();
+    let port = map.get(&Coord::new(2, 0)).unwrap();
+
+    assert!(!can_unload_from(UnitClass::Lander, ocean));
+    assert!(can_unload_from(UnitClass::Lander, shoal));
+    assert!(can_unload_from(UnitClass::Lander, port));
+    assert!(!can_unload_from(UnitClass::BlackBoat, ocean));
+    assert!(can_unload_from(UnitClass::Cruiser, ocean));
+    assert!(can_unload_from(UnitClass::Carrier, ocean));
+}
+
+#[test]
+fn test_transport_boosting() {
+    let map = grass_map(5, 1);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    let apc = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::APC, Nation::OrangeStar));
+    let first = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let second = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Mech, Nation::OrangeStar));
+
+    state.start_turn();
+
+    // Moving onto the transport loads the unit
+    assert_eq!(state.move_along(first, &path(&[(0, 0), (1, 0)])).unwrap(), vec![
+        Event::Moved { unit: first, path: path(&[(0, 0), (1, 0)]) },
+        Event::Loaded { unit: first, transport: apc },
+    ]);
+
+    assert_eq!(state.unit(first).unwrap().coord, Coord::new(1, 0));
+    assert_eq!(state.unit(first).unwrap().transport, Some(apc));
+    assert_eq!(state.unit(apc).unwrap().cargo, vec![first]);
+    assert_eq!(state.unit_at(&Coord::new(1, 0)).unwrap().0, apc);
+
+    // The APC is full
+    assert_eq!(state.move_along(second, &path(&[(2, 0), (1, 0)])), Err(RuleError::CannotMove(second)));
+    assert!(!state.reachable(second).unwrap().destinations().any(|coord| coord == Coord::new(1, 0)));
+
+    state.move_unit(apc, Coord::new(3, 0)).unwrap();
+    assert_eq!(state.unit(first).unwrap().coord, Coord::new(3, 0));
+
+    state.unload_unit(apc, first, Coord::new(4, 0)).unwrap();
+
+    assert_eq!(state.unit(first).unwrap().coord, Coord::new(4, 0));
+    assert!(state.unit(apc).unwrap().cargo.is_empty());
+
+    // The transport can load and unload again in the same turn
+    state.move_unit(apc, Coord::new(1, 0)).unwrap();
+    state.move_along(second, &path(&[(2, 0), (1, 0)])).unwrap();
+    state.move_unit(apc, Coord::new(0, 0)).unwrap();
+    state.unload_unit(apc, second, Coord::new(1, 0)).unwrap();
+
+    assert_eq!(state.unit(second).unwrap().coord, Coord::new(1, 0));
+
+    // Unloaded units cannot act
+    assert_eq!(state.load_unit(second, apc), Err(RuleError::UnitWaited(second)));
+}
+
+#[test]
+fn test_cannot_load() {
+    let map = grass_map(5, 1);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let apc = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::APC, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let near = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let far = state.add_unit(Unit::new(Coord::new(3, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let enemy_apc = state.add_unit(Unit::new(Coord::new(4, 0), UnitClass::APC, Nation::BlueMoon));
+
+    state.start_turn();
+
+    assert_eq!(state.load_unit(tank, apc), Err(RuleError::CannotLoad(tank)));
+    assert_eq!(state.move_along(tank, &path(&[(0, 0), (1, 0)])), Err(RuleError::CannotMove(tank)));
+
+    // Units must be on the transport's tile
+    assert_eq!(state.load_unit(near, apc), Err(RuleError::CannotLoad(near)));
+    assert_eq!(state.load_unit(far, apc), Err(RuleError::CannotLoad(far)));
+    assert_eq!(state.load_unit(far, enemy_apc), Err(RuleError::CannotLoad(far)));
+    assert_eq!(state.load_unit(apc, apc), Err(RuleError::CannotLoad(apc)));
+}
+
+#[test]
+fn test_move_onto_transport() {
+    let mut map = grass_map(4, 1);
+
+    set(&mut map, 1, 0, TerrainClass::Ocean);
+    set(&mut map, 2, 0, TerrainClass::Shoal);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    let lander = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Lander, Nation::OrangeStar));
+    let first = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let second = state.add_unit(Unit::new(Coord::new(3, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.start_turn();
+
+    // Infantry cannot walk onto a Lander in the ocean
+    assert!(!state.reachable(first).unwrap().contains(&Coord::new(1, 0)));
+    assert_eq!(state.move_along(first, &path(&[(0, 0), (1, 0)])), Err(RuleError::CannotMove(first)));
+
+    state.move_unit(lander, Coord::new(2, 0)).unwrap();
+
+    assert!(state.reachable(second).unwrap().destinations().any(|coord| coord == Coord::new(2, 0)));
+
+    state.move_along(second, &path(&[(3, 0), (2, 0)])).unwrap();
+
+    assert_eq!(state.unit(second).unwrap().transport, Some(lander));
+    assert_eq!(state.unit_at(&Coord::new(2, 0)).unwrap().0, lander);
+}
+
+#[test]
+fn test_cannot_unload() {
+    let mut map = grass_map(4, 1);
+
+    set(&mut map, 1, 0, TerrainClass::Ocean);
+    set(&mut map, 2, 0, TerrainClass::Shoal);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    let lander = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Lander, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::OrangeStar));
+    let blocker = state.add_unit(Unit::new(Coord::new(3, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.start_turn();
+    state.load_unit(tank, lander).unwrap();
+
+    // Landers cannot unload from the ocean
+    assert_eq!(state.unload_unit(lander, tank, Coord::new(0, 0)), Err(RuleError::CannotUnload(tank)));
+
+    state.move_unit(lander, Coord::new(2, 0)).unwrap();
+
+    // Tanks cannot move onto the ocean
+    assert_eq!(state.unload_unit(lander, tank, Coord::new(1, 0)), Err(RuleError::CannotUnload(tank)));
+
+    // The tile is occupied
+    assert_eq!(state.unload_unit(lander, tank, Coord::new(3, 0)), Err(RuleError::CannotUnload(tank)));
+
+    // Not adjacent
+    assert_eq!(state.unload_unit(lander, tank, Coord::new(0, 0)), Err(RuleError::CannotUnload(tank)));
+
+    // Not in the transport
+    assert_eq!(state.unload_unit(lander, blocker, Coord::new(1, 0)), Err(RuleError::CannotUnload(blocker)));
+
+    state.remove_unit(blocker);
+    state.unload_unit(lander, tank, Coord::new(3, 0)).unwrap();
+}
+
+#[test]
+fn test_cargo_destroyed() {
+    let mut map = grass_map(3, 1);
+
+    set(&mut map, 0, 0, TerrainClass::Shoal);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::new(Co::Andy));
+
+    let lander = state.add_unit(Unit { hp: 50, ..Unit::new(Coord::new(0, 0), UnitClass::Lander, Nation::OrangeStar) });
+    let apc = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::APC, Nation::OrangeStar));
+    let infantry = state.add_unit(Unit { hp: 70, ..Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::OrangeStar) });
+
+    state.start_turn();
+    state.move_along(infantry, &path(&[(2, 0), (1, 0)])).unwrap();
+    state.move_along(apc, &path(&[(1, 0), (0, 0)])).unwrap();
+
+    assert_eq!(state.unit(infantry).unwrap().coord, Coord::new(0, 0));
+
+    // Units inside of transports do not give vision
+    let vision = Vision::new(&state.map, state.units().map(|(_, unit)| unit), Nation::OrangeStar);
+    assert!(vision.is_visible(&Coord::new(1, 0)));
+    assert!(!vision.is_visible(&Coord::new(2, 0)));
+
+    let events = state.destroy_unit(lander);
+
+    assert_eq!(events, vec![
+        Event::Destroyed { unit: lander, coord: Coord::new(0, 0), nation: Nation::OrangeStar, value: 6000 },
+        Event::Destroyed { unit: apc, coord: Coord::new(0, 0), nation: Nation::OrangeStar, value: 5000 },
+        Event::Destroyed { unit: infantry, coord: Coord::new(0, 0), nation: Nation::OrangeStar, value: 700 },
+    ]);
+
+    assert_eq!(state.units().count(), 0);
+
+    // The held units add to the power meter
+    assert_eq!(state.player(Nation::OrangeStar).unwrap().co.as_ref().unwrap().meter, 5700);
+}
+
+#[test]
+fn test_cargo_destroyed_in_combat() {
+    let mut state = GameState::new(grass_map(2, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::new(Co::Andy));
+    state.player_mut(Nation::BlueMoon).unwrap().co = Some(PlayerCo::new(Co::Andy));
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::BlueMoon));
+    let apc = state.add_unit(Unit { hp: 10, ..Unit::new(Coord::new(1, 0), UnitClass::APC, Nation::OrangeStar) });
+    let infantry = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.start_turn();
+    state.load_unit(infantry, apc).unwrap();
+    state.end_turn();
+
+    state.resolve_attack(tank, apc, NoLuck).unwrap();
+
+    assert!(state.unit(infantry).is_none());
+
+    // The APC's damage is worth 500 and the infantry is worth 1000, damage dealt charges half as much
+    assert_eq!(state.player(Nation::OrangeStar).unwrap().co.as_ref().unwrap().meter, 1500);
+    assert_eq!(state.player(Nation::BlueMoon).unwrap().co.as_ref().unwrap().meter, 750);
+}
@@ -0,0 +1,260 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, BuildingClass, Event, UnitId, Rules,
+};
+
+mod common;
+
+use common::{grass_map, set_building};
+
+
+#[test]
+fn test_turn_order() {
+    let mut state = GameState::new(grass_map(3, 3), &[Nation::YellowComet, Nation::OrangeStar, Nation::BlueMoon]);
+
+    assert_eq!(state.day(), 1);
+    assert_eq!(state.current_player().nation, Nation::OrangeStar);
+
+    assert_eq!(state.start_turn()[0], Event::TurnStarted { nation: Nation::OrangeStar, day: 1 });
+
+    state.end_turn();
+    assert_eq!(state.current_player().nation, Nation::BlueMoon);
+    assert_eq!(state.day(), 1);
+
+    state.end_turn();
+    assert_eq!(state.current_player().nation, Nation::YellowComet);
+    assert_eq!(state.day(), 1);
+
+    let events = state.end_turn();
+    assert_eq!(state.current_player().nation, Nation::OrangeStar);
+    assert_eq!(state.day(), 2);
+    assert_eq!(events[0], Event::TurnStarted { nation: Nation::OrangeStar, day: 2 });
+
+    // Defeated players are skipped
+    state.player_mut(Nation::BlueMoon).unwrap().defeated = true;
+
+    state.end_turn();
+    assert_eq!(state.current_player().nation, Nation::YellowComet);
+}
+
+#[test]
+fn test_income() {
+    let mut map = grass_map(5, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::HQ1, Some(Nation::OrangeStar));
+    set_building(&mut map, 1, 0, BuildingClass::City, Some(Nation::OrangeStar));
+    set_building(&mut map, 2, 0, BuildingClass::ComTower, Some(Nation::OrangeStar));
+    set_building(&mut map, 3, 0, BuildingClass::City, Some(Nation::BlueMoon));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let events = state.start_turn();
+
+    assert_eq!(events[1], Event::Income { nation: Nation::OrangeStar, funds: 2000 });
+    assert_eq!(state.player(Nation::OrangeStar).unwrap().funds, 2000);
+    assert_eq!(state.player(Nation::BlueMoon).unwrap().funds, 0);
+
+    state.end_turn();
+    assert_eq!(state.player(Nation::BlueMoon).unwrap().funds, 1000);
+}
+
+#[test]
+fn test_repair() {
+    let mut map = grass_map(5, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::City, Some(Nation::OrangeStar));
+    set_building(&mut map, 1, 0, BuildingClass::City, Some(Nation::BlueMoon));
+    set_building(&mut map, 2, 0, BuildingClass::Airport, Some(Nation::BlueMoon));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit { hp: 50, ammo: 0, ..Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar) });
+    let enemy_city = state.add_unit(Unit { hp: 50, ..Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::OrangeStar) });
+    let airport = state.add_unit(Unit { hp: 50, ..Unit::new(Coord::new(2, 0), UnitClass::Tank, Nation::OrangeStar) });
+
+    state.player_mut(Nation::OrangeStar).unwrap().funds = 1000;
+
+    let events = state.start_turn();
+
+    assert!(events.contains(&Event::Repaired { unit: tank, hp: 20, cost: 1400 }));
+    assert!(events.contains(&Event::Resupplied { unit: tank }));
+
+    assert_eq!(state.unit(tank).unwrap().hp, 70);
+    assert_eq!(state.unit(tank).unwrap().ammo, 9);
+    assert_eq!(state.unit(enemy_city).unwrap().hp, 50);
+    assert_eq!(state.unit(airport).unwrap().hp, 50);
+
+    // City income minus the repair cost
+    assert_eq!(state.current_player().funds, 1000 + 1000 - 1400);
+
+    state.unit_mut(tank).unwrap().hp = 10;
+
+    state.end_turn();
+    let events = state.end_turn();
+
+    // The full repair costs 1400, and there is 1600 funds
+    assert!(events.contains(&Event::Repaired { unit: tank, hp: 20, cost: 1400 }));
+    assert_eq!(state.current_player().funds, 200);
+
+    state.end_turn();
+    let events = state.end_turn();
+
+    // Only 17 HP can be afforded with 1200 funds
+    assert!(events.contains(&Event::Repaired { unit: tank, hp: 17, cost: 1190 }));
+    assert_eq!(state.unit(tank).unwrap().hp, 47);
+    assert_eq!(state.current_player().funds, 10);
+}
+
+#[test]
+fn test_repair_max_hp() {
+    let mut map = grass_map(1, 1);
+
+    set_building(&mut map, 0, 0, BuildingClass::City, Some(Nation::OrangeStar));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    let unit = state.add_unit(Unit { hp: 95, ..Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar) });
+
+    let events = state.start_turn();
+
+    assert!(events.contains(&Event::Repaired { unit, hp: 5, cost: 50 }));
+    assert_eq!(state.unit(unit).unwrap().hp, 100);
+    assert_eq!(state.current_player().funds, 950);
+
+    let events = state.end_turn();
+
+    assert!(!events.iter().any(|event| matches!(event, Event::Repaired { .. })));
+}
+
+#[test]
+fn test_resupply_before_fuel() {
+    let map = grass_map(5, 1);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    let apc = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::APC, Nation::OrangeStar));
+    let supplied = state.add_unit(Unit { fuel: 0, ..Unit::new(Coord::new(1, 0), UnitClass::BCopter, Nation::OrangeStar) });
+    let crashed = state.add_unit(Unit { fuel: 0, ..Unit::new(Coord::new(3, 0), UnitClass::BCopter, Nation::OrangeStar) });
+
+    let value = state.unit(crashed).unwrap().value();
+
+    let events = state.start_turn();
+
+    assert_eq!(&events[1..], &[
+        Event::Resupplied { unit: supplied },
+        Event::FuelUsed { unit: supplied, fuel: 2 },
+        Event::Crashed { unit: crashed, coord: Coord::new(3, 0) },
+        Event::Destroyed { unit: crashed, coord: Coord::new(3, 0), nation: Nation::OrangeStar, value },
+    ]);
+
+    assert!(state.unit(apc).is_some());
+    assert_eq!(state.unit(supplied).unwrap().fuel, 97);
+    assert!(state.unit(crashed).is_none());
+}
+
+#[test]
+fn test_daily_fuel() {
+    let map = grass_map(5, 1);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let fighter = state.add_unit(Unit { fuel: 7, ..Unit::new(Coord::new(0, 0), UnitClass::Fighter, Nation::OrangeStar) });
+    let tank = state.add_unit(Unit { fuel: 0, ..Unit::new(Coord::new(1, 0), UnitClass::Tank, Nation::OrangeStar) });
+    let enemy = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Fighter, Nation::BlueMoon));
+
+    state.start_turn();
+
+    assert_eq!(state.unit(fighter).unwrap().fuel, 2);
+    assert_eq!(state.unit(enemy).unwrap().fuel, 99);
+
+    // Ground units do not crash
+    assert_eq!(state.unit(tank).unwrap().fuel, 0);
+
+    state.end_turn();
+    assert_eq!(state.unit(enemy).unwrap().fuel, 94);
+
+    let events = state.end_turn();
+
+    assert!(events.contains(&Event::FuelUsed { unit: fighter, fuel: 2 }));
+    assert!(events.contains(&Event::Crashed { unit: fighter, coord: Coord::new(0, 0) }));
+    assert!(state.unit(fighter).is_none());
+    assert!(state.unit(tank).is_some());
+
+    assert_eq!(state.units().map(|(id, _)| id).collect::<Vec<UnitId>>(), vec![tank, enemy]);
+}
+
+#[test]
+fn test_crashed_cargo() {
+    let map = grass_map(5, 1);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let copter = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::TCopter, Nation::OrangeStar));
+    let infantry = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.start_turn();
+
+    state.move_along(infantry, &[Coord::new(1, 0), Coord::new(0, 0)]).unwrap();
+    state.unit_mut(copter).unwrap().fuel = 2;
+
+    state.end_turn();
+
+    let events = state.end_turn();
+
+    assert!(events.contains(&Event::Crashed { unit: copter, coord: Coord::new(0, 0) }));
+    assert!(events.contains(&Event::Destroyed { unit: infantry, coord: Coord::new(0, 0), nation: Nation::OrangeStar, value: 1000 }));
+    assert!(state.unit(copter).is_none());
+    assert!(state.unit(infantry).is_none());
+}
+
+#[test]
+fn test_state_vision() {
+    let mut map = grass_map(8, 1);
+
+    set_building(&mut map, 7, 0, BuildingClass::City, Some(Nation::OrangeStar));
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.rules = Rules { fog: true, ..Rules::default() };
+
+    state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+    state.add_unit(Unit::new(Coord::new(4, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    let vision = state.vision(Nation::OrangeStar);
+
+    assert!(vision.is_visible(&Coord::new(2, 0)));
+    assert!(!vision.is_visible(&Coord::new(3, 0)));
+    assert!(vision.is_visible(&Coord::new(7, 0)));
+
+    let vision = state.vision(Nation::BlueMoon);
+
+    assert!(vision.is_visible(&Coord::new(2, 0)));
+    assert!(!vision.is_visible(&Coord::new(1, 0)));
+    assert!(!vision.is_visible(&Coord::new(7, 0)));
+}
+
+#[test]
+fn test_hidden_fuel() {
+    let map = grass_map(5, 1);
+
+    let mut state = GameState::new(map, &[Nation::OrangeStar]);
+
+    let stealth = state.add_unit(Unit { hidden: true, ..Unit::new(Coord::new(0, 0), UnitClass::Stealth, Nation::OrangeStar) });
+    let visible = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Stealth, Nation::OrangeStar));
+    let sub = state.add_unit(Unit { hidden: true, fuel: 4, ..Unit::new(Coord::new(2, 0), UnitClass::Submarine, Nation::OrangeStar) });
+
+    let value = state.unit(sub).unwrap().value();
+
+    let events = state.start_turn();
+
+    assert_eq!(&events[1..], &[
+        Event::FuelUsed { unit: stealth, fuel: 8 },
+        Event::FuelUsed { unit: visible, fuel: 5 },
+        Event::FuelUsed { unit: sub, fuel: 4 },
+        Event::Crashed { unit: sub, coord: Coord::new(2, 0) },
+        Event::Destroyed { unit: sub, coord: Coord::new(2, 0), nation: Nation::OrangeStar, value },
+    ]);
+
+    assert_eq!(state.unit(stealth).unwrap().fuel, 52);
+    assert_eq!(state.unit(visible).unwrap().fuel, 55);
+    assert!(state.unit(sub).is_none());
+}
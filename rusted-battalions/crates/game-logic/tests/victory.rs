@@ -0,0 +1,228 @@
+use rusted_battalions_game_logic::{
+    GameState, Map, Coord, Unit, UnitClass, Nation, BuildingClass, Event, RuleError,
+    Rules, Victory, Command, Vision,
+};
+
+mod common;
+
+use common::{NoLuck, grass_map};
+
+
+fn victory_state(map: Map, nations: &[Nation], victory: Vec<Victory>) -> GameState {
+    let mut state = GameState::new(map, nations);
+
+    state.rules = Rules { victory, ..Rules::default() };
+
+    state
+}
+
+
+#[test]
+fn test_rout() {
+    let mut state = victory_state(grass_map(3, 1), &[Nation::OrangeStar, Nation::BlueMoon], vec![Victory::Rout]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+
+    state.start_turn();
+
+    // Players do not need units on day 1
+    assert_eq!(state.apply(Command::EndTurn, NoLuck), Ok(vec![Event::TurnStarted { nation: Nation::BlueMoon, day: 1 }]));
+
+    let events = state.apply(Command::EndTurn, NoLuck).unwrap();
+
+    assert_eq!(events, vec![
+        Event::Defeated { nation: Nation::BlueMoon },
+        Event::GameOver { ranking: vec![Nation::OrangeStar, Nation::BlueMoon] },
+    ]);
+
+    assert!(state.is_game_over());
+    assert_eq!(state.apply(Command::Wait { unit: tank }, NoLuck), Err(RuleError::GameOver));
+}
+
+#[test]
+fn test_single_player() {
+    let mut state = victory_state(grass_map(3, 1), &[Nation::OrangeStar], vec![Victory::Rout, Victory::Properties(1)]);
+
+    state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+
+    state.start_turn();
+
+    // A match without any defeats never ends
+    assert_eq!(state.apply(Command::EndTurn, NoLuck), Ok(vec![Event::TurnStarted { nation: Nation::OrangeStar, day: 2 }]));
+    assert!(!state.is_game_over());
+}
+
+#[test]
+fn test_team_properties() {
+    let mut map = grass_map(4, 1);
+
+    map.set_building(Coord::new(0, 0), BuildingClass::City, Some(Nation::OrangeStar));
+    map.set_building(Coord::new(1, 0), BuildingClass::City, Some(Nation::GreenEarth));
+    map.set_building(Coord::new(2, 0), BuildingClass::City, None);
+    map.set_building(Coord::new(3, 0), BuildingClass::City, Some(Nation::BlueMoon));
+
+    let nations = [Nation::OrangeStar, Nation::BlueMoon, Nation::GreenEarth];
+
+    let mut state = victory_state(map, &nations, vec![Victory::Properties(3)]);
+
+    state.player_mut(Nation::OrangeStar).unwrap().team = Some(0);
+    state.player_mut(Nation::GreenEarth).unwrap().team = Some(0);
+
+    assert!(state.same_team(Nation::OrangeStar, Nation::GreenEarth));
+    assert!(!state.same_team(Nation::OrangeStar, Nation::BlueMoon));
+
+    let infantry = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.start_turn();
+
+    assert!(state.apply(Command::Capture { unit: infantry }, NoLuck).unwrap().iter().all(|event| !matches!(event, Event::GameOver { .. })));
+
+    state.end_turn();
+    state.end_turn();
+    state.end_turn();
+
+    let events = state.apply(Command::Capture { unit: infantry }, NoLuck).unwrap();
+
+    assert_eq!(&events[2..], &[
+        Event::Defeated { nation: Nation::BlueMoon },
+        Event::GameOver { ranking: vec![Nation::OrangeStar, Nation::GreenEarth, Nation::BlueMoon] },
+    ]);
+}
+
+#[test]
+fn test_lab_and_resign() {
+    let mut map = grass_map(2, 1);
+
+    map.set_building(Coord::new(0, 0), BuildingClass::Lab, Some(Nation::BlueMoon));
+
+    let nations = [Nation::OrangeStar, Nation::BlueMoon, Nation::GreenEarth];
+
+    let mut state = victory_state(map, &nations, vec![Victory::Lab]);
+
+    let infantry = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.start_turn();
+    state.apply(Command::Capture { unit: infantry }, NoLuck).unwrap();
+
+    for _ in 0..3 {
+        state.apply(Command::EndTurn, NoLuck).unwrap();
+    }
+
+    let events = state.apply(Command::Capture { unit: infantry }, NoLuck).unwrap();
+
+    assert_eq!(&events[2..], &[Event::Defeated { nation: Nation::BlueMoon }]);
+    assert!(state.unit(enemy).is_none());
+
+    // Blue Moon's turn is skipped
+    state.apply(Command::EndTurn, NoLuck).unwrap();
+
+    assert_eq!(state.current_player().nation, Nation::GreenEarth);
+
+    let events = state.apply(Command::Resign, NoLuck).unwrap();
+
+    assert_eq!(events, vec![
+        Event::Defeated { nation: Nation::GreenEarth },
+        Event::GameOver { ranking: vec![Nation::OrangeStar, Nation::GreenEarth, Nation::BlueMoon] },
+    ]);
+}
+
+#[test]
+fn test_destroy_target() {
+    let mut state = GameState::new(grass_map(2, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::MegaTank, Nation::OrangeStar));
+    let target = state.add_unit(Unit { hp: 10, ..Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::BlueMoon) });
+
+    let victory = vec![Victory::Destroy { nation: Nation::OrangeStar, unit: target, day: 2 }];
+
+    state.rules = Rules { victory: victory.clone(), ..Rules::default() };
+
+    state.start_turn();
+
+    let events = state.apply(Command::Attack { unit: tank, target }, NoLuck).unwrap();
+
+    assert_eq!(&events[events.len() - 2..], &[
+        Event::Defeated { nation: Nation::BlueMoon },
+        Event::GameOver { ranking: vec![Nation::OrangeStar, Nation::BlueMoon] },
+    ]);
+
+    // The target survives until the end of day 2
+    let mut state = victory_state(grass_map(2, 1), &[Nation::OrangeStar, Nation::BlueMoon], victory);
+
+    state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar));
+    state.add_unit(Unit::new(Coord::new(1, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.start_turn();
+
+    for _ in 0..3 {
+        state.end_turn();
+    }
+
+    assert!(!state.is_game_over());
+
+    assert_eq!(state.end_turn(), vec![
+        Event::Defeated { nation: Nation::OrangeStar },
+        Event::GameOver { ranking: vec![Nation::BlueMoon, Nation::OrangeStar] },
+    ]);
+}
+
+#[test]
+fn test_survive() {
+    let victory = vec![Victory::Survive { nation: Nation::BlueMoon, day: 1 }];
+
+    let mut state = victory_state(grass_map(2, 1), &[Nation::OrangeStar, Nation::BlueMoon], victory);
+
+    state.start_turn();
+
+    assert_eq!(state.end_turn(), vec![Event::TurnStarted { nation: Nation::BlueMoon, day: 1 }]);
+
+    assert_eq!(state.end_turn(), vec![
+        Event::Defeated { nation: Nation::OrangeStar },
+        Event::GameOver { ranking: vec![Nation::BlueMoon, Nation::OrangeStar] },
+    ]);
+}
+
+#[test]
+fn test_team_rules() {
+    let mut map = grass_map(8, 1);
+
+    map.set_building(Coord::new(2, 0), BuildingClass::City, Some(Nation::GreenEarth));
+
+    let nations = [Nation::OrangeStar, Nation::BlueMoon, Nation::GreenEarth];
+
+    let mut state = victory_state(map, &nations, vec![Victory::Hq]);
+
+    state.rules.fog = true;
+
+    state.player_mut(Nation::OrangeStar).unwrap().team = Some(0);
+    state.player_mut(Nation::GreenEarth).unwrap().team = Some(0);
+
+    let apc = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::APC, Nation::OrangeStar));
+    let ally = state.add_unit(Unit { fuel: 10, ..Unit::new(Coord::new(1, 0), UnitClass::Recon, Nation::GreenEarth) });
+    let infantry = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Infantry, Nation::OrangeStar));
+
+    state.add_unit(Unit::new(Coord::new(7, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.start_turn();
+
+    // Teammates cannot attack each other or capture each other's properties
+    assert_eq!(state.apply(Command::Attack { unit: infantry, target: ally }, NoLuck), Err(RuleError::CannotAttack(infantry)));
+    assert_eq!(state.apply(Command::Capture { unit: infantry }, NoLuck), Err(RuleError::CannotCapture(infantry)));
+
+    // Teammates can move through each other, but they cannot stop on each other
+    let reachable = state.reachable(apc).unwrap();
+
+    assert!(reachable.contains(&Coord::new(1, 0)));
+    assert!(!reachable.destinations().any(|coord| coord == Coord::new(1, 0)));
+
+    // APCs supply teammates
+    assert_eq!(state.apply(Command::Supply { unit: apc }, NoLuck), Ok(vec![Event::Resupplied { unit: ally }]));
+
+    // Teammates share their vision
+    let own = Vision::new(&state.map, state.units().map(|(_, unit)| unit), Nation::OrangeStar);
+
+    assert!(!own.is_visible(&Coord::new(6, 0)));
+    assert!(state.vision(Nation::OrangeStar).is_visible(&Coord::new(6, 0)));
+    assert!(!state.vision(Nation::BlueMoon).is_visible(&Coord::new(0, 0)));
+}
@@ -0,0 +1,112 @@
+use rusted_battalions_game_logic::{
+    Coord, Unit, UnitClass, Nation, TerrainClass, BuildingClass, Vision, vision_range,
+};
+
+mod common;
+
+use common::{grass_map, set};
+
+
+#[test]
+fn test_vision_diamond() {
+    let map = grass_map(9, 9);
+
+    let unit = Unit::new(Coord::new(4, 4), UnitClass::Tank, Nation::OrangeStar);
+
+    let vision = Vision::new(&map, [&unit], Nation::OrangeStar);
+
+    for tile in map.tiles() {
+        let distance = tile.coord.distance(&unit.coord);
+        assert_eq!(vision.is_visible(&tile.coord), distance <= 3, "{:?}", tile.coord);
+    }
+
+    // Enemy units do not give vision
+    let vision = Vision::new(&map, [&unit], Nation::BlueMoon);
+
+    for tile in map.tiles() {
+        assert!(!vision.is_visible(&tile.coord));
+    }
+
+    assert!(!vision.is_visible(&Coord::new(20, 20)));
+}
+
+#[test]
+fn test_mountain_bonus() {
+    let mut map = grass_map(9, 1);
+
+    set(&mut map, 0, 0, TerrainClass::Mountain { variant: 0 });
+
+    let mountain = map.get(&Coord::new(0, 0)).unwrap();
+    let grass = map.get(&Coord::new(1, 0)).unwrap();
+
+    assert_eq!(vision_range(UnitClass::Infantry, grass), 2);
+    assert_eq!(vision_range(UnitClass::Infantry, mountain), 5);
+    assert_eq!(vision_range(UnitClass::Mech, mountain), 5);
+    assert_eq!(vision_range(UnitClass::Tank, grass), 3);
+
+    let unit = Unit::new(Coord::new(0, 0), UnitClass::Infantry, Nation::OrangeStar);
+
+    let vision = Vision::new(&map, [&unit], Nation::OrangeStar);
+
+    assert!(vision.is_visible(&Coord::new(5, 0)));
+    assert!(!vision.is_visible(&Coord::new(6, 0)));
+}
+
+#[test]
+fn test_concealment() {
+    let mut map = grass_map(9, 1);
+
+    set(&mut map, 1, 0, TerrainClass::Forest);
+    set(&mut map, 3, 0, TerrainClass::Forest);
+    set(&mut map, 4, 0, TerrainClass::Reef);
+
+    let unit = Unit::new(Coord::new(0, 0), UnitClass::Recon, Nation::OrangeStar);
+
+    let vision = Vision::new(&map, [&unit], Nation::OrangeStar);
+
+    // Adjacent forests can be seen
+    assert!(vision.is_visible(&Coord::new(1, 0)));
+    assert!(vision.is_visible(&Coord::new(2, 0)));
+    assert!(!vision.is_visible(&Coord::new(3, 0)));
+    assert!(!vision.is_visible(&Coord::new(4, 0)));
+    assert!(vision.is_visible(&Coord::new(5, 0)));
+    assert!(!vision.is_visible(&Coord::new(6, 0)));
+
+    let hidden = Unit::new(Coord::new(3, 0), UnitClass::Infantry, Nation::BlueMoon);
+
+    assert!(!vision.can_see(&hidden));
+
+    // Units can always see themselves, even inside of a forest
+    let vision = Vision::new(&map, [&hidden], Nation::BlueMoon);
+
+    assert!(vision.can_see(&hidden));
+    assert!(vision.is_visible(&Coord::new(4, 0)));
+}
+
+#[test]
+fn test_buildings() {
+    let mut map = grass_map(5, 1);
+
+    let tile = map.get_mut(&Coord::new(2, 0)).unwrap();
+    tile.building = Some(BuildingClass::City);
+    tile.owner = Some(Nation::BlueMoon);
+
+    let vision = Vision::new(&map, [], Nation::BlueMoon);
+
+    assert!(!vision.is_visible(&Coord::new(1, 0)));
+    assert!(vision.is_visible(&Coord::new(2, 0)));
+    assert!(!vision.is_visible(&Coord::new(3, 0)));
+
+    let vision = Vision::new(&map, [], Nation::OrangeStar);
+
+    assert!(!vision.is_visible(&Coord::new(2, 0)));
+}
+
+#[test]
+fn test_no_fog() {
+    let vision = Vision::all(3, 3);
+
+    assert!(vision.is_visible(&Coord::new(0, 0)));
+    assert!(vision.is_visible(&Coord::new(2, 2)));
+    assert!(!vision.is_visible(&Coord::new(3, 0)));
+}
@@ -0,0 +1,190 @@
+use rusted_battalions_game_logic::{
+    GameState, Coord, Unit, UnitClass, Nation, TerrainClass, BuildingClass, MovementType, Event, RuleError,
+    Rules, Weather, Command, Co, PlayerCo, PowerKind, SeededRng,
+};
+
+mod common;
+
+use common::{NoLuck, grass_map, path};
+
+
+#[test]
+fn test_movement_costs() {
+    let mut map = grass_map(5, 1);
+
+    map.get_mut(&Coord::new(1, 0)).unwrap().class = TerrainClass::Forest;
+    map.get_mut(&Coord::new(2, 0)).unwrap().class = TerrainClass::Mountain { variant: 0 };
+    map.get_mut(&Coord::new(3, 0)).unwrap().class = TerrainClass::Ocean;
+    map.set_building(Coord::new(4, 0), BuildingClass::City, None);
+
+    let cost = |weather: Weather, movement: MovementType, x: u32| {
+        weather.movement_cost(movement, map.get(&Coord::new(x, 0)).unwrap())
+    };
+
+    // Clear and sandstorm do not change the movement costs
+    for weather in [Weather::Clear, Weather::Sandstorm] {
+        for tile in map.tiles() {
+            for movement in [MovementType::Foot, MovementType::Tires, MovementType::Air, MovementType::Sea] {
+                assert_eq!(weather.movement_cost(movement, tile), movement.cost(tile));
+            }
+        }
+    }
+
+    assert_eq!(cost(Weather::Rain, MovementType::Treads, 0), Some(2));
+    assert_eq!(cost(Weather::Rain, MovementType::Tires, 1), Some(4));
+    assert_eq!(cost(Weather::Rain, MovementType::Foot, 0), Some(1));
+    assert_eq!(cost(Weather::Rain, MovementType::Air, 0), Some(1));
+
+    assert_eq!(cost(Weather::Snow, MovementType::Foot, 0), Some(2));
+    assert_eq!(cost(Weather::Snow, MovementType::Foot, 2), Some(4));
+    assert_eq!(cost(Weather::Snow, MovementType::Boots, 2), Some(2));
+    assert_eq!(cost(Weather::Snow, MovementType::Treads, 1), Some(3));
+    assert_eq!(cost(Weather::Snow, MovementType::Air, 4), Some(2));
+    assert_eq!(cost(Weather::Snow, MovementType::Sea, 3), Some(2));
+    assert_eq!(cost(Weather::Snow, MovementType::Treads, 2), None);
+
+    // Buildings are not slowed down for ground units
+    assert_eq!(cost(Weather::Snow, MovementType::Tires, 4), Some(1));
+}
+
+#[test]
+fn test_snow_movement() {
+    let mut state = GameState::new(grass_map(8, 1), &[Nation::OrangeStar]);
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+
+    state.weather = Weather::Snow;
+
+    state.start_turn();
+
+    let reachable = state.reachable(tank).unwrap();
+
+    assert!(reachable.contains(&Coord::new(3, 0)));
+    assert!(!reachable.contains(&Coord::new(4, 0)));
+
+    assert_eq!(state.apply(Command::Move { unit: tank, path: path(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]) }, NoLuck), Err(RuleError::CannotMove(tank)));
+
+    state.apply(Command::Move { unit: tank, path: path(&[(0, 0), (1, 0), (2, 0), (3, 0)]) }, NoLuck).unwrap();
+
+    assert_eq!(state.unit(tank).unwrap().fuel, 64);
+}
+
+#[test]
+fn test_rain_vision() {
+    let mut state = GameState::new(grass_map(9, 1), &[Nation::OrangeStar]);
+
+    state.rules = Rules { fog: true, ..Rules::default() };
+
+    state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Recon, Nation::OrangeStar));
+
+    assert_eq!(Weather::Rain.vision(1), 1);
+    assert!(state.vision(Nation::OrangeStar).is_visible(&Coord::new(5, 0)));
+
+    state.weather = Weather::Rain;
+
+    let vision = state.vision(Nation::OrangeStar);
+
+    assert!(vision.is_visible(&Coord::new(4, 0)));
+    assert!(!vision.is_visible(&Coord::new(5, 0)));
+}
+
+#[test]
+fn test_sandstorm_range() {
+    let mut state = GameState::new(grass_map(4, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    let artillery = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Artillery, Nation::OrangeStar));
+    let tank = state.add_unit(Unit::new(Coord::new(2, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(3, 0), UnitClass::Infantry, Nation::BlueMoon));
+
+    state.weather = Weather::Sandstorm;
+
+    state.start_turn();
+
+    assert_eq!(state.attack_range(artillery), Some((2, 2)));
+    assert_eq!(state.attack_range(tank), Some((1, 1)));
+
+    assert_eq!(state.apply(Command::Attack { unit: artillery, target: enemy }, NoLuck), Err(RuleError::CannotAttack(artillery)));
+
+    state.weather = Weather::Clear;
+
+    assert!(state.apply(Command::Attack { unit: artillery, target: enemy }, NoLuck).is_ok());
+}
+
+#[test]
+fn test_olaf() {
+    let mut state = GameState::new(grass_map(8, 1), &[Nation::OrangeStar, Nation::BlueMoon]);
+
+    state.player_mut(Nation::OrangeStar).unwrap().co = Some(PlayerCo::new(Co::Olaf));
+
+    let tank = state.add_unit(Unit::new(Coord::new(0, 0), UnitClass::Tank, Nation::OrangeStar));
+    let enemy = state.add_unit(Unit::new(Coord::new(7, 0), UnitClass::Tank, Nation::BlueMoon));
+
+    state.start_turn();
+
+    let co = state.player_mut(Nation::OrangeStar).unwrap().co.as_mut().unwrap();
+
+    co.meter = co.max_meter();
+
+    let events = state.activate_power(PowerKind::SuperPower).unwrap();
+
+    assert_eq!(events, vec![
+        Event::PowerActivated { nation: Nation::OrangeStar, co: Co::Olaf, power: PowerKind::SuperPower },
+        Event::WeatherChanged { weather: Weather::Snow },
+        Event::PowerHit { unit: enemy, damage: 20 },
+    ]);
+
+    assert_eq!(state.current_weather(), Weather::Snow);
+
+    // Olaf's units are not slowed down by snow
+    assert!(state.reachable(tank).unwrap().contains(&Coord::new(6, 0)));
+
+    let events = state.end_turn();
+
+    assert_eq!(events[0], Event::TurnStarted { nation: Nation::BlueMoon, day: 1 });
+
+    assert!(state.reachable(enemy).unwrap().contains(&Coord::new(4, 0)));
+    assert!(!state.reachable(enemy).unwrap().contains(&Coord::new(3, 0)));
+
+    // The snow stops when Olaf's next turn starts
+    let events = state.end_turn();
+
+    assert_eq!(&events[..2], &[
+        Event::TurnStarted { nation: Nation::OrangeStar, day: 2 },
+        Event::WeatherChanged { weather: Weather::Clear },
+    ]);
+
+    assert_eq!(state.current_weather(), Weather::Clear);
+}
+
+#[test]
+fn test_random_weather() {
+    let mut state = GameState::new(grass_map(2, 1), &[Nation::OrangeStar]);
+
+    state.rules = Rules { random_weather: true, seed: 7, ..Rules::default() };
+
+    state.start_turn();
+
+    for day in 2..20 {
+        let events = state.end_turn();
+
+        let weather = Weather::random(SeededRng::with_key(7, day));
+
+        assert_eq!(state.day(), day as u32);
+        assert_eq!(state.current_weather(), weather);
+
+        for event in events {
+            if let Event::WeatherChanged { weather: changed } = event {
+                assert_eq!(changed, weather);
+            }
+        }
+    }
+
+    // Not every day is clear
+    assert!((0..100).any(|day| Weather::random(SeededRng::with_key(7, day)) != Weather::Clear));
+
+    // Matches with different seeds have different weather
+    let weather = |seed| (0..20).map(|day| Weather::random(SeededRng::with_key(seed, day))).collect::<Vec<_>>();
+
+    assert_eq!(weather(7), weather(7));
+    assert_ne!(weather(7), weather(8));
+}
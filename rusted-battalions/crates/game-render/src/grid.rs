@@ -1,5 +1,6 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::future::Future;
+use futures_signals::map_ref;
 use futures_signals::signal::{Mutable, Signal, SignalExt};
 use futures_signals::signal_vec::{SignalVecExt};
 use dominator::clone;
@@ -10,16 +11,24 @@ use crate::{Game};
 use crate::util::future::{FutureSpawner};
 use crate::util::signal::{SortedVec};
 
-use terrain::{Terrain, TerrainClass, Orientation, TerrainTile};
+use terrain::{Terrain, TerrainClass, Orientation, TerrainTile, random_mountain};
 use building::{Building, BuildingClass};
 use unit::{Unit, UnitClass};
 use explosion::{Explosion};
+use floating_text::{FloatingText};
+use weather::{Weather};
 
 pub mod action;
 pub mod terrain;
 pub mod unit;
 pub mod building;
 pub mod explosion;
+pub mod floating_text;
+pub mod weather;
+
+pub use rusted_battalions_game_logic::{Nation};
+use rusted_battalions_game_logic::{Vision};
+use rusted_battalions_game_logic as logic;
 
 
 pub(crate) const UNIT_ANIMATION_TIME: f64 = 250.0;
@@ -27,6 +36,12 @@ pub(crate) const EXPLOSION_ANIMATION_TIME: f64 = 500.0;
 pub(crate) const BUILDING_ANIMATION_TIME: f64 = 500.0;
 pub(crate) const TERRAIN_ANIMATION_TIME: f64 = 500.0;
 pub(crate) const FOG_ANIMATION_TIME: f64 = 1000.0;
+pub(crate) const WEATHER_ANIMATION_TIME: f64 = 150.0;
+pub(crate) const FLOATING_TEXT_TIME: f64 = 1000.0;
+pub(crate) const UNIT_HIDE_TIME: f64 = 500.0;
+
+// Transparency of hidden Stealths and dived Submarines, as seen by their owner
+pub(crate) const HIDDEN_UNIT_ALPHA: f32 = 0.5;
 
 // Number of milliseconds to move 1 tile
 pub(crate) const UNIT_MOVE_TIME: f64 = 200.0;
@@ -37,26 +52,6 @@ fn lerp_f32(from: f32, to: f32, percent: f32) -> f32 {
 }
 
 
-#[derive(Debug, Clone, Copy)]
-pub enum Nation {
-    OrangeStar,
-    BlueMoon,
-    GreenEarth,
-    YellowComet,
-    BlackHole,
-}
-
-impl Nation {
-    pub const ALL: &[Self] = &[
-        Self::OrangeStar,
-        Self::BlueMoon,
-        Self::GreenEarth,
-        Self::YellowComet,
-        Self::BlackHole,
-    ];
-}
-
-
 #[derive(Debug, Clone, Copy)]
 pub struct Coord {
     pub x: f32,
@@ -64,6 +59,19 @@ pub struct Coord {
 }
 
 impl Coord {
+    /// Returns the tile which the coordinate is closest to.
+    pub fn tile(&self) -> Option<logic::Coord> {
+        let x = self.x.round();
+        let y = self.y.round();
+
+        if x < 0.0 || y < 0.0 {
+            None
+
+        } else {
+            Some(logic::Coord::new(x as u32, y as u32))
+        }
+    }
+
     pub fn lerp(self, other: Self, percent: f32) -> Self {
         Self {
             x: lerp_f32(self.x, other.x, percent),
@@ -86,7 +94,7 @@ pub struct Grid {
     pub(crate) width: f32,
     pub(crate) height: f32,
 
-    pub(crate) terrain: Terrain,
+    pub(crate) terrain: Mutex<Terrain>,
 
     pub(crate) buildings: Vec<Arc<Building>>,
 
@@ -94,8 +102,20 @@ pub struct Grid {
 
     pub(crate) explosions: SortedVec<Explosion>,
 
+    pub(crate) floating_texts: SortedVec<FloatingText>,
+
     pub(crate) time: Mutable<f64>,
 
+    /// The player who is viewing the grid, and what they can see.
+    ///
+    /// If it is `None` then Fog of War is disabled.
+    pub(crate) vision: Mutable<Option<(Nation, Arc<Vision>)>>,
+
+    /// The game which is displayed and the player who is viewing it, the vision is recalculated from it when units move.
+    pub(crate) state: Mutex<Option<(Nation, logic::GameState)>>,
+
+    pub(crate) weather: Mutable<Weather>,
+
     spawner: FutureSpawner,
 }
 
@@ -112,11 +132,17 @@ impl Grid {
 
             units: SortedVec::with_values(units),
             explosions: SortedVec::new(),
+            floating_texts: SortedVec::new(),
             buildings,
-            terrain,
+            terrain: Mutex::new(terrain),
 
             time: Mutable::new(0.0),
 
+            vision: Mutable::new(None),
+            state: Mutex::new(None),
+
+            weather: Mutable::new(Weather::Clear),
+
             spawner: FutureSpawner::new(),
         })
     }
@@ -222,13 +248,179 @@ impl Grid {
     }
 
 
+    /// Changes the weather, which changes the colors of the terrain and is displayed on top of the grid.
+    pub fn set_weather(&self, weather: Weather) {
+        self.weather.set_neq(weather);
+    }
+
+
+    /// Changes the Fog of War for the player, or disables the Fog of War if it is `None`.
+    pub fn set_vision(&self, vision: Option<(Nation, Vision)>) {
+        let vision = vision.map(|(nation, vision)| (nation, Arc::new(vision)));
+
+        for building in self.buildings.iter() {
+            let fog = match &vision {
+                Some((_, vision)) => match building.coord.tile() {
+                    Some(coord) => !vision.is_visible(&coord),
+                    None => true,
+                },
+                None => false,
+            };
+
+            building.fog.set_neq(fog);
+        }
+
+        self.vision.set(vision);
+    }
+
+    /// Displays the game from the player's point of view, the Fog of War is calculated from the game.
+    pub fn set_state(&self, viewer: Nation, state: logic::GameState) {
+        self.set_vision(Some((viewer, state.vision(viewer))));
+        *self.state.lock().unwrap() = Some((viewer, state));
+    }
+
+    /// Finds the unit in the game, it matches the class and nation because allies can move through each other.
+    fn state_unit(state: &logic::GameState, unit: &Unit, coord: &logic::Coord) -> Option<logic::UnitId> {
+        state.units()
+            .find(|(_, other)| other.coord == *coord && other.class == unit.class && other.nation == unit.nation && !other.is_loaded())
+            .map(|(id, _)| id)
+    }
+
+    /// Moves the unit in the game and recalculates the Fog of War, this is called when the unit moves to a different tile.
+    pub(crate) fn move_state_unit(&self, unit: &Unit, from: Option<logic::Coord>, to: Option<logic::Coord>) {
+        let mut lock = self.state.lock().unwrap();
+
+        if let Some((viewer, state)) = lock.as_mut() {
+            if let (Some(from), Some(to)) = (from, to) {
+                if let Some(id) = Self::state_unit(state, unit, &from) {
+                    state.unit_mut(id).unwrap().coord = to;
+                    self.set_vision(Some((*viewer, state.vision(*viewer))));
+                }
+            }
+        }
+    }
+
+    /// Removes the destroyed unit from the game and recalculates the Fog of War.
+    pub(crate) fn remove_state_unit(&self, unit: &Unit) {
+        let mut lock = self.state.lock().unwrap();
+
+        if let Some((viewer, state)) = lock.as_mut() {
+            if let Some(coord) = unit.coord.get().tile() {
+                if let Some(id) = Self::state_unit(state, unit, &coord) {
+                    state.remove_unit(id);
+                    self.set_vision(Some((*viewer, state.vision(*viewer))));
+                }
+            }
+        }
+    }
+
+    /// Changes the terrain of the tile, and updates the adjacent tiles.
+    pub fn set_terrain(&self, coord: &logic::Coord, class: TerrainClass) {
+        let mut terrain = self.terrain.lock().unwrap();
+
+        terrain.get_mut(coord.x, coord.y).class = class;
+        terrain.update_tiles();
+    }
+
+    /// Changes the owner of the building, for example when it is captured.
+    ///
+    /// Properties give vision to their owner, so the Fog of War is recalculated.
+    pub fn set_building_nation(&self, coord: &logic::Coord, nation: Option<Nation>) {
+        for building in self.buildings.iter() {
+            if building.coord.tile().as_ref() == Some(coord) {
+                building.nation.set_neq(nation);
+            }
+        }
+
+        let mut lock = self.state.lock().unwrap();
+
+        if let Some((viewer, state)) = lock.as_mut() {
+            if let Some(tile) = state.map.get_mut(coord) {
+                tile.owner = nation;
+            }
+
+            self.set_vision(Some((*viewer, state.vision(*viewer))));
+        }
+    }
+
+    /// Changes the owner of the building when a property is captured or turned over.
+    pub fn capture_building(&self, event: &logic::Event) {
+        match event {
+            logic::Event::Captured { coord, nation, .. } |
+            logic::Event::TurnedOver { coord, nation } => {
+                self.set_building_nation(coord, Some(*nation));
+            },
+            _ => {},
+        }
+    }
+
+    /// Changes the class of the building, for example when a missile silo is launched.
+    pub fn set_building_class(&self, coord: &logic::Coord, class: BuildingClass) {
+        for building in self.buildings.iter() {
+            if building.coord.tile().as_ref() == Some(coord) {
+                building.class.set_neq(class);
+            }
+        }
+    }
+
+    /// Whether the tile is covered by Fog of War.
+    pub(crate) fn fog(&self, coord: &Coord) -> impl Signal<Item = bool> {
+        let coord = coord.tile();
+
+        self.vision.signal_ref(move |vision| {
+            match vision {
+                Some((_, vision)) => match coord {
+                    Some(coord) => !vision.is_visible(&coord),
+                    None => true,
+                },
+                None => false,
+            }
+        }).dedupe()
+    }
+
+    /// Whether the unit is hidden by the Fog of War.
+    ///
+    /// The player's own units are never hidden, even while they are moving.
+    ///
+    /// Hidden Stealths and dived Submarines are only visible to adjacent units.
+    pub(crate) fn unit_fog(&self, unit: &Unit) -> impl Signal<Item = bool> {
+        let nation = unit.nation;
+
+        map_ref! {
+            let coord = unit.coord.signal_ref(|coord| coord.tile()).dedupe(),
+            let hidden = unit.hidden.signal(),
+            let vision = self.vision.signal_cloned() => {
+                match vision {
+                    Some((viewer, vision)) => {
+                        if *viewer == nation {
+                            false
+
+                        } else {
+                            match coord {
+                                Some(coord) => if *hidden {
+                                    !vision.is_detected(coord)
+
+                                } else {
+                                    !vision.is_visible(coord)
+                                },
+                                None => true,
+                            }
+                        }
+                    },
+                    None => false,
+                }
+            }
+        }.dedupe()
+    }
+
+
     pub(crate) fn render(game: &Arc<Game>, this: &Arc<Self>) -> Node {
         engine::Stack::builder()
-            .children(this.terrain.iter().map(|tile| {
+            .children(this.terrain.lock().unwrap().iter().map(|tile| {
                 TerrainTile::render(game, this, tile)
             }))
 
-            .children(this.buildings.iter().map(|building| {
+            .children(this.buildings.iter().filter(|building| building.has_sprite()).map(|building| {
                 Building::render(game, this, building)
             }))
 
@@ -246,6 +438,15 @@ impl Grid {
                 })))
                 .build())
 
+            .child(engine::Stack::builder()
+                .order(Order::Parent(0.0))
+                .children_signal_vec(this.floating_texts.signal_vec().map(clone!(game, this => move |text| {
+                    FloatingText::render(&game, &this, &text)
+                })))
+                .build())
+
+            .child(weather::render(game, this))
+
             .build()
     }
 
@@ -351,13 +552,13 @@ impl Grid {
         }
 
         let mut tiles = vec![
-            (0, 0, TerrainClass::random_mountain()),
-            (0, 1, TerrainClass::random_mountain()),
-            (0, 2, TerrainClass::random_mountain()),
+            (0, 0, random_mountain()),
+            (0, 1, random_mountain()),
+            (0, 2, random_mountain()),
             (1, 1, TerrainClass::Forest),
-            (1, 2, TerrainClass::random_mountain()),
-            (0, 3, TerrainClass::random_mountain()),
-            (1, 3, TerrainClass::random_mountain()),
+            (1, 2, random_mountain()),
+            (0, 3, random_mountain()),
+            (1, 3, random_mountain()),
 
             (0, 4, TerrainClass::Forest),
             (1, 4, TerrainClass::Forest),
@@ -527,7 +728,40 @@ impl Grid {
             Some(Nation::OrangeStar),
         ));
 
-        Self::new(terrain, buildings, units)
+        buildings.push(Building::new(
+            Coord { x: 16.0, y: 11.0 },
+            BuildingClass::City,
+            None,
+        ));
+
+        // The demo is displayed from Orange Star's point of view, with Fog of War enabled
+        let mut map = logic::Map::new(terrain.width, terrain.height);
+
+        for tile in terrain.iter() {
+            map.get_mut(&logic::Coord::new(tile.x, tile.y)).unwrap().class = tile.class;
+        }
+
+        for building in buildings.iter() {
+            if let Some(coord) = building.coord.tile() {
+                map.set_building(coord, building.class.get(), building.nation.get());
+            }
+        }
+
+        let mut state = logic::GameState::new(map, &[Nation::OrangeStar, Nation::BlackHole]);
+
+        state.rules.fog = true;
+
+        for unit in units.iter() {
+            if let Some(coord) = unit.coord.get().tile() {
+                state.add_unit(logic::Unit::new(coord, unit.class, unit.nation));
+            }
+        }
+
+        let grid = Self::new(terrain, buildings, units);
+
+        grid.set_state(Nation::OrangeStar, state);
+
+        grid
     }
 
     pub fn test_performance() -> Arc<Self> {
@@ -2,10 +2,14 @@ use std::sync::Arc;
 use std::future::Future;
 use futures_signals::signal::{SignalExt};
 use dominator::clone;
+use rusted_battalions_game_logic as logic;
 
-use crate::grid::{EXPLOSION_ANIMATION_TIME, UNIT_MOVE_TIME, Grid, Coord};
+use crate::grid::{EXPLOSION_ANIMATION_TIME, FLOATING_TEXT_TIME, UNIT_MOVE_TIME, UNIT_HIDE_TIME, HIDDEN_UNIT_ALPHA, Grid, Coord, lerp_f32};
 use crate::grid::unit::{Unit, UnitAnimation};
 use crate::grid::explosion::{Explosion, ExplosionAnimation};
+use crate::grid::floating_text::{FloatingText};
+use crate::grid::terrain::{TerrainClass};
+use crate::grid::building::{BuildingClass};
 
 
 #[derive(Debug, Clone, Copy)]
@@ -57,11 +61,24 @@ impl Grid {
             let start = unit.coord.get();
             let end = direction.end(start, length);
 
+            let mut tile = start.tile();
+
             unit.animation.set_neq(direction.animation());
 
             grid.timer((length as f64) * UNIT_MOVE_TIME)
-                .for_each(clone!(unit => move |percent| {
-                    unit.coord.set(start.lerp(end, percent as f32));
+                .for_each(clone!(grid, unit => move |percent| {
+                    let coord = start.lerp(end, percent as f32);
+
+                    unit.coord.set(coord);
+
+                    // The Fog of War changes as soon as the unit is closer to the next tile
+                    let next = coord.tile();
+
+                    if next != tile {
+                        grid.move_state_unit(&unit, tile, next);
+                        tile = next;
+                    }
+
                     async {}
                 })).await;
 
@@ -89,6 +106,66 @@ impl Grid {
     }
 
 
+    pub fn floating_text(self: &Arc<Self>, coord: Coord, text: String) -> impl Future<Output = ()> + Send {
+        let grid = self.clone();
+
+        async move {
+            let text = FloatingText::new(coord, text.into());
+
+            grid.floating_texts.insert(text.clone());
+
+            grid.timer(FLOATING_TEXT_TIME)
+                .for_each(clone!(text => move |percent| {
+                    text.percent.set(percent as f32);
+                    async {}
+                })).await;
+
+            grid.floating_texts.remove(&text);
+        }
+    }
+
+
+    /// Displays the amount of HP that the unit was repaired by.
+    pub fn repair_unit(self: &Arc<Self>, unit: &Arc<Unit>, hp: u32) -> impl Future<Output = ()> + Send {
+        self.floating_text(unit.coord.get(), format!("+{}", hp))
+    }
+
+
+    /// Displays the amount of HP that the unit lost.
+    pub fn damage_unit(self: &Arc<Self>, unit: &Arc<Unit>, hp: u32) -> impl Future<Output = ()> + Send {
+        self.floating_text(unit.coord.get(), format!("-{}", hp))
+    }
+
+
+    /// Plays back the attack and the counter-attack, destroying any units which reached 0 HP.
+    pub fn attack_unit(self: &Arc<Self>, attacker: &Arc<Unit>, defender: &Arc<Unit>, result: &logic::CombatResult) -> impl Future<Output = ()> + Send {
+        let grid = self.clone();
+        let attacker = attacker.clone();
+        let defender = defender.clone();
+
+        let damage = result.damage;
+        let counter = result.counter;
+        let attacker_destroyed = result.attacker_destroyed;
+        let defender_destroyed = result.defender_destroyed;
+
+        async move {
+            grid.damage_unit(&defender, damage).await;
+
+            if defender_destroyed {
+                grid.destroy_unit(&defender).await;
+            }
+
+            if let Some(counter) = counter {
+                grid.damage_unit(&attacker, counter).await;
+
+                if attacker_destroyed {
+                    grid.destroy_unit(&attacker).await;
+                }
+            }
+        }
+    }
+
+
     pub fn hide_unit(self: &Arc<Self>, unit: &Arc<Unit>, time: f64) -> impl Future<Output = ()> + Send {
         let grid = self.clone();
         let unit = unit.clone();
@@ -117,18 +194,105 @@ impl Grid {
     }
 
 
+    /// Removes the unit from the grid because it is inside the transport, the transport shows the loaded badge.
+    pub fn load_unit(self: &Arc<Self>, unit: &Arc<Unit>, transport: &Arc<Unit>) {
+        self.units.remove(unit);
+        transport.loaded.set_neq(true);
+    }
+
+
+    /// Places the unit onto the tile, `loaded` is whether the transport is still holding other units.
+    pub fn unload_unit(self: &Arc<Self>, transport: &Arc<Unit>, unit: &Arc<Unit>, coord: Coord, loaded: bool) {
+        unit.coord.set(coord);
+        self.units.insert(unit.clone());
+        transport.loaded.set_neq(loaded);
+    }
+
+
+    /// Explodes the pipeseam and changes it to a destroyed pipeseam.
+    pub fn destroy_pipeseam(self: &Arc<Self>, coord: &logic::Coord) -> impl Future<Output = ()> + Send {
+        self.set_terrain(coord, TerrainClass::Pipeseam { destroyed: true });
+
+        self.explosion(ExplosionAnimation::Land, Coord {
+            x: coord.x as f32,
+            y: coord.y as f32,
+        })
+    }
+
+
+    /// Empties the missile silo and explodes the missile at the target.
+    pub fn launch_missile(self: &Arc<Self>, silo: &logic::Coord, target: &logic::Coord) -> impl Future<Output = ()> + Send {
+        self.set_building_class(silo, BuildingClass::MissileSiloEmpty);
+
+        self.explosion(ExplosionAnimation::Mega, Coord {
+            x: target.x as f32,
+            y: target.y as f32,
+        })
+    }
+
+
+    fn fade_unit(self: &Arc<Self>, unit: &Arc<Unit>, alpha: f32, time: f64) -> impl Future<Output = ()> + Send {
+        let grid = self.clone();
+        let unit = unit.clone();
+
+        async move {
+            let start = unit.alpha.get();
+
+            grid.timer(time)
+                .for_each(move |percent| {
+                    unit.alpha.set(lerp_f32(start, alpha, percent as f32));
+                    async {}
+                }).await;
+        }
+    }
+
+
+    /// Partially fades out the unit when a Stealth hides or a Submarine dives.
+    ///
+    /// The owner can still see the unit, other players can only see it if [`Grid::unit_fog`] allows it.
+    pub fn dive_unit(self: &Arc<Self>, unit: &Arc<Unit>) -> impl Future<Output = ()> + Send {
+        unit.hidden.set_neq(true);
+        self.fade_unit(unit, HIDDEN_UNIT_ALPHA, UNIT_HIDE_TIME)
+    }
+
+
+    /// Fades in the unit when a Stealth appears or a Submarine surfaces.
+    pub fn surface_unit(self: &Arc<Self>, unit: &Arc<Unit>) -> impl Future<Output = ()> + Send {
+        unit.hidden.set_neq(false);
+        self.fade_unit(unit, 1.0, UNIT_HIDE_TIME)
+    }
+
+
     pub fn destroy_unit(self: &Arc<Self>, unit: &Arc<Unit>) -> impl Future<Output = ()> + Send {
+        self.destroy_with_animation(unit, ExplosionAnimation::from_unit(&unit.class))
+    }
+
+
+    /// The Oozium engulfs the unit, the Oozium should be moved onto the tile afterwards.
+    pub fn engulf_unit(self: &Arc<Self>, unit: &Arc<Unit>) -> impl Future<Output = ()> + Send {
+        self.destroy_with_animation(unit, ExplosionAnimation::Ooze)
+    }
+
+
+    /// The Black Bomb self-destructs, damaging every unit within 3 tiles.
+    pub fn explode_unit(self: &Arc<Self>, unit: &Arc<Unit>) -> impl Future<Output = ()> + Send {
+        self.destroy_with_animation(unit, ExplosionAnimation::BlackBomb)
+    }
+
+
+    fn destroy_with_animation(self: &Arc<Self>, unit: &Arc<Unit>, animation: ExplosionAnimation) -> impl Future<Output = ()> + Send {
         let grid = self.clone();
         let unit = unit.clone();
 
         async move {
             let coord = unit.coord.get();
 
-            let explosion = Explosion::new(coord, unit.class.explosion_animation());
+            let explosion = Explosion::new(coord, animation);
 
             grid.explosions.insert(explosion.clone());
 
             grid.units.remove(&unit);
+            grid.remove_state_unit(&unit);
 
             grid.timer(EXPLOSION_ANIMATION_TIME)
                 .for_each(clone!(explosion => move |percent| {
@@ -7,63 +7,13 @@ use rusted_battalions_engine::{Node, Size, Offset, Tile, ParentWidth, ParentHeig
 use crate::Game;
 use crate::grid::{BUILDING_ANIMATION_TIME, FOG_ANIMATION_TIME, Grid, Coord, Nation};
 
-
-#[derive(Debug, Clone, Copy)]
-pub enum BuildingClass {
-    HQ1, // Orange Star
-    HQ2, // Blue Moon
-    HQ3, // Green Earth
-    HQ4, // Yellow Comet
-    HQ5, // Black Hole
-    City,
-    Base,
-    Airport,
-    Port,
-    ComTower,
-    Lab,
-    MissileSilo,
-    MissileSiloEmpty,
-    /*BlackCrystal,
-    Laser,
-    Minicannon { direction: , grass: bool },
-    Volcano,
-    BlackOnyx, // Flying Fortress
-    Fortress,
-    BlackArmageddon,
-    BlackCannon { direction: },
-    BlackObelisk,*/
-}
-
-impl BuildingClass {
-    pub const ALL: &[Self] = &[
-        Self::HQ1,
-        Self::HQ2,
-        Self::HQ3,
-        Self::HQ4,
-        Self::HQ5,
-        Self::City,
-        Self::Base,
-        Self::Airport,
-        Self::Port,
-        Self::ComTower,
-        Self::Lab,
-        Self::MissileSilo,
-        Self::MissileSiloEmpty,
-    ];
-
-    fn can_have_nation(&self) -> bool {
-        match self {
-            Self::MissileSilo | Self::MissileSiloEmpty => false,
-            _ => true,
-        }
-    }
-}
+pub use rusted_battalions_game_logic::{BuildingClass};
 
 
 pub struct Building {
     pub coord: Coord,
     pub nation: Mutable<Option<Nation>>,
-    pub class: BuildingClass,
+    pub class: Mutable<BuildingClass>,
     pub fog: Mutable<bool>,
 }
 
@@ -74,7 +24,7 @@ impl Building {
     pub fn new(coord: Coord, class: BuildingClass, nation: Option<Nation>) -> Arc<Self> {
         Arc::new(Self {
             coord,
-            class,
+            class: Mutable::new(class),
             nation: Mutable::new(nation),
             fog: Mutable::new(false),
         })
@@ -84,18 +34,18 @@ impl Building {
         self.nation.signal_ref(|nation| nation.is_some()).dedupe()
     }
 
-    fn tile_x(&self, grid: &Arc<Grid>) -> impl Signal<Item = u32> {
-        let can_have_nation = self.class.can_have_nation();
-
+    /// Returns the index of the animation frame.
+    fn frame(&self, grid: &Arc<Grid>) -> impl Signal<Item = u32> {
         map_ref! {
             let fog = self.fog.signal(),
             let has_nation = self.has_nation(),
+            let can_have_nation = self.class.signal_ref(|class| class.can_have_nation()),
             let frame = grid.animation_loop(BUILDING_ANIMATION_TIME, 4) => move {
                 if *fog {
-                    Self::TILE_WIDTH
+                    1
 
-                } else if can_have_nation && *has_nation {
-                    (2 + frame) * Self::TILE_WIDTH
+                } else if *can_have_nation && *has_nation {
+                    2 + frame
 
                 } else {
                     0
@@ -104,23 +54,44 @@ impl Building {
         }.dedupe()
     }
 
-    pub fn render(game: &Arc<Game>, grid: &Arc<Grid>, this: &Arc<Self>) -> Node {
-        let tile_y = match this.class {
-            BuildingClass::HQ1 => 0 * Self::TILE_HEIGHT,
-            BuildingClass::HQ2 => 1 * Self::TILE_HEIGHT,
-            BuildingClass::HQ3 => 2 * Self::TILE_HEIGHT,
-            BuildingClass::HQ4 => 3 * Self::TILE_HEIGHT,
-            BuildingClass::HQ5 => 4 * Self::TILE_HEIGHT,
-            BuildingClass::City => 5 * Self::TILE_HEIGHT,
-            BuildingClass::Base => 6 * Self::TILE_HEIGHT,
-            BuildingClass::Airport => 7 * Self::TILE_HEIGHT,
-            BuildingClass::Port => 8 * Self::TILE_HEIGHT,
-            BuildingClass::ComTower => 9 * Self::TILE_HEIGHT,
-            BuildingClass::Lab => 10 * Self::TILE_HEIGHT,
-            BuildingClass::MissileSilo => 11 * Self::TILE_HEIGHT,
-            BuildingClass::MissileSiloEmpty => 12 * Self::TILE_HEIGHT,
-        };
+    /// The Black Hole structures are not in the spritesheet, so they are not rendered.
+    fn tile_y(class: BuildingClass) -> Option<u32> {
+        match class {
+            BuildingClass::HQ1 => Some(0 * Self::TILE_HEIGHT),
+            BuildingClass::HQ2 => Some(1 * Self::TILE_HEIGHT),
+            BuildingClass::HQ3 => Some(2 * Self::TILE_HEIGHT),
+            BuildingClass::HQ4 => Some(3 * Self::TILE_HEIGHT),
+            BuildingClass::HQ5 => Some(4 * Self::TILE_HEIGHT),
+            BuildingClass::City => Some(5 * Self::TILE_HEIGHT),
+            BuildingClass::Base => Some(6 * Self::TILE_HEIGHT),
+            BuildingClass::Airport => Some(7 * Self::TILE_HEIGHT),
+            BuildingClass::Port => Some(8 * Self::TILE_HEIGHT),
+            BuildingClass::ComTower => Some(9 * Self::TILE_HEIGHT),
+            BuildingClass::Lab => Some(10 * Self::TILE_HEIGHT),
+            BuildingClass::MissileSilo => Some(11 * Self::TILE_HEIGHT),
+            BuildingClass::MissileSiloEmpty => Some(12 * Self::TILE_HEIGHT),
+            _ => None,
+        }
+    }
 
+    /// Whether the building has a sprite in the spritesheet.
+    pub(crate) fn has_sprite(&self) -> bool {
+        Self::tile_y(self.class.get()).is_some()
+    }
+
+    fn tile(class: BuildingClass, frame: u32) -> Tile {
+        let start_x = frame * Self::TILE_WIDTH;
+        let start_y = Self::tile_y(class).unwrap();
+
+        Tile {
+            start_x,
+            start_y,
+            end_x: start_x + Self::TILE_WIDTH,
+            end_y: start_y + Self::TILE_HEIGHT,
+        }
+    }
+
+    pub fn render(game: &Arc<Game>, grid: &Arc<Grid>, this: &Arc<Self>) -> Node {
         let (x, y) = grid.tile_offset(&this.coord);
 
         let offset = Offset {
@@ -133,20 +104,20 @@ impl Building {
             height: ParentHeight(grid.height * 2.0),
         };
 
+        let order = grid.order(&this.coord);
+
         engine::Stack::builder()
             .order(Order::Parent(0.0))
 
             .child(engine::Sprite::builder()
                 .spritesheet(game.spritesheets.building.clone())
 
-                .tile_signal(this.tile_x(grid).map(move |tile_x| {
-                    Tile {
-                        start_x: tile_x,
-                        start_y: tile_y,
-                        end_x: tile_x + Self::TILE_WIDTH,
-                        end_y: tile_y + Self::TILE_HEIGHT,
+                .tile_signal(map_ref! {
+                    let frame = this.frame(grid),
+                    let class = this.class.signal() => {
+                        Self::tile(*class, *frame)
                     }
-                }))
+                })
 
                 .palette_signal(this.nation.signal_ref(|nation| {
                     match nation {
@@ -159,7 +130,7 @@ impl Building {
                     }
                 }))
 
-                .order(Order::Parent(grid.order(&this.coord) + (2.0 / 6.0)))
+                .order(Order::Parent(order + (2.0 / 6.0)))
                 .offset(offset)
                 .size(size)
                 .build())
@@ -167,12 +138,7 @@ impl Building {
             .child(engine::Sprite::builder()
                 .spritesheet(game.spritesheets.building.clone())
 
-                .tile(Tile {
-                    start_x: Self::TILE_WIDTH,
-                    start_y: tile_y,
-                    end_x: Self::TILE_WIDTH + Self::TILE_WIDTH,
-                    end_y: tile_y + Self::TILE_HEIGHT,
-                })
+                .tile_signal(this.class.signal_ref(|class| Self::tile(*class, 1)))
 
                 .palette(0)
 
@@ -187,7 +153,7 @@ impl Building {
                     }
                 }))
 
-                .order(Order::Parent(grid.order(&this.coord) + (3.0 / 6.0)))
+                .order(Order::Parent(order + (3.0 / 6.0)))
                 .offset(offset)
                 .size(size)
                 .build())
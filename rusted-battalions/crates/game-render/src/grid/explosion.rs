@@ -5,6 +5,7 @@ use rusted_battalions_engine::{Node, Size, Offset, Tile, ParentWidth, ParentHeig
 
 use crate::Game;
 use crate::grid::{Grid, Coord};
+use crate::grid::unit::{UnitClass};
 
 
 #[derive(Debug, Clone, Copy)]
@@ -30,9 +31,47 @@ pub enum ExplosionAnimation {
     Air,
     Sea,
     Mega,
+    Ooze,
+    BlackBomb,
 }
 
 impl ExplosionAnimation {
+    pub fn from_unit(class: &UnitClass) -> Self {
+        match class {
+            UnitClass::Infantry |
+            UnitClass::Mech |
+            UnitClass::Recon |
+            UnitClass::APC |
+            UnitClass::Artillery |
+            UnitClass::Tank |
+            UnitClass::AntiAir |
+            UnitClass::Missile |
+            UnitClass::Rocket |
+            UnitClass::MediumTank |
+            UnitClass::Neotank => Self::Land,
+
+            UnitClass::BCopter |
+            UnitClass::TCopter |
+            UnitClass::Fighter |
+            UnitClass::Bomber |
+            UnitClass::Stealth => Self::Air,
+
+            UnitClass::Battleship |
+            UnitClass::Cruiser |
+            UnitClass::Submarine |
+            UnitClass::Lander |
+            UnitClass::Carrier |
+            UnitClass::BlackBoat => Self::Sea,
+
+            UnitClass::MegaTank |
+            UnitClass::Piperunner => Self::Mega,
+
+            UnitClass::Oozium => Self::Ooze,
+
+            UnitClass::BlackBomb => Self::BlackBomb,
+        }
+    }
+
     fn info(&self) -> ExplosionInfo {
         match self {
             Self::Land => ExplosionInfo {
@@ -94,6 +133,38 @@ impl ExplosionAnimation {
 
                 frames: 12,
             },
+
+            // Used when the Oozium is destroyed or when it engulfs a unit, it uses the splash from the Sea row.
+            Self::Ooze => ExplosionInfo {
+                width: 2.0,
+                height: 2.0,
+
+                offset_x: -0.5,
+                offset_y: -1.0,
+
+                tile_x: 0,
+                tile_y: 64,
+                tile_width: 32,
+                tile_height: 32,
+
+                frames: 7,
+            },
+
+            // Uses the Mega row, but it is centered on the tile because the Black Bomb explodes in the air.
+            Self::BlackBomb => ExplosionInfo {
+                width: 7.0,
+                height: 3.0,
+
+                offset_x: -3.0,
+                offset_y: -1.0,
+
+                tile_x: 0,
+                tile_y: 96,
+                tile_width: 112,
+                tile_height: 48,
+
+                frames: 12,
+            },
         }
     }
 }
@@ -143,8 +214,8 @@ impl Explosion {
 
             .apply(|builder| {
                 match this.animation {
-                    // Air explosion is always displayed on top of everything else.
-                    ExplosionAnimation::Air => builder.order(Order::Above(1.0)),
+                    // Air explosions are always displayed on top of everything else.
+                    ExplosionAnimation::Air | ExplosionAnimation::BlackBomb => builder.order(Order::Above(1.0)),
 
                     // Other explosions follow the usual order, so they can be obscured by mountains / forests.
                     _ => builder.order(Order::Parent(grid.order(&this.coord) + (5.0 / 6.0))),
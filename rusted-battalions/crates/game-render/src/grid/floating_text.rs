@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use std::borrow::Cow;
+use futures_signals::signal::{Mutable};
+use rusted_battalions_engine as engine;
+use rusted_battalions_engine::{Node, Offset, Size, CharSize, ColorRgb, Px, ParentWidth, ParentHeight, SmallestWidth, SmallestHeight, Order};
+
+use crate::Game;
+use crate::grid::{Grid, Coord};
+
+
+/// Text which floats upwards above a tile, for example "+7" when a unit is repaired.
+pub struct FloatingText {
+    coord: Coord,
+    text: Cow<'static, str>,
+    pub percent: Mutable<f32>,
+}
+
+impl FloatingText {
+    /// Number of tiles that the text moves upwards.
+    const DISTANCE: f32 = 0.5;
+
+    pub fn new(coord: Coord, text: Cow<'static, str>) -> Arc<Self> {
+        Arc::new(Self {
+            coord,
+            text,
+            percent: Mutable::new(0.0),
+        })
+    }
+
+    pub fn render(game: &Arc<Game>, grid: &Arc<Grid>, this: &Arc<Self>) -> Node {
+        let (x, y) = grid.tile_offset(&this.coord);
+
+        let height = grid.height;
+
+        engine::BitmapText::builder()
+            .text(this.text.clone())
+            .font(game.fonts.unifont.clone())
+
+            .text_color(ColorRgb { r: 1.0, g: 1.0, b: 1.0 })
+
+            .char_size(CharSize {
+                width: Px(8),
+                height: Px(16),
+            })
+
+            .size(Size {
+                width: SmallestWidth(1.0),
+                height: SmallestHeight(1.0),
+            })
+
+            .offset_signal(this.percent.signal_ref(move |percent| {
+                Offset {
+                    x: ParentWidth(x),
+                    y: ParentHeight(y - (height * Self::DISTANCE * percent)),
+                }
+            }))
+
+            .order(Order::Above(1.0))
+
+            .build()
+    }
+}
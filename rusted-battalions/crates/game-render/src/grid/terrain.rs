@@ -1,10 +1,14 @@
 use std::sync::Arc;
-use futures_signals::signal::{SignalExt};
+use futures_signals::signal::{SignalExt, Mutable, always};
 use rusted_battalions_engine as engine;
 use rusted_battalions_engine::{SpriteBuilder, Size, Offset, Tile, Node, ParentWidth, ParentHeight, Order};
 
 use crate::grid::{Game, Grid, Coord, TERRAIN_ANIMATION_TIME, FOG_ANIMATION_TIME};
 use crate::util::random::{random};
+use crate::grid::weather::{terrain_palette};
+
+pub use rusted_battalions_game_logic::{TerrainClass, Orientation};
+use rusted_battalions_game_logic::{Map};
 
 mod sea;
 mod river;
@@ -124,7 +128,7 @@ pub struct Terrain {
 }
 
 impl Terrain {
-    /*pub fn from_map(map: &Map) -> Self {
+    pub fn from_map(map: &Map) -> Self {
         let tiles = map.tiles().map(|tile| {
             TerrainTile::new(tile.coord.x, tile.coord.y, tile.class)
         }).collect();
@@ -138,7 +142,7 @@ impl Terrain {
         terrain.update_tiles();
 
         terrain
-    }*/
+    }
 
 
     pub fn new(width: u32, height: u32) -> Self {
@@ -208,40 +212,40 @@ impl Terrain {
                 let mut adjacent = Adjacent::default();
 
                 if let Some(down) = self.get_checked(tile.x, tile.y + 1) {
-                    adjacent.down = down.class.flag();
+                    adjacent.down = TerrainFlag::from_tile(&down.class);
                 }
 
                 if let Some(right) = self.get_checked(tile.x + 1, tile.y) {
-                    adjacent.right = right.class.flag();
+                    adjacent.right = TerrainFlag::from_tile(&right.class);
                 }
 
                 if let Some(down_right) = self.get_checked(tile.x + 1, tile.y + 1) {
-                    adjacent.down_right = down_right.class.flag();
+                    adjacent.down_right = TerrainFlag::from_tile(&down_right.class);
                 }
 
                 if let Some(x) = tile.x.checked_sub(1) {
                     if let Some(left) = self.get_checked(x, tile.y) {
-                        adjacent.left = left.class.flag();
+                        adjacent.left = TerrainFlag::from_tile(&left.class);
                     }
 
                     if let Some(down_left) = self.get_checked(x, tile.y + 1) {
-                        adjacent.down_left = down_left.class.flag();
+                        adjacent.down_left = TerrainFlag::from_tile(&down_left.class);
                     }
 
                     if let Some(y) = tile.y.checked_sub(1) {
                         if let Some(up_left) = self.get_checked(x, y) {
-                            adjacent.up_left = up_left.class.flag();
+                            adjacent.up_left = TerrainFlag::from_tile(&up_left.class);
                         }
                     }
                 }
 
                 if let Some(y) = tile.y.checked_sub(1) {
                     if let Some(up) = self.get_checked(tile.x, y) {
-                        adjacent.up = up.class.flag();
+                        adjacent.up = TerrainFlag::from_tile(&up.class);
                     }
 
                     if let Some(up_right) = self.get_checked(tile.x + 1, y) {
-                        adjacent.up_right = up_right.class.flag();
+                        adjacent.up_right = TerrainFlag::from_tile(&up_right.class);
                     }
                 }
 
@@ -253,7 +257,7 @@ impl Terrain {
             .collect::<Vec<Change>>();
 
         for change in changes {
-            self.tiles[change.index].info = change.info;
+            self.tiles[change.index].info.set(change.info);
         }
     }
 }
@@ -435,74 +439,9 @@ impl TerrainRule {
 }
 
 
-#[derive(Debug, Clone, Copy)]
-pub enum Orientation {
-    Horizontal,
-    Vertical,
-}
-
-
-#[derive(Debug, Clone, Copy)]
-pub enum TerrainClass {
-    Empty,
-    Grass,
-    Road {
-        ruins: bool,
-    },
-    Bridge {
-        orientation: Orientation,
-    },
-    Forest,
-    Mountain {
-        variant: u32,
-    },
-    Pipeline,
-    Pipeseam {
-        destroyed: bool,
-    },
-    Ocean,
-    River,
-    Shoal,
-    Reef,
-}
-
-impl TerrainClass {
-    pub const ALL: &[Self] = &[
-        Self::Empty,
-        Self::Grass,
-        Self::Road { ruins: false },
-        Self::Bridge { orientation: Orientation::Horizontal },
-        Self::Forest,
-        Self::Mountain { variant: 0 },
-        Self::Pipeline,
-        Self::Pipeseam { destroyed: false, },
-        Self::Ocean,
-        Self::River,
-        Self::Shoal,
-        Self::Reef,
-    ];
-
-    fn flag(&self) -> TerrainFlag {
-        match self {
-            Self::Empty => TerrainFlag::EMPTY,
-            Self::Grass => TerrainFlag::PLAIN,
-            Self::Road { .. } => TerrainFlag::ROAD,
-            Self::Bridge { .. } => TerrainFlag::BRIDGE,
-            Self::Forest => TerrainFlag::WOOD,
-            Self::Mountain { .. } => TerrainFlag::MOUNTAIN,
-            Self::Pipeline => TerrainFlag::PIPELINE,
-            Self::Pipeseam { .. } => TerrainFlag::PIPESEAM,
-            Self::Ocean => TerrainFlag::SEA,
-            Self::River => TerrainFlag::RIVER,
-            Self::Shoal => TerrainFlag::SHOAL,
-            Self::Reef => TerrainFlag::REEF,
-        }
-    }
-
-    pub fn random_mountain() -> Self {
-        Self::Mountain {
-            variant: (random() * 3.0) as u32,
-        }
+pub fn random_mountain() -> TerrainClass {
+    TerrainClass::Mountain {
+        variant: (random() * 3.0) as u32,
     }
 }
 
@@ -768,7 +707,9 @@ pub struct TerrainTile {
     pub x: u32,
     pub y: u32,
     pub class: TerrainClass,
-    info: TileInfo,
+
+    /// This is changed by [`Terrain::update_tiles`], for example when a pipeseam is destroyed.
+    info: Mutable<TileInfo>,
 }
 
 impl TerrainTile {
@@ -777,7 +718,7 @@ impl TerrainTile {
             x,
             y,
             class,
-            info: TileInfo::ERROR,
+            info: Mutable::new(TileInfo::ERROR),
         }
     }
 
@@ -786,12 +727,13 @@ impl TerrainTile {
             x,
             y,
             class: TerrainClass::Empty,
-            info: TileInfo::ERROR,
+            info: Mutable::new(TileInfo::ERROR),
         }
     }
 
     pub fn render(game: &Arc<Game>, grid: &Arc<Grid>, this: &Self) -> Node {
-        let info = this.info;
+        // The size of the tile never changes, only its position in the spritesheet
+        let info = this.info.get();
 
         let coord = Coord {
             x: this.x as f32,
@@ -812,8 +754,10 @@ impl TerrainTile {
             height: ParentHeight(grid.height * ratio),
         };
 
-        fn tile_animation(grid: &Arc<Grid>, info: TileInfo) -> impl FnOnce(SpriteBuilder) -> SpriteBuilder + '_ {
-            move |builder| {
+        fn tile_animation(grid: &Arc<Grid>, info: &Mutable<TileInfo>) -> impl FnOnce(SpriteBuilder) -> SpriteBuilder {
+            let grid = grid.clone();
+
+            let signal = info.signal().switch(move |info| {
                 let TileInfo { tile_x, tile_y, tile_width, tile_height, frame_info } = info;
 
                 let mut tile = Tile {
@@ -824,16 +768,18 @@ impl TerrainTile {
                 };
 
                 if let Some(frame_info) = frame_info {
-                    builder.tile_signal(grid.animation_pendulum(TERRAIN_ANIMATION_TIME, frame_info.frames).map(move |frame| {
+                    grid.animation_pendulum(TERRAIN_ANIMATION_TIME, frame_info.frames).map(move |frame| {
                         tile.start_y = tile_y + (frame * frame_info.offset_y);
                         tile.end_y = tile.start_y + tile_width;
                         tile
-                    }))
+                    }).boxed_local()
 
                 } else {
-                    builder.tile(tile)
+                    always(tile).boxed_local()
                 }
-            }
+            });
+
+            move |builder| builder.tile_signal(signal)
         }
 
         engine::Stack::builder()
@@ -841,11 +787,11 @@ impl TerrainTile {
 
             .child(engine::Sprite::builder()
                 .spritesheet(game.spritesheets.terrain.clone())
-                .apply(tile_animation(grid, this.info))
+                .apply(tile_animation(grid, &this.info))
                 .order(Order::Parent(grid.order(&coord)))
                 .offset(offset)
                 .size(size)
-                .palette(0)
+                .palette_signal(grid.weather.signal().map(terrain_palette))
                 .build())
 
             .child(engine::Sprite::builder()
@@ -860,14 +806,16 @@ impl TerrainTile {
                     }
                 }))*/
 
-                .alpha(if coord.x > 16.0 {
-                    1.0
-                } else {
-                    0.0
-                })
+                .alpha_signal(grid.fog(&coord).map(|fog| {
+                    if fog {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }))
 
                 .spritesheet(game.spritesheets.terrain.clone())
-                .apply(tile_animation(grid, this.info))
+                .apply(tile_animation(grid, &this.info))
                 .order(Order::Parent(grid.order(&coord) + (1.0 / 6.0)))
                 .offset(offset)
                 .size(size)
@@ -3,150 +3,54 @@ use futures_signals::map_ref;
 use futures_signals::signal::{Mutable, Signal, SignalExt};
 use dominator::clone;
 use rusted_battalions_engine as engine;
-use rusted_battalions_engine::{Node, Size, Offset, Tile, ParentWidth, ParentHeight, Order};
+use rusted_battalions_engine::{Node, Size, Offset, Tile, CharSize, ColorRgb, ParentWidth, ParentHeight, SmallestWidth, SmallestHeight, Order};
 
 use crate::Game;
 use crate::grid::{UNIT_ANIMATION_TIME, FOG_ANIMATION_TIME, Grid, Coord, Nation};
-use crate::grid::explosion::{ExplosionAnimation};
-
-
-#[derive(Debug, Clone, Copy)]
-pub enum UnitClass {
-    Infantry,
-    Mech,
-    Recon,
-    APC,
-    Artillery,
-    Tank,
-    AntiAir,
-    Missile,
-    Rocket,
-    MediumTank,
-    Piperunner,
-    Neotank,
-    MegaTank,
-    BCopter,
-    TCopter,
-    Fighter,
-    Bomber,
-    Stealth,
-    Battleship,
-    Cruiser,
-    Submarine,
-    Lander,
-    Carrier,
-    BlackBoat,
-    BlackBomb,
-    Oozium,
-}
-
-impl UnitClass {
-    pub const ALL: &[Self] = &[
-        Self::Infantry,
-        Self::Mech,
-        Self::Recon,
-        Self::APC,
-        Self::Artillery,
-        Self::Tank,
-        Self::AntiAir,
-        Self::Missile,
-        Self::Rocket,
-        Self::MediumTank,
-        Self::Piperunner,
-        Self::Neotank,
-        Self::MegaTank,
-        Self::BCopter,
-        Self::TCopter,
-        Self::Fighter,
-        Self::Bomber,
-        Self::Stealth,
-        Self::Battleship,
-        Self::Cruiser,
-        Self::Submarine,
-        Self::Lander,
-        Self::Carrier,
-        Self::BlackBoat,
-        Self::BlackBomb,
-        Self::Oozium,
-    ];
-
-    fn tile_y(&self, nation: &Nation) -> u32 {
-        match self {
-            Self::Infantry => match nation {
-                Nation::OrangeStar => 0,
-                Nation::BlueMoon => 1,
-                Nation::GreenEarth => 2,
-                Nation::YellowComet => 3,
-                Nation::BlackHole => 4,
-            },
-            Self::Mech => match nation {
-                Nation::OrangeStar => 5,
-                Nation::BlueMoon => 6,
-                Nation::GreenEarth => 7,
-                Nation::YellowComet => 8,
-                Nation::BlackHole => 9,
-            },
-            Self::Recon => 10,
-            Self::Tank => 11,
-            Self::MediumTank => 12,
-            Self::Neotank => 13,
-            Self::MegaTank => 14,
-            Self::APC => 15,
-            Self::AntiAir => 16,
-            Self::Artillery => 17,
-            Self::Rocket => 18,
-            Self::Missile => 19,
-            Self::Piperunner => 20,
-            Self::Oozium => 21,
-            Self::Fighter => 22,
-            Self::Bomber => 23,
-            Self::BlackBomb => 24,
-            Self::Stealth => 25,
-            Self::BCopter => 26,
-            Self::TCopter => 27,
-            Self::Battleship => 28,
-            Self::Cruiser => 29,
-            Self::Submarine => 30,
-            Self::Lander => 31,
-            Self::BlackBoat => 32,
-            Self::Carrier => 33,
-        }
-    }
 
-    pub fn explosion_animation(&self) -> ExplosionAnimation {
-        match self {
-            UnitClass::Infantry |
-            UnitClass::Mech |
-            UnitClass::Recon |
-            UnitClass::APC |
-            UnitClass::Artillery |
-            UnitClass::Tank |
-            UnitClass::AntiAir |
-            UnitClass::Missile |
-            UnitClass::Rocket |
-            UnitClass::MediumTank |
-            UnitClass::Neotank => ExplosionAnimation::Land,
-
-            UnitClass::BCopter |
-            UnitClass::TCopter |
-            UnitClass::Fighter |
-            UnitClass::Bomber |
-            UnitClass::Stealth |
-            UnitClass::BlackBomb => ExplosionAnimation::Air,
-
-            UnitClass::Battleship |
-            UnitClass::Cruiser |
-            UnitClass::Submarine |
-            UnitClass::Lander |
-            UnitClass::Carrier |
-            UnitClass::BlackBoat => ExplosionAnimation::Sea,
-
-            UnitClass::MegaTank |
-            UnitClass::Piperunner => ExplosionAnimation::Mega,
-
-            // TODO
-            UnitClass::Oozium => ExplosionAnimation::Mega,
-        }
+pub use rusted_battalions_game_logic::{UnitClass};
+
+
+fn unit_tile_y(class: &UnitClass, nation: &Nation) -> u32 {
+    match class {
+        UnitClass::Infantry => match nation {
+            Nation::OrangeStar => 0,
+            Nation::BlueMoon => 1,
+            Nation::GreenEarth => 2,
+            Nation::YellowComet => 3,
+            Nation::BlackHole => 4,
+        },
+        UnitClass::Mech => match nation {
+            Nation::OrangeStar => 5,
+            Nation::BlueMoon => 6,
+            Nation::GreenEarth => 7,
+            Nation::YellowComet => 8,
+            Nation::BlackHole => 9,
+        },
+        UnitClass::Recon => 10,
+        UnitClass::Tank => 11,
+        UnitClass::MediumTank => 12,
+        UnitClass::Neotank => 13,
+        UnitClass::MegaTank => 14,
+        UnitClass::APC => 15,
+        UnitClass::AntiAir => 16,
+        UnitClass::Artillery => 17,
+        UnitClass::Rocket => 18,
+        UnitClass::Missile => 19,
+        UnitClass::Piperunner => 20,
+        UnitClass::Oozium => 21,
+        UnitClass::Fighter => 22,
+        UnitClass::Bomber => 23,
+        UnitClass::BlackBomb => 24,
+        UnitClass::Stealth => 25,
+        UnitClass::BCopter => 26,
+        UnitClass::TCopter => 27,
+        UnitClass::Battleship => 28,
+        UnitClass::Cruiser => 29,
+        UnitClass::Submarine => 30,
+        UnitClass::Lander => 31,
+        UnitClass::BlackBoat => 32,
+        UnitClass::Carrier => 33,
     }
 }
 
@@ -204,17 +108,34 @@ pub struct Unit {
     pub alpha: Mutable<f32>,
     pub animation: Mutable<UnitAnimation>,
     pub waited: Mutable<bool>,
+
+    /// Whether the transport is holding any units.
+    pub loaded: Mutable<bool>,
+
+    /// Whether a Stealth is hidden or a Submarine is dived.
+    pub hidden: Mutable<bool>,
+
     pub nation: Nation,
     pub class: UnitClass,
 }
 
 impl Unit {
+    /// The dialog box frame in the hud spritesheet, it is the same frame which is used by the UI dialog boxes.
+    const LOADED_BADGE: Tile = Tile {
+        start_x: 11,
+        start_y: 59,
+        end_x: 37,
+        end_y: 85,
+    };
+
     pub fn new(coord: Coord, class: UnitClass, nation: Nation) -> Arc<Self> {
         Arc::new(Self {
             coord: Mutable::new(coord),
             alpha: Mutable::new(1.0),
             animation: Mutable::new(UnitAnimation::Idle),
             waited: Mutable::new(false),
+            loaded: Mutable::new(false),
+            hidden: Mutable::new(false),
             nation,
             class,
         })
@@ -230,10 +151,98 @@ impl Unit {
         self.animation.signal_ref(move |animation| animation.direction(&nation)).dedupe()
     }
 
+    /// Small badge with an "L" in the bottom left corner of the tile.
+    fn render_loaded(game: &Arc<Game>, grid: &Arc<Grid>, this: &Arc<Self>) -> Node {
+        let offset = |grid: &Arc<Grid>, coord: &Coord, shift: f32| {
+            let (x, y) = grid.tile_offset(coord);
+
+            Offset {
+                x: ParentWidth(x + (grid.width * shift)),
+                y: ParentHeight(y + (grid.height * 0.5)),
+            }
+        };
+
+        let order = |grid: &Arc<Grid>, coord: &Coord| {
+            Order::Parent(grid.order(coord) + (4.5 / 6.0))
+        };
+
+        engine::Stack::builder()
+            .order(Order::Parent(0.0))
+
+            // The text does not have an alpha, so the badge is hidden instead
+            .visible_signal(map_ref! {
+                let alpha = this.alpha.signal(),
+                let fog = grid.unit_fog(this) => {
+                    !*fog && *alpha > 0.0
+                }
+            })
+
+            .child(engine::Sprite::builder()
+                .spritesheet(game.spritesheets.hud.clone())
+                .tile(Self::LOADED_BADGE)
+
+                .offset_signal(this.coord.signal_ref(clone!(grid => move |coord| offset(&grid, coord, 0.0))))
+
+                .size(Size {
+                    width: ParentWidth(grid.width * 0.5),
+                    height: ParentHeight(grid.height * 0.5),
+                })
+
+                .order_signal(this.coord.signal_ref(clone!(grid => move |coord| order(&grid, coord))).dedupe())
+
+                .alpha_signal(this.alpha.signal())
+
+                .build())
+
+            .child(engine::BitmapText::builder()
+                .text("L".into())
+                .font(game.fonts.unifont.clone())
+
+                .text_color(ColorRgb { r: 1.0, g: 1.0, b: 1.0 })
+
+                .char_size(CharSize {
+                    width: ParentWidth(grid.width * 0.25),
+                    height: ParentHeight(grid.height * 0.5),
+                })
+
+                // The character is half as wide as the badge, so it is shifted to the center
+                .offset_signal(this.coord.signal_ref(clone!(grid => move |coord| offset(&grid, coord, 0.125))))
+
+                .size(Size {
+                    width: SmallestWidth(1.0),
+                    height: SmallestHeight(1.0),
+                })
+
+                .order_signal(this.coord.signal_ref(clone!(grid => move |coord| order(&grid, coord))).dedupe())
+
+                .build())
+
+            .build()
+    }
+
     pub fn render(game: &Arc<Game>, grid: &Arc<Grid>, this: &Arc<Self>) -> Node {
+        engine::Stack::builder()
+            .order(Order::Parent(0.0))
+
+            .child(Self::render_unit(game, grid, this))
+
+            // The badge is only created while the transport is loaded
+            .child_signal(this.loaded.signal().map(clone!(game, grid, this => move |loaded| {
+                if loaded {
+                    Some(Self::render_loaded(&game, &grid, &this))
+
+                } else {
+                    None
+                }
+            })))
+
+            .build()
+    }
+
+    fn render_unit(game: &Arc<Game>, grid: &Arc<Grid>, this: &Arc<Self>) -> Node {
         let nation = this.nation;
 
-        let tile_y = this.class.tile_y(&nation);
+        let tile_y = unit_tile_y(&this.class, &nation);
 
         engine::Sprite::builder()
             .spritesheet_signal(game.unit_spritesheet())
@@ -256,7 +265,16 @@ impl Unit {
                 Order::Parent(grid.order(coord) + (4.0 / 6.0))
             })).dedupe())
 
-            .alpha_signal(this.alpha.signal())
+            .alpha_signal(map_ref! {
+                let alpha = this.alpha.signal(),
+                let fog = grid.unit_fog(this) => {
+                    if *fog {
+                        0.0
+                    } else {
+                        *alpha
+                    }
+                }
+            })
 
             /*.alpha_signal(grid.animation(FOG_ANIMATION_TIME).map(move |time| {
                 let time = (time % 2.0) as f32;
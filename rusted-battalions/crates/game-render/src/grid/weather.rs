@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use futures_signals::map_ref;
+use futures_signals::signal::{SignalExt};
+use rusted_battalions_engine as engine;
+use rusted_battalions_engine::{RgbaImage, Node, Size, Offset, CharSize, ColorRgb, ParentWidth, ParentHeight, Order};
+
+use crate::Game;
+use crate::grid::{Grid, WEATHER_ANIMATION_TIME};
+
+pub use rusted_battalions_game_logic::{Weather};
+
+
+/// The terrain palette has the normal colors in row 0 and the fog colors in row 1,
+/// the weather palettes are added after them.
+const WEATHER_PALETTE_START: u32 = 2;
+
+/// The overlay pattern repeats every 11 characters, so it loops after 11 frames.
+const OVERLAY_FRAMES: u32 = 11;
+
+
+/// The row of the terrain palette which is used for the weather.
+pub(crate) fn terrain_palette(weather: Weather) -> u32 {
+    match weather {
+        Weather::Clear => 0,
+        Weather::Rain => WEATHER_PALETTE_START,
+        Weather::Snow => WEATHER_PALETTE_START + 1,
+        Weather::Sandstorm => WEATHER_PALETTE_START + 2,
+    }
+}
+
+/// Blends the color towards the weather's color.
+fn tint(weather: Weather, color: image::Rgba<u8>) -> image::Rgba<u8> {
+    let (tint, amount) = match weather {
+        Weather::Clear => return color,
+        Weather::Rain => ([40, 60, 110], 0.35),
+        Weather::Snow => ([235, 240, 255], 0.5),
+        Weather::Sandstorm => ([210, 170, 100], 0.4),
+    };
+
+    let [r, g, b, a] = color.0;
+
+    let blend = |from: u8, to: u8| {
+        (from as f32 + (to as f32 - from as f32) * amount).round() as u8
+    };
+
+    image::Rgba([blend(r, tint[0]), blend(g, tint[1]), blend(b, tint[2]), a])
+}
+
+/// Adds a row to the terrain palette for every weather, they are tinted versions of the normal colors.
+pub(crate) fn weather_palettes(palette: &RgbaImage) -> RgbaImage {
+    let width = palette.image.width();
+    let height = WEATHER_PALETTE_START + (Weather::ALL.len() as u32 - 1);
+
+    RgbaImage::from_fn("terrain_palette", width, height, |x, y| {
+        if y < WEATHER_PALETTE_START {
+            *palette.image.get_pixel(x, y)
+
+        } else {
+            let weather = Weather::ALL.iter()
+                .copied()
+                .find(|weather| terrain_palette(*weather) == y)
+                .unwrap();
+
+            tint(weather, *palette.image.get_pixel(x, 0))
+        }
+    })
+}
+
+/// The character which is scattered over the grid, and the direction that it moves in.
+fn overlay_char(weather: Weather) -> Option<(char, i64, i64)> {
+    match weather {
+        Weather::Clear => None,
+        Weather::Rain => Some(('/', -1, 1)),
+        Weather::Snow => Some(('*', 0, 1)),
+        Weather::Sandstorm => Some(('~', 1, 0)),
+    }
+}
+
+fn overlay_color(weather: Weather) -> ColorRgb {
+    match weather {
+        Weather::Rain => ColorRgb { r: 0.6, g: 0.7, b: 1.0 },
+        Weather::Sandstorm => ColorRgb { r: 0.9, g: 0.75, b: 0.5 },
+        _ => ColorRgb { r: 1.0, g: 1.0, b: 1.0 },
+    }
+}
+
+/// Scatters the character in a diagonal pattern, every frame moves the pattern by 1 character.
+fn overlay_text(weather: Weather, columns: u32, rows: u32, frame: u32) -> String {
+    let (character, x_step, y_step) = match overlay_char(weather) {
+        Some(info) => info,
+        None => return String::new(),
+    };
+
+    let frame = frame as i64;
+
+    let mut text = String::with_capacity(((columns + 1) * rows) as usize);
+
+    for y in 0..rows {
+        for x in 0..columns {
+            let x = x as i64 - (x_step * frame);
+            let y = y as i64 - (y_step * frame);
+
+            if ((5 * x) + (3 * y)).rem_euclid(OVERLAY_FRAMES as i64) == 0 {
+                text.push(character);
+
+            } else {
+                text.push(' ');
+            }
+        }
+
+        text.push('\n');
+    }
+
+    text
+}
+
+
+/// Animated rain / snow / sand which is drawn over the entire grid, above the units.
+///
+/// It uses characters from the unifont font, every tile is 2 characters wide.
+pub(crate) fn render(game: &Arc<Game>, grid: &Arc<Grid>) -> Node {
+    let (columns, rows) = {
+        let terrain = grid.terrain.lock().unwrap();
+        (terrain.width * 2, terrain.height)
+    };
+
+    engine::BitmapText::builder()
+        .font(game.fonts.unifont.clone())
+
+        .text_signal(map_ref! {
+            let weather = grid.weather.signal(),
+            let frame = grid.animation_loop(WEATHER_ANIMATION_TIME, OVERLAY_FRAMES) => {
+                overlay_text(*weather, columns, rows, *frame).into()
+            }
+        })
+
+        .text_color_signal(grid.weather.signal().map(overlay_color))
+
+        .char_size(CharSize {
+            width: ParentWidth(grid.width * 0.5),
+            height: ParentHeight(grid.height),
+        })
+
+        .offset(Offset {
+            x: ParentWidth(0.0),
+            y: ParentHeight(0.0),
+        })
+
+        .size(Size {
+            width: ParentWidth(1.0),
+            height: ParentHeight(1.0),
+        })
+
+        .order(Order::Above(1.0))
+
+        .build()
+}
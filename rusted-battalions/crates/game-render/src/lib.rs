@@ -355,7 +355,7 @@ impl Game {
             let palette = Texture::new();
 
             texture.load(&mut engine, &terrain_small);
-            palette.load(&mut engine, &terrain_palette);
+            palette.load(&mut engine, &grid::weather::weather_palettes(&terrain_palette));
 
             self.spritesheets.terrain.load(&mut engine, SpritesheetSettings {
                 texture: &texture,
@@ -477,6 +477,8 @@ impl Game {
             use grid::unit::{Unit, UnitClass};
             use grid::action::MoveDirection;
             use grid::explosion::ExplosionAnimation;
+            use grid::weather::Weather;
+            use rusted_battalions_game_logic as logic;
             use util::random::random;
 
             grid.spawn_future(clone!(grid => async move {
@@ -553,6 +555,32 @@ impl Game {
                 grid.units.insert(megatank.clone());
             }));
 
+            // The neutral city is turned over to Black Hole, and then it becomes neutral again
+            grid.spawn_future(clone!(grid => async move {
+                let city = logic::Coord::new(16, 11);
+
+                loop {
+                    grid.wait(4000.0).await;
+
+                    grid.capture_building(&logic::Event::TurnedOver {
+                        coord: city,
+                        nation: Nation::BlackHole,
+                    });
+
+                    grid.wait(4000.0).await;
+                    grid.set_building_nation(&city, None);
+                }
+            }));
+
+            grid.spawn_future(clone!(grid => async move {
+                loop {
+                    for weather in Weather::ALL {
+                        grid.set_weather(*weather);
+                        grid.wait(5000.0).await;
+                    }
+                }
+            }));
+
             /*grid.spawn_futures([(1.0, 2.0)].into_iter().map(|(x, y)| {
                 clone!(grid => async move {
                     loop {
@@ -599,10 +627,11 @@ impl Game {
             }));
 
 
-            grid.spawn_futures(grid.terrain.iter().map(|tile| {
-                let x = tile.x as f32;
-                let y = tile.y as f32;
+            let coords: Vec<(f32, f32)> = grid.terrain.lock().unwrap().iter()
+                .map(|tile| (tile.x as f32, tile.y as f32))
+                .collect();
 
+            grid.spawn_futures(coords.into_iter().map(|(x, y)| {
                 clone!(grid => async move {
                     let amount = (random() * 4.0) as u32;
 